@@ -0,0 +1,8 @@
+fn main() {
+    // `cargo fuzz` builds the crate with `--cfg fuzzing`, see src/fuzzing.rs
+    println!("cargo:rustc-check-cfg=cfg(fuzzing)");
+
+    // Node.js addons resolve the napi symbols at load time
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
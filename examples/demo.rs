@@ -4,13 +4,12 @@ fn main() {
     better_panic::install();
 
     let word_count: KeyPhraseType = KeyPhraseType::Words12;
-    let lang: Language = Language::English;
     let lang: Language = Language::ChineseSimplified;
     let keyphrase: KeyPhrase = KeyPhrase::new(word_count, lang);
     let phrase: &str = keyphrase.phrase();
     println!("{:#?}", phrase);
 
-    let seed: Seed = Seed::new(&keyphrase, "");
+    let seed: Seed = Seed::new_bip39(&keyphrase, "");
     println!("{:X}", seed);
 
     // get the HD wallet seed as raw bytes
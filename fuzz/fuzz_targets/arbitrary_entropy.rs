@@ -0,0 +1,8 @@
+#![no_main]
+
+use keyphrase::ArbitraryEntropy;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ArbitraryEntropy| {
+    keyphrase_fuzz::check_arbitrary_entropy(&input);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use keyphrase::ArbitraryPhrase;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ArbitraryPhrase| {
+    keyphrase_fuzz::check_arbitrary_phrase(&input);
+});
@@ -0,0 +1,351 @@
+//! Checks shared by the fuzz targets of the keyphrase crate.
+//!
+//! Every check takes the raw fuzzer input, feeds it to one of the parsing paths and asserts the
+//! invariants that must hold for whatever that path accepts. They are kept out of the targets so
+//! that they can be reused from other harnesses (honggfuzz, AFL, proptest) against a pinned
+//! version of the crate.
+//!
+//! Run them with `cargo fuzz run <target>` from the `fuzz` directory.
+
+use keyphrase::keystore::{self, KdfParams};
+use keyphrase::qr::{QrMatrix, QrPayload};
+use keyphrase::{
+    compress, fuzzing, ur, ArbitraryEntropy, ArbitraryPhrase, BytewordsStyle, KeyPhrase, Language,
+};
+use std::convert::TryFrom;
+use unicode_normalization::UnicodeNormalization;
+
+/// All the languages enabled in the build of the crate under test
+pub fn languages() -> Vec<Language> {
+    vec![
+        Language::English,
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified,
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional,
+        #[cfg(feature = "french")]
+        Language::French,
+        #[cfg(feature = "italian")]
+        Language::Italian,
+        #[cfg(feature = "japanese")]
+        Language::Japanese,
+        #[cfg(feature = "korean")]
+        Language::Korean,
+        #[cfg(feature = "spanish")]
+        Language::Spanish,
+    ]
+}
+
+/// Split the input into a language selector and the remaining payload
+pub fn split_language(data: &[u8]) -> Option<(Language, &[u8])> {
+    let (first, rest) = data.split_first()?;
+    let languages: Vec<Language> = languages();
+
+    Some((languages[*first as usize % languages.len()], rest))
+}
+
+/// Split the input into a bytewords style selector and the remaining payload
+pub fn split_bytewords_style(data: &[u8]) -> Option<(BytewordsStyle, &[u8])> {
+    let (first, rest) = data.split_first()?;
+    let styles: [BytewordsStyle; 3] = [
+        BytewordsStyle::Standard,
+        BytewordsStyle::Uri,
+        BytewordsStyle::Minimal,
+    ];
+
+    Some((styles[*first as usize % styles.len()], rest))
+}
+
+/// Normalize a phrase to NFKD words separated by single spaces, the form phrases are compared in
+pub fn normalize(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(|word: &str| word.nfkd().collect::<String>())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// A phrase accepted by `KeyPhrase::from_phrase` must re-encode to the same phrase, once
+/// normalized
+pub fn check_from_phrase(data: &[u8]) {
+    let (lang, payload) = match split_language(data) {
+        Some(split) => split,
+        None => return,
+    };
+    let phrase: &str = match std::str::from_utf8(payload) {
+        Ok(phrase) => phrase,
+        Err(_) => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::from_phrase(phrase, lang) {
+        assert_eq!(normalize(keyphrase.phrase()), normalize(phrase));
+        assert!(KeyPhrase::validate(phrase, lang).is_ok());
+
+        let reencoded: KeyPhrase = KeyPhrase::from_entropy(keyphrase.entropy(), lang)
+            .expect("entropy of a valid phrase is valid");
+
+        assert_eq!(normalize(reencoded.phrase()), normalize(phrase));
+    } else {
+        assert!(KeyPhrase::validate(phrase, lang).is_err());
+    }
+}
+
+/// Same as `check_from_phrase`, for the non-standard phrase lengths
+pub fn check_from_phrase_nonstandard(data: &[u8]) {
+    let (lang, payload) = match split_language(data) {
+        Some(split) => split,
+        None => return,
+    };
+    let phrase: &str = match std::str::from_utf8(payload) {
+        Ok(phrase) => phrase,
+        Err(_) => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::from_phrase_nonstandard(phrase, lang) {
+        assert_eq!(keyphrase.phrase(), phrase);
+
+        let reencoded: KeyPhrase = KeyPhrase::from_entropy_nonstandard(keyphrase.entropy(), lang)
+            .expect("entropy of a valid phrase is valid");
+
+        assert_eq!(reencoded.phrase(), phrase);
+    }
+}
+
+/// Entropy accepted by `KeyPhrase::from_entropy` must survive a round trip through the phrase
+pub fn check_from_entropy(data: &[u8]) {
+    let (lang, entropy) = match split_language(data) {
+        Some(split) => split,
+        None => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::from_entropy(entropy, lang) {
+        assert_eq!(keyphrase.entropy(), entropy);
+
+        let parsed: KeyPhrase = KeyPhrase::from_phrase(keyphrase.phrase(), lang)
+            .expect("generated phrase is valid");
+
+        assert_eq!(parsed.entropy(), entropy);
+    }
+}
+
+/// Phrase sets accepted by `compress::decode` must survive a round trip through `encode`
+pub fn check_compress_decode(data: &[u8]) {
+    if let Ok(phrases) = compress::decode(data) {
+        let encoded: Vec<u8> = compress::encode(&phrases).expect("decoded phrases are standard");
+        let decoded: Vec<KeyPhrase> = compress::decode(&encoded).expect("encoded phrases decode");
+
+        assert_eq!(phrases.len(), decoded.len());
+
+        for (original, decoded) in phrases.iter().zip(decoded.iter()) {
+            assert_eq!(original.phrase(), decoded.phrase());
+        }
+    }
+}
+
+/// Same as `check_from_phrase`, for phrases of wordlist words of a valid length
+pub fn check_arbitrary_phrase(input: &ArbitraryPhrase) {
+    match KeyPhrase::from_phrase(input.phrase.as_str(), input.lang) {
+        Ok(keyphrase) => {
+            let reencoded: KeyPhrase = KeyPhrase::from_entropy(keyphrase.entropy(), input.lang)
+                .expect("entropy of a valid phrase is valid");
+
+            assert_eq!(reencoded.phrase(), input.phrase);
+        }
+        Err(_) => assert!(KeyPhrase::validate(&input.phrase, input.lang).is_err()),
+    }
+}
+
+/// Entropy of a valid length must always be accepted and survive a round trip
+pub fn check_arbitrary_entropy(input: &ArbitraryEntropy) {
+    let keyphrase: KeyPhrase = KeyPhrase::from_entropy(&input.entropy, input.lang)
+        .expect("entropy of a valid length is valid");
+
+    assert_eq!(
+        keyphrase.phrase().split(' ').count(),
+        input.keyphrase_type.word_count()
+    );
+
+    let parsed: KeyPhrase =
+        KeyPhrase::from_phrase(keyphrase.phrase(), input.lang).expect("generated phrase is valid");
+
+    assert_eq!(parsed.entropy(), input.entropy.as_slice());
+}
+
+/// Bytes split into 11 bit words and packed back must give the same bits
+pub fn check_bits(data: &[u8]) {
+    let (words, packed): (Vec<u16>, Vec<u8>) = fuzzing::bits_round_trip(data);
+    let bits: usize = words.len() * 11;
+
+    assert_eq!(words.len(), data.len() * 8 / 11);
+    assert!(words.iter().all(|&word: &u16| word < 2048));
+    assert_eq!(packed.len(), (bits + 7) / 8);
+    assert_eq!(packed[..bits / 8], data[..bits / 8]);
+
+    if bits % 8 != 0 {
+        let mask: u8 = !(0xff >> (bits % 8));
+
+        assert_eq!(packed[bits / 8], data[bits / 8] & mask);
+    }
+}
+
+/// Digits accepted by `KeyPhrase::from_seedqr_digits` must be the SeedQR of the keyphrase
+pub fn check_seedqr_digits(data: &[u8]) {
+    let digits: &str = match std::str::from_utf8(data) {
+        Ok(digits) => digits,
+        Err(_) => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::from_seedqr_digits(digits) {
+        assert_eq!(keyphrase.language(), Language::English);
+        assert_eq!(
+            keyphrase.to_seedqr_digits().expect("parsed SeedQRs are valid"),
+            digits
+        );
+    }
+}
+
+/// Bytes accepted by `KeyPhrase::from_compact_seedqr_bytes` must be the entropy of the keyphrase
+pub fn check_compact_seedqr(data: &[u8]) {
+    if let Ok(keyphrase) = KeyPhrase::from_compact_seedqr_bytes(data) {
+        assert_eq!(keyphrase.entropy(), data);
+        assert_eq!(
+            keyphrase
+                .to_compact_seedqr_bytes()
+                .expect("parsed SeedQRs are valid"),
+            data
+        );
+    }
+}
+
+/// Bytewords accepted by `KeyPhrase::from_bytewords` must decode the same once re-encoded
+pub fn check_bytewords(data: &[u8]) {
+    let (style, payload) = match split_bytewords_style(data) {
+        Some(split) => split,
+        None => return,
+    };
+    let encoded: &str = match std::str::from_utf8(payload) {
+        Ok(encoded) => encoded,
+        Err(_) => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::from_bytewords(encoded, style, Language::English) {
+        let reencoded: String = keyphrase.to_bytewords(style);
+
+        assert_eq!(reencoded, encoded.trim().to_ascii_lowercase());
+
+        let decoded: KeyPhrase = KeyPhrase::from_bytewords(&reencoded, style, Language::English)
+            .expect("encoded bytewords decode");
+
+        assert_eq!(decoded.entropy(), keyphrase.entropy());
+    }
+}
+
+/// Entropy decoded by `ur::decode_crypto_seed` must survive a round trip through `encode`
+pub fn check_ur_crypto_seed(data: &[u8]) {
+    let encoded: &str = match std::str::from_utf8(data) {
+        Ok(encoded) => encoded,
+        Err(_) => return,
+    };
+
+    if let Ok(entropy) = ur::decode_crypto_seed(encoded) {
+        let reencoded: String = ur::encode_crypto_seed(&entropy);
+
+        assert_eq!(
+            ur::decode_crypto_seed(&reencoded).expect("encoded URs decode"),
+            entropy
+        );
+    }
+}
+
+/// A phrase whose language is detected by `KeyPhrase::try_from` must be valid in that language
+pub fn check_detect_language(data: &[u8]) {
+    let phrase: &str = match std::str::from_utf8(data) {
+        Ok(phrase) => phrase,
+        Err(_) => return,
+    };
+
+    if let Ok(keyphrase) = KeyPhrase::try_from(phrase) {
+        let parsed: KeyPhrase = KeyPhrase::from_phrase(phrase, keyphrase.language())
+            .expect("phrases are valid in their detected language");
+
+        assert_eq!(parsed.entropy(), keyphrase.entropy());
+    }
+}
+
+/// A payload read from a QR code must be written back the same by the `qr` module
+///
+/// The crate writes QR codes but leaves reading their images to the scanner, so the input is the
+/// payload a scanner reads: SeedQR digits, CompactSeedQR bytes, an uppercase `ur:crypto-seed` or
+/// a phrase, tried in the order a wallet would.
+pub fn check_qr_payload(data: &[u8]) {
+    let text: Option<&str> = std::str::from_utf8(data).ok();
+    let scanned: Option<(KeyPhrase, QrPayload)> = text
+        .and_then(|digits: &str| KeyPhrase::from_seedqr_digits(digits).ok())
+        .map(|keyphrase: KeyPhrase| (keyphrase, QrPayload::SeedQr))
+        .or_else(|| {
+            KeyPhrase::from_compact_seedqr_bytes(data)
+                .ok()
+                .map(|keyphrase: KeyPhrase| (keyphrase, QrPayload::CompactSeedQr))
+        })
+        .or_else(|| {
+            text.filter(|ur: &&str| ur.starts_with("UR:"))
+                .and_then(|ur: &str| KeyPhrase::from_ur_crypto_seed(ur, Language::English).ok())
+                .map(|keyphrase: KeyPhrase| (keyphrase, QrPayload::Ur))
+        })
+        .or_else(|| {
+            text.and_then(|phrase: &str| KeyPhrase::try_from(phrase).ok())
+                .map(|keyphrase: KeyPhrase| (keyphrase, QrPayload::Phrase))
+        });
+
+    if let Some((keyphrase, payload)) = scanned {
+        let qr: QrMatrix = keyphrase
+            .to_qr(payload)
+            .expect("scanned keyphrases fit in their QR code");
+
+        assert!(qr.width() >= 21 && (qr.width() - 17) % 4 == 0);
+        assert!(qr.to_png(1).starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        match payload {
+            QrPayload::SeedQr => assert_eq!(keyphrase.to_seedqr_digits().unwrap().as_bytes(), data),
+            QrPayload::CompactSeedQr => {
+                assert_eq!(keyphrase.to_compact_seedqr_bytes().unwrap(), data)
+            }
+            QrPayload::Ur => assert_eq!(
+                ur::decode_crypto_seed(&keyphrase.to_ur_crypto_seed().to_uppercase()).unwrap(),
+                keyphrase.entropy()
+            ),
+            QrPayload::Phrase => assert_eq!(
+                normalize(keyphrase.phrase()),
+                normalize(text.unwrap_or_default())
+            ),
+        }
+    }
+}
+
+/// A keystore accepted by `keystore::decrypt` must hold a key that survives a round trip
+///
+/// The first byte of the input is the length of the password that follows, the rest is the JSON.
+pub fn check_keystore_decrypt(data: &[u8]) {
+    let (length, rest) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let length: usize = (*length as usize).min(rest.len());
+    let (password, json) = rest.split_at(length);
+    let (password, json): (&str, &str) =
+        match (std::str::from_utf8(password), std::str::from_utf8(json)) {
+            (Ok(password), Ok(json)) => (password, json),
+            _ => return,
+        };
+
+    if let Ok(key) = keystore::decrypt(json, password) {
+        let encrypted: String =
+            keystore::encrypt_with_kdf(&key, password, KdfParams::Pbkdf2 { rounds: 1 })
+                .expect("decrypted keys can be encrypted");
+
+        assert_eq!(
+            keystore::decrypt(&encrypted, password).expect("encrypted keys decrypt"),
+            key
+        );
+    }
+}
@@ -0,0 +1,183 @@
+#ifndef KEYPHRASE_H
+#define KEYPHRASE_H
+
+/* Generated with cbindgen:0.24.3 */
+
+/* Warning, this file is autogenerated by cbindgen. Don't modify this manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Length of the seeds written by `keyphrase_seed_bip39()` and `keyphrase_seed()`
+ */
+#define KEYPHRASE_SEED_BYTES 64
+
+/**
+ * Longest entropy written by `keyphrase_to_entropy()`, for 24 words
+ */
+#define KEYPHRASE_MAX_ENTROPY_BYTES 32
+
+/**
+ * The result of every function of the C bindings
+ */
+typedef enum KeyPhraseStatus {
+  /**
+   * Success
+   */
+  KEY_PHRASE_STATUS_OK = 0,
+  /**
+   * A pointer is null, a string isn't UTF-8, or a buffer is too small
+   */
+  KEY_PHRASE_STATUS_INVALID_ARGUMENT = 1,
+  /**
+   * The language code is unknown, or its feature isn't enabled
+   */
+  KEY_PHRASE_STATUS_UNSUPPORTED_LANGUAGE = 2,
+  /**
+   * A word isn't in the wordlist
+   */
+  KEY_PHRASE_STATUS_INVALID_WORD = 3,
+  /**
+   * The phrase doesn't match its checksum
+   */
+  KEY_PHRASE_STATUS_INVALID_CHECKSUM = 4,
+  /**
+   * The number of words isn't 12, 15, 18, 21 or 24
+   */
+  KEY_PHRASE_STATUS_INVALID_WORD_LENGTH = 5,
+  /**
+   * The entropy isn't 16, 20, 24, 28 or 32 bytes
+   */
+  KEY_PHRASE_STATUS_INVALID_KEYSIZE = 6,
+  /**
+   * The operating system gave no randomness
+   */
+  KEY_PHRASE_STATUS_ENTROPY_UNAVAILABLE = 7,
+  /**
+   * Any other error
+   */
+  KEY_PHRASE_STATUS_OTHER = 8,
+} KeyPhraseStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Generate a new keyphrase of `word_count` words in the language `language`
+ *
+ * On success, `*out_phrase` points to the phrase, to release with `keyphrase_string_free()`.
+ *
+ * # Safety
+ *
+ * `out_phrase` must be null or valid for writes.
+ */
+KeyPhraseStatus keyphrase_generate(uintptr_t word_count, uint8_t language, char **out_phrase);
+
+/**
+ * Check that `phrase` is a valid keyphrase in the language `language`
+ *
+ * # Safety
+ *
+ * `phrase` must be null or a valid NUL terminated string.
+ */
+KeyPhraseStatus keyphrase_validate(const char *phrase, uint8_t language);
+
+/**
+ * Write the entropy of `phrase` into `out_entropy`, and its length into `*out_len`
+ *
+ * `out_capacity` is the size of `out_entropy`, `KEYPHRASE_MAX_ENTROPY_BYTES` is always enough.
+ *
+ * # Safety
+ *
+ * `phrase` must be null or a valid NUL terminated string, `out_entropy` null or valid for
+ * `out_capacity` bytes of writes, and `out_len` null or valid for writes.
+ */
+KeyPhraseStatus keyphrase_to_entropy(const char *phrase,
+                                     uint8_t language,
+                                     uint8_t *out_entropy,
+                                     uintptr_t out_capacity,
+                                     uintptr_t *out_len);
+
+/**
+ * Create the keyphrase of `entropy_len` bytes of entropy, in the language `language`
+ *
+ * On success, `*out_phrase` points to the phrase, to release with `keyphrase_string_free()`.
+ *
+ * # Safety
+ *
+ * `entropy` must be null or valid for `entropy_len` bytes of reads, and `out_phrase` null or
+ * valid for writes.
+ */
+KeyPhraseStatus keyphrase_from_entropy(const uint8_t *entropy,
+                                       uintptr_t entropy_len,
+                                       uint8_t language,
+                                       char **out_phrase);
+
+/**
+ * Derive the BIP39 seed of `phrase` and `password` into `out_seed`
+ *
+ * The seed is the one of any BIP39 wallet for the same phrase and password.
+ *
+ * # Safety
+ *
+ * `phrase` and `password` must be null or valid NUL terminated strings, and `out_seed` null or
+ * valid for `KEYPHRASE_SEED_BYTES` bytes of writes.
+ */
+KeyPhraseStatus keyphrase_seed_bip39(const char *phrase,
+                                     uint8_t language,
+                                     const char *password,
+                                     uint8_t *out_seed);
+
+#if !defined(KEYPHRASE_STRICT_BIP39_ONLY)
+/**
+ * Derive the legacy seed of `phrase` and `password`, salted with `"keyphrase"`, into
+ * `out_seed`
+ *
+ * Only built without the `strict-bip39-only` feature, like `Seed::new()`.
+ *
+ * # Safety
+ *
+ * `phrase` and `password` must be null or valid NUL terminated strings, and `out_seed` null or
+ * valid for `KEYPHRASE_SEED_BYTES` bytes of writes.
+ */
+KeyPhraseStatus keyphrase_seed(const char *phrase,
+                               uint8_t language,
+                               const char *password,
+                               uint8_t *out_seed);
+#endif
+
+/**
+ * Wipe and release a string returned by the library
+ *
+ * # Safety
+ *
+ * `string` must be null or a string returned by the library, not released yet.
+ */
+void keyphrase_string_free(char *string);
+
+/**
+ * Wipe `len` bytes at `buffer`, in a way the compiler can't optimize out
+ *
+ * # Safety
+ *
+ * `buffer` must be null or valid for `len` bytes of writes.
+ */
+void keyphrase_zeroize(uint8_t *buffer, uintptr_t len);
+
+/**
+ * Get a static description of a status, never to be released
+ *
+ * The status is taken as an integer, as C can pass any value for an enum: values that aren't a
+ * `KeyPhraseStatus` get `"unknown status"`.
+ */
+const char *keyphrase_status_message(uint32_t status);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* KEYPHRASE_H */
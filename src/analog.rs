@@ -0,0 +1,410 @@
+//! Creating a [`KeyPhrase`][KeyPhrase] from physical sources of entropy, so that air-gapped users
+//! don't have to trust any RNG.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::util::{Bit, BitWriter};
+
+/// Number of cards in a deck
+const DECK_SIZE: usize = 52;
+
+/// A playing card, with its rank from 1 (ace) to 13 (king)
+///
+/// Cards are ordered by suit (clubs, diamonds, hearts, spades), then by rank, which is the order
+/// [`KeyPhrase::from_card_shuffle()`][KeyPhrase::from_card_shuffle()] ranks shuffles against.
+///
+/// [KeyPhrase::from_card_shuffle()]: ../keyphrase/struct.KeyPhrase.html#method.from_card_shuffle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Card {
+    Clubs(u8),
+    Diamonds(u8),
+    Hearts(u8),
+    Spades(u8),
+}
+
+impl Card {
+    /// Position of the card in a sorted deck, from 0 to 51
+    fn index(self) -> Option<usize> {
+        let (suit, rank): (usize, u8) = match self {
+            Card::Clubs(rank) => (0, rank),
+            Card::Diamonds(rank) => (1, rank),
+            Card::Hearts(rank) => (2, rank),
+            Card::Spades(rank) => (3, rank),
+        };
+
+        if (1..=13).contains(&rank) {
+            Some(suit * 13 + rank as usize - 1)
+        } else {
+            None
+        }
+    }
+}
+
+/// `value = value * factor + term` on a little endian arbitrary size integer
+fn mul_add(value: &mut Vec<u32>, factor: u32, term: u32) {
+    let mut carry: u64 = u64::from(term);
+
+    for limb in value.iter_mut() {
+        let product: u64 = u64::from(*limb) * u64::from(factor) + carry;
+        *limb = product as u32;
+        carry = product >> 32;
+    }
+
+    if carry != 0 {
+        value.push(carry as u32);
+    }
+}
+
+/// Compare `a >> (32 * limbs)` with `b >> (32 * limbs)`
+fn high_limbs_less(a: &[u32], b: &[u32], limbs: usize) -> bool {
+    let len: usize = a.len().max(b.len());
+
+    for i in (limbs..len).rev() {
+        let x: u32 = a.get(i).cloned().unwrap_or(0);
+        let y: u32 = b.get(i).cloned().unwrap_or(0);
+
+        if x != y {
+            return x < y;
+        }
+    }
+
+    false
+}
+
+impl KeyPhrase {
+    /// Create a [`KeyPhrase`][KeyPhrase] from casino dice rolls
+    ///
+    /// Every roll must be a value from 1 to 6, otherwise an `Error` of kind
+    /// `ErrorKind::InvalidDiceRoll` is returned.
+    ///
+    /// The rolls are read in pairs, forming a base-6 number from 0 to 35. Values from 0 to 31
+    /// provide 5 bits of entropy, while 32 to 35 are rejected, so that every bit is unbiased.
+    /// Rolls left over once enough entropy has been gathered are ignored.
+    ///
+    /// About 1 in 9 pairs gets rejected, so you should roll more than the minimum. The recommended
+    /// number of rolls is enough 999 times out of 1000:
+    ///
+    /// | Words | Minimum rolls | Recommended rolls |
+    /// |-------|---------------|-------------------|
+    /// | 12    | 52            | 74                |
+    /// | 15    | 64            | 88                |
+    /// | 18    | 78            | 106               |
+    /// | 21    | 90            | 120               |
+    /// | 24    | 104           | 138               |
+    ///
+    /// If the rolls don't provide enough entropy, an `Error` of kind
+    /// `ErrorKind::NotEnoughEntropy` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let rolls: Vec<u8> = "3264151523613645461325642352311244365615213653421516263425463154231662"
+    ///     .bytes()
+    ///     .map(|c| c - b'0')
+    ///     .collect();
+    ///
+    /// let keyphrase = KeyPhrase::from_dice_rolls(&rolls, KeyPhraseType::Words12, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 12);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_dice_rolls(
+        rolls: &[u8],
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        if let Some(&roll) = rolls.iter().find(|&&roll| !(1..=6).contains(&roll)) {
+            Err(ErrorKind::InvalidDiceRoll(roll))?;
+        }
+
+        let entropy_bits: usize = keyphrase_type.entropy_bits();
+        let mut bits = BitWriter::with_capacity(entropy_bits + 5);
+
+        for pair in rolls.chunks_exact(2) {
+            if bits.len() >= entropy_bits {
+                break;
+            }
+
+            let value: u8 = (pair[0] - 1) * 6 + (pair[1] - 1);
+
+            if value >= 32 {
+                continue;
+            }
+
+            for shift in (0..5).rev() {
+                bits.push(Bit(value >> shift & 1 == 1));
+            }
+        }
+
+        if bits.len() < entropy_bits {
+            Err(ErrorKind::NotEnoughEntropy(entropy_bits))?;
+        }
+
+        let mut entropy: Vec<u8> = bits.into_bytes();
+        entropy.truncate(entropy_bits / 8);
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from coin flips
+    ///
+    /// Every flip provides one bit of entropy, `true` (heads) being `1`, read most significant
+    /// bit first, so the result can be verified against other tools. Exactly
+    /// `keyphrase_type.entropy_bits()` flips are consumed, any flips after that are ignored.
+    ///
+    /// If there are fewer flips than needed, an `Error` of kind `ErrorKind::NotEnoughEntropy`
+    /// is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let flips: Vec<bool> = vec![false; 128];
+    ///
+    /// let keyphrase = KeyPhrase::from_coin_flips(&flips, KeyPhraseType::Words12, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.entropy(), &[0; 16]);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_coin_flips(
+        flips: &[bool],
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        let entropy_bits: usize = keyphrase_type.entropy_bits();
+
+        if flips.len() < entropy_bits {
+            Err(ErrorKind::NotEnoughEntropy(entropy_bits))?;
+        }
+
+        let mut bits = BitWriter::with_capacity(entropy_bits);
+
+        for &flip in &flips[..entropy_bits] {
+            bits.push(Bit(flip));
+        }
+
+        Ok(KeyPhrase::from_entropy_unchecked(bits.into_bytes(), lang))
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from shuffled decks of cards
+    ///
+    /// A single deck of 52 cards creates a 12 word keyphrase, two decks (104 cards, each deck
+    /// containing every card exactly once) create a 24 word keyphrase. A card with a rank outside
+    /// of 1 to 13, or appearing twice in the same deck, returns an `Error` of kind
+    /// `ErrorKind::InvalidCard`, any other number of cards returns `ErrorKind::InvalidDeckSize`.
+    ///
+    /// The shuffle is ranked among all possible orders of the decks (about 225 bits per deck),
+    /// and the entropy is the lowest 128 or 256 bits of that rank. To keep every bit unbiased,
+    /// the very few highest ranks that don't fill a whole range of bits are rejected with an
+    /// `Error` of kind `ErrorKind::NotEnoughEntropy`, asking for a reshuffle. This happens for
+    /// about 1 in 2^97 shuffles, but a deck in exactly reversed order is one of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{Card, KeyPhrase, Language};
+    ///
+    /// let mut deck: Vec<Card> = Vec::new();
+    ///
+    /// for rank in 1..=13 {
+    ///     deck.extend(&[Card::Spades(rank), Card::Hearts(rank), Card::Diamonds(rank), Card::Clubs(rank)]);
+    /// }
+    ///
+    /// let keyphrase = KeyPhrase::from_card_shuffle(&deck, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 12);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_card_shuffle(cards: &[Card], lang: Language) -> Result<KeyPhrase, Error> {
+        let keyphrase_type: KeyPhraseType = match cards.len() {
+            DECK_SIZE => KeyPhraseType::Words12,
+            len if len == 2 * DECK_SIZE => KeyPhraseType::Words24,
+            len => Err(ErrorKind::InvalidDeckSize(len))?,
+        };
+
+        let entropy_bits: usize = keyphrase_type.entropy_bits();
+        let mut rank: Vec<u32> = Vec::new();
+        let mut total: Vec<u32> = vec![1];
+
+        for deck in cards.chunks_exact(DECK_SIZE) {
+            let mut seen: u64 = 0;
+
+            for (i, &card) in deck.iter().enumerate() {
+                let index: usize = match card.index() {
+                    Some(index) if seen & 1 << index == 0 => index,
+                    _ => Err(ErrorKind::InvalidCard(card))?,
+                };
+
+                // Number of cards left to draw that sort before this one
+                let smaller: u32 = (!seen & ((1 << index) - 1)).count_ones();
+                seen |= 1 << index;
+
+                let remaining: u32 = (DECK_SIZE - i) as u32;
+                mul_add(&mut rank, remaining, smaller);
+                mul_add(&mut total, remaining, 0);
+            }
+        }
+
+        let limbs: usize = entropy_bits / 32;
+
+        if !high_limbs_less(&rank, &total, limbs) {
+            Err(ErrorKind::NotEnoughEntropy(entropy_bits))?;
+        }
+
+        rank.resize(rank.len().max(limbs), 0);
+
+        let entropy: Vec<u8> = rank[..limbs]
+            .iter()
+            .rev()
+            .flat_map(|limb: &u32| limb.to_be_bytes().to_vec())
+            .collect();
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dice_rolls() {
+        let ones: Vec<u8> = vec![1; 52];
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_dice_rolls(&ones, KeyPhraseType::Words12, Language::English).unwrap();
+
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            keyphrase.phrase()
+        );
+
+        // 6-6 is rejected, 1-2 is 00001 and 6-2 is 11111
+        let rolls: Vec<u8> = [6, 6, 1, 2, 6, 2].repeat(26);
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_dice_rolls(&rolls, KeyPhraseType::Words12, Language::English).unwrap();
+
+        assert_eq!(
+            &[
+                0x0F, 0xC3, 0xF0, 0xFC, 0x3F, 0x0F, 0xC3, 0xF0, 0xFC, 0x3F, 0x0F, 0xC3, 0xF0, 0xFC,
+                0x3F, 0x0F
+            ],
+            keyphrase.entropy()
+        );
+    }
+
+    #[test]
+    fn dice_rolls_invalid() {
+        let mut rolls: Vec<u8> = vec![1; 104];
+
+        assert!(
+            KeyPhrase::from_dice_rolls(&rolls, KeyPhraseType::Words24, Language::English).is_ok()
+        );
+        assert!(
+            KeyPhrase::from_dice_rolls(&rolls[1..], KeyPhraseType::Words24, Language::English)
+                .is_err()
+        );
+
+        rolls[100] = 7;
+
+        assert!(
+            KeyPhrase::from_dice_rolls(&rolls, KeyPhraseType::Words12, Language::English).is_err()
+        );
+
+        rolls[100] = 0;
+
+        assert!(
+            KeyPhrase::from_dice_rolls(&rolls, KeyPhraseType::Words12, Language::English).is_err()
+        );
+    }
+
+    #[test]
+    fn coin_flips() {
+        let flips: Vec<bool> = [true, false, false, false].repeat(64);
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_coin_flips(&flips, KeyPhraseType::Words24, Language::English).unwrap();
+
+        assert_eq!(keyphrase.entropy(), &[0x88; 32][..]);
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_coin_flips(&flips, KeyPhraseType::Words12, Language::English).unwrap();
+
+        assert_eq!(keyphrase.entropy(), &[0x88; 16][..]);
+
+        assert!(
+            KeyPhrase::from_coin_flips(&flips[1..], KeyPhraseType::Words24, Language::English)
+                .is_err()
+        );
+    }
+
+    fn sorted_deck() -> Vec<Card> {
+        let mut deck: Vec<Card> = Vec::new();
+
+        deck.extend((1..=13).map(Card::Clubs));
+        deck.extend((1..=13).map(Card::Diamonds));
+        deck.extend((1..=13).map(Card::Hearts));
+        deck.extend((1..=13).map(Card::Spades));
+
+        deck
+    }
+
+    #[test]
+    fn card_shuffle() {
+        let mut deck: Vec<Card> = sorted_deck();
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_card_shuffle(&deck, Language::English).unwrap();
+
+        assert_eq!(keyphrase.entropy(), &[0; 16][..]);
+
+        // Swapping the last two cards is the next order, with rank 1
+        deck.swap(50, 51);
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_card_shuffle(&deck, Language::English).unwrap();
+        let mut expected: [u8; 16] = [0; 16];
+        expected[15] = 1;
+
+        assert_eq!(keyphrase.entropy(), &expected[..]);
+
+        // The second deck makes up the lowest digits of the rank
+        let mut decks: Vec<Card> = sorted_deck();
+        decks.extend(&deck);
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_card_shuffle(&decks, Language::English).unwrap();
+        let mut expected: [u8; 32] = [0; 32];
+        expected[31] = 1;
+
+        assert_eq!(keyphrase.entropy(), &expected[..]);
+    }
+
+    #[test]
+    fn card_shuffle_invalid() {
+        let mut deck: Vec<Card> = sorted_deck();
+
+        assert!(KeyPhrase::from_card_shuffle(&deck[1..], Language::English).is_err());
+
+        // The highest rank can't be mapped without bias
+        deck.reverse();
+
+        assert!(KeyPhrase::from_card_shuffle(&deck, Language::English).is_err());
+
+        deck[0] = Card::Hearts(1);
+
+        assert!(KeyPhrase::from_card_shuffle(&deck, Language::English).is_err());
+
+        deck[0] = Card::Spades(14);
+
+        assert!(KeyPhrase::from_card_shuffle(&deck, Language::English).is_err());
+    }
+}
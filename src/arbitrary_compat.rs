@@ -0,0 +1,144 @@
+//! `Arbitrary` implementations for fuzzers, enabled by the `arbitrary` feature.
+//!
+//! [`KeyPhraseType`][KeyPhraseType] and [`Language`][Language] are picked among the values
+//! enabled in the build. [`ArbitraryEntropy`][ArbitraryEntropy] and
+//! [`ArbitraryPhrase`][ArbitraryPhrase] are structured inputs for
+//! [`KeyPhrase::from_entropy()`][KeyPhrase::from_entropy()] and
+//! [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()]: their lengths are valid, so fuzzers
+//! spend their time past the length checks, while the checksum of a phrase is only right by
+//! chance. An arbitrary [`KeyPhrase`][KeyPhrase] is always valid.
+//!
+//! # Example
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use keyphrase::{ArbitraryEntropy, KeyPhrase};
+//!
+//! let data = [7u8; 64];
+//! let input = ArbitraryEntropy::arbitrary(&mut Unstructured::new(&data)).unwrap();
+//!
+//! assert!(KeyPhrase::from_entropy(&input.entropy, input.lang).is_ok());
+//! ```
+//!
+//! [KeyPhraseType]: ../keyphrase_type/enum.KeyPhraseType.html
+//! [Language]: ../language/enum.Language.html
+//! [ArbitraryEntropy]: ./struct.ArbitraryEntropy.html
+//! [ArbitraryPhrase]: ./struct.ArbitraryPhrase.html
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//! [KeyPhrase::from_entropy()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy
+//! [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::util::Bits11;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+const KEYPHRASE_TYPES: [KeyPhraseType; 5] = [
+    KeyPhraseType::Words12,
+    KeyPhraseType::Words15,
+    KeyPhraseType::Words18,
+    KeyPhraseType::Words21,
+    KeyPhraseType::Words24,
+];
+
+/// Entropy of a valid length, with the language to encode it in
+#[derive(Debug, Clone)]
+pub struct ArbitraryEntropy {
+    /// The number of words of the phrase the entropy encodes to
+    pub keyphrase_type: KeyPhraseType,
+    /// The language of the phrase
+    pub lang: Language,
+    /// `keyphrase_type.entropy_bits() / 8` bytes
+    pub entropy: Vec<u8>,
+}
+
+/// A phrase of words of the wordlist, of a valid length, whose checksum is only right by chance
+#[derive(Debug, Clone)]
+pub struct ArbitraryPhrase {
+    /// The language whose wordlist the words are taken from
+    pub lang: Language,
+    /// The words, separated by single spaces
+    pub phrase: String,
+}
+
+impl<'a> Arbitrary<'a> for KeyPhraseType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<KeyPhraseType> {
+        u.choose(&KEYPHRASE_TYPES).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Language {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Language> {
+        let languages: Vec<Language> = Language::all().collect();
+
+        u.choose(&languages).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for ArbitraryEntropy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<ArbitraryEntropy> {
+        let keyphrase_type: KeyPhraseType = u.arbitrary()?;
+        let lang: Language = u.arbitrary()?;
+        let entropy: Vec<u8> = u.bytes(keyphrase_type.entropy_bits() / 8)?.to_vec();
+
+        Ok(ArbitraryEntropy {
+            keyphrase_type,
+            lang,
+            entropy,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ArbitraryPhrase {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<ArbitraryPhrase> {
+        let keyphrase_type: KeyPhraseType = u.arbitrary()?;
+        let lang: Language = u.arbitrary()?;
+        let words: Vec<&'static str> = (0..keyphrase_type.word_count())
+            .map(|_| {
+                let index: u16 = u.int_in_range(0..=2047)?;
+
+                Ok(lang.wordlist().get_word(Bits11::from(index)))
+            })
+            .collect::<Result<Vec<&'static str>>>()?;
+
+        Ok(ArbitraryPhrase {
+            lang,
+            phrase: words.join(" "),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for KeyPhrase {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<KeyPhrase> {
+        let input: ArbitraryEntropy = u.arbitrary()?;
+
+        Ok(KeyPhrase::from_entropy(&input.entropy, input.lang)
+            .expect("entropy of a valid length is valid"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arbitrary_phrase_length() {
+        let data: Vec<u8> = (0..=255).collect();
+        let input: ArbitraryPhrase = Unstructured::new(&data).arbitrary().unwrap();
+
+        assert!(KeyPhraseType::for_word_count(input.phrase.split(' ').count()).is_ok());
+        assert!(KeyPhrase::validate_words(&input.phrase, input.lang)
+            .unwrap()
+            .iter()
+            .all(|&known: &bool| known));
+    }
+
+    #[test]
+    fn arbitrary_keyphrase() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        let keyphrase: KeyPhrase = Unstructured::new(&data).arbitrary().unwrap();
+
+        assert!(KeyPhrase::validate(keyphrase.phrase(), keyphrase.language()).is_ok());
+    }
+}
@@ -0,0 +1,219 @@
+//! Heuristics detecting hand-crafted or well-known keyphrases, which are not safe to use.
+
+use crate::keyphrase::KeyPhrase;
+use crate::language::WordList;
+use crate::util::Bits11;
+use std::fmt;
+
+/// Minimum number of words with consecutive wordlist indices to report
+const SEQUENCE_LENGTH: usize = 4;
+
+/// Minimum number of occurrences of a word to report
+const REPEAT_COUNT: usize = 3;
+
+/// Byte patterns that fill the entropy of well-known test vectors
+const TEST_PATTERNS: &[u8] = &[0x00, 0x7F, 0x80, 0xFF];
+
+/// Entropy of the other BIP39 test vectors, and of the examples of this crate
+const TEST_VECTORS: &[&str] = &[
+    "9e885d952ad362caeb4efe34a8e91bd2",
+    "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+    "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+    "c0ba5a8e914111210f2bd131f3d5e08d",
+    "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+    "23db8160a31d3e0dca3688ed941adbf3",
+    "f30f8c1da665478f49b001d94c5fc452",
+    "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+    "a056b28d3d8915a25ee05ea8761d9984",
+    "33e46bb13a746ea41cdde45c90846a79",
+];
+
+/// A reason not to trust a [`KeyPhrase`][KeyPhrase], returned by
+/// [`KeyPhrase::audit()`][KeyPhrase::audit()]
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::audit()]: ../keyphrase/struct.KeyPhrase.html#method.audit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Every word but the last one (which carries the checksum) is the same
+    IdenticalWords,
+    /// A word appears at least 3 times, which happens to fewer than 1 in 2000 random phrases
+    RepeatedWord { word: &'static str, count: usize },
+    /// At least 4 words in a row are consecutive in the wordlist, in either direction
+    SequentialWords { position: usize, len: usize },
+    /// The entropy is the one of a published test vector
+    KnownTestVector,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Warning::IdenticalWords => f.write_str("all the words are identical"),
+            Warning::RepeatedWord { word, count } => {
+                write!(f, "the word {:?} appears {} times", word, count)
+            }
+            Warning::SequentialWords { position, len } => write!(
+                f,
+                "{} words from position {} follow the wordlist order",
+                len, position
+            ),
+            Warning::KnownTestVector => f.write_str("the phrase is a published test vector"),
+        }
+    }
+}
+
+impl KeyPhrase {
+    /// Look for signs that the keyphrase wasn't randomly generated
+    ///
+    /// Hand-crafted phrases, such as `abandon abandon … about`, sequences taken from the
+    /// wordlist or test vectors copied from documentation are valid, but anyone can guess them.
+    /// Wallets should warn users before funding an address derived from a keyphrase that has any
+    /// warnings. An empty list doesn't mean the keyphrase is safe, only that none of these
+    /// patterns were found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Warning};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert!(keyphrase.audit().contains(&Warning::IdenticalWords));
+    /// ```
+    pub fn audit(&self) -> Vec<Warning> {
+        let wordlist: &WordList = self.language().wordlist();
+        let indices: Vec<u16> = self.word_indices();
+        let mut warnings: Vec<Warning> = Vec::new();
+
+        // The last word is mostly checksum, it can't be chosen freely
+        let chosen: &[u16] = &indices[..indices.len() - 1];
+
+        if chosen.windows(2).all(|pair: &[u16]| pair[0] == pair[1]) {
+            warnings.push(Warning::IdenticalWords);
+        }
+
+        let mut counted: Vec<u16> = Vec::new();
+
+        for &index in &indices {
+            if counted.contains(&index) {
+                continue;
+            }
+
+            let count: usize = indices.iter().filter(|&&other| other == index).count();
+
+            if count >= REPEAT_COUNT {
+                warnings.push(Warning::RepeatedWord {
+                    word: wordlist.get_word(Bits11::from(index)),
+                    count,
+                });
+            }
+
+            counted.push(index);
+        }
+
+        let mut start: usize = 0;
+
+        while start < indices.len() {
+            let mut end: usize = start + 1;
+
+            for step in &[1i32, -1i32] {
+                let mut candidate: usize = start + 1;
+
+                while candidate < indices.len()
+                    && i32::from(indices[candidate]) - i32::from(indices[candidate - 1]) == *step
+                {
+                    candidate += 1;
+                }
+
+                end = end.max(candidate);
+            }
+
+            if end - start >= SEQUENCE_LENGTH {
+                warnings.push(Warning::SequentialWords {
+                    position: start,
+                    len: end - start,
+                });
+            }
+
+            start = end;
+        }
+
+        let hex: String = self
+            .entropy()
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect();
+
+        let patterned: bool = TEST_PATTERNS
+            .iter()
+            .any(|&pattern: &u8| self.entropy().iter().all(|&byte| byte == pattern));
+
+        if patterned || TEST_VECTORS.contains(&hex.as_str()) {
+            warnings.push(Warning::KnownTestVector);
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    use crate::keyphrase_type::KeyPhraseType;
+    use crate::language::Language;
+
+    fn audit(phrase: &str) -> Vec<Warning> {
+        KeyPhrase::from_phrase(phrase, Language::English)
+            .unwrap()
+            .audit()
+    }
+
+    #[test]
+    fn audit_test_vectors() {
+        let warnings: Vec<Warning> = audit("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::IdenticalWords,
+                Warning::RepeatedWord {
+                    word: "abandon",
+                    count: 11
+                },
+                Warning::KnownTestVector
+            ]
+        );
+
+        assert_eq!(
+            audit("ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"),
+            vec![Warning::KnownTestVector]
+        );
+    }
+
+    #[test]
+    fn audit_sequential_words() {
+        let wordlist: &WordList = Language::English.wordlist();
+        let partial: String = (0..11)
+            .map(|i: u16| wordlist.get_word(Bits11::from(1000 - i)))
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let last: &str = KeyPhrase::valid_last_words(&partial, Language::English).unwrap()[0];
+
+        let warnings: Vec<Warning> = audit(&format!("{} {}", partial, last));
+
+        assert!(warnings.contains(&Warning::SequentialWords {
+            position: 0,
+            len: 11
+        }));
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn audit_random() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+
+        assert!(!keyphrase.audit().contains(&Warning::KnownTestVector));
+    }
+}
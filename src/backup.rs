@@ -0,0 +1,249 @@
+//! Engraving guides of a [`KeyPhrase`][KeyPhrase] for metal backup plates.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::keyphrase::{abbreviate, KeyPhrase};
+use crate::language::{Language, WordList};
+use crate::util::Bits11;
+
+/// Bits of the 1-based wordlist index punched on dot plates, from 2048 down to 1
+const BINARY_BITS: u32 = 12;
+
+/// One word of a [`BackupTemplate`][BackupTemplate]
+///
+/// [BackupTemplate]: ./struct.BackupTemplate.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    /// Position of the word in the phrase, starting at 1 like on the plates
+    pub number: usize,
+    /// The first four letters of the word, or the whole word if it's shorter
+    pub abbreviation: &'static str,
+    /// The whole word
+    pub word: &'static str,
+    /// Index of the word in the wordlist, starting at 0
+    pub index: u16,
+}
+
+/// The words of a keyphrase laid out for a metal backup, returned by
+/// [`KeyPhrase::backup_template()`][KeyPhrase::backup_template()]
+///
+/// Apps can use the entries directly, or print an engraving guide with
+/// [`BackupTemplate::to_csv()`][BackupTemplate::to_csv()] or
+/// [`BackupTemplate::to_text()`][BackupTemplate::to_text()]. The guide is as secret as the
+/// keyphrase: print it on a printer that keeps no copy, and destroy it once the plate is done.
+///
+/// [KeyPhrase::backup_template()]: ../keyphrase/struct.KeyPhrase.html#method.backup_template
+/// [BackupTemplate::to_csv()]: ./struct.BackupTemplate.html#method.to_csv
+/// [BackupTemplate::to_text()]: ./struct.BackupTemplate.html#method.to_text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupTemplate {
+    /// Language of the words
+    pub language: Language,
+    /// Every word of the keyphrase, in order
+    pub entries: Vec<BackupEntry>,
+}
+
+/// The layouts of [`BackupTemplate::to_text()`][BackupTemplate::to_text()], after the common
+/// kinds of steel plates
+///
+/// [BackupTemplate::to_text()]: ./struct.BackupTemplate.html#method.to_text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlateLayout {
+    /// One word per line, its number then its first four letters one by one, as slotted into
+    /// letter tile capsules and cassettes
+    Tiles,
+    /// The numbered abbreviations in two columns, the first half of the words on the left, as
+    /// stamped on plates with two rows of slots
+    TwoColumns,
+    /// The wordlist index plus 1, from 1 to 2048, as 12 bits to punch from 2048 down to 1, as on
+    /// dot plates
+    Binary,
+}
+
+impl BackupTemplate {
+    /// Write the template as CSV, with a header row
+    ///
+    /// The columns are `number,abbreviation,word,index`. Words never contain commas or quotes,
+    /// so nothing is escaped.
+    pub fn to_csv(&self) -> String {
+        let mut csv: String = String::from("number,abbreviation,word,index\n");
+
+        self.entries.iter().for_each(|entry: &BackupEntry| {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                entry.number, entry.abbreviation, entry.word, entry.index
+            ))
+        });
+
+        csv
+    }
+
+    /// Write the template as plain text laid out for a kind of steel plate
+    ///
+    /// Letters are in uppercase, like the tiles and stamps of the plates.
+    pub fn to_text(&self, layout: PlateLayout) -> String {
+        let mut text: String = String::new();
+
+        match layout {
+            PlateLayout::Tiles => self.entries.iter().for_each(|entry: &BackupEntry| {
+                let letters: Vec<String> = entry
+                    .abbreviation
+                    .chars()
+                    .map(|letter: char| letter.to_uppercase().collect())
+                    .collect();
+
+                text.push_str(&format!("{:02} {}\n", entry.number, letters.join(" ")));
+            }),
+            PlateLayout::TwoColumns => {
+                let rows: usize = self.entries.len().div_ceil(2);
+                let cell = |entry: &BackupEntry| -> String {
+                    format!(
+                        "{:02} {:<4}",
+                        entry.number,
+                        entry.abbreviation.to_uppercase()
+                    )
+                };
+
+                (0..rows).for_each(|row: usize| {
+                    match self.entries.get(rows + row) {
+                        Some(right) => text.push_str(&format!(
+                            "{}    {}\n",
+                            cell(&self.entries[row]),
+                            cell(right)
+                        )),
+                        None => text.push_str(&format!("{}\n", cell(&self.entries[row]))),
+                    };
+                });
+            }
+            PlateLayout::Binary => {
+                text.push_str("  ");
+                (0..BINARY_BITS)
+                    .rev()
+                    .for_each(|bit: u32| text.push_str(&format!("{:>5}", 1 << bit)));
+                text.push('\n');
+
+                self.entries.iter().for_each(|entry: &BackupEntry| {
+                    let value: u16 = entry.index + 1;
+
+                    text.push_str(&format!("{:02}", entry.number));
+                    (0..BINARY_BITS).rev().for_each(|bit: u32| {
+                        text.push_str(if value >> bit & 1 == 1 {
+                            "    X"
+                        } else {
+                            "    ."
+                        })
+                    });
+                    text.push('\n');
+                });
+            }
+        }
+
+        text
+    }
+}
+
+impl KeyPhrase {
+    /// Get the words of the keyphrase laid out for a metal backup
+    ///
+    /// Every entry has the number of the word, its first four letters, which is what most plates
+    /// store, the whole word and its index in the wordlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, PlateLayout};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    /// let template = keyphrase.backup_template();
+    ///
+    /// assert_eq!(template.entries[1].number, 2);
+    /// assert_eq!(template.entries[1].abbreviation, "rema");
+    /// assert!(template.to_text(PlateLayout::Tiles).starts_with("01 P A R K\n02 R E M A\n"));
+    /// ```
+    pub fn backup_template(&self) -> BackupTemplate {
+        let wordlist: &WordList = self.language().wordlist();
+
+        BackupTemplate {
+            language: self.language(),
+            entries: self
+                .word_indices()
+                .into_iter()
+                .enumerate()
+                .map(|(position, index): (usize, u16)| {
+                    let word: &'static str = wordlist.get_word(Bits11::from(index));
+
+                    BackupEntry {
+                        number: position + 1,
+                        abbreviation: abbreviate(word),
+                        word,
+                        index,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn template() -> BackupTemplate {
+        KeyPhrase::from_phrase(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap()
+        .backup_template()
+    }
+
+    #[test]
+    fn backup_template_entries() {
+        let template: BackupTemplate = template();
+
+        assert_eq!(template.entries.len(), 12);
+        assert_eq!(
+            template.entries[11],
+            BackupEntry {
+                number: 12,
+                abbreviation: "ankl",
+                word: "ankle",
+                index: 73,
+            }
+        );
+        assert_eq!(
+            template.to_csv().lines().take(3).collect::<Vec<&str>>(),
+            vec![
+                "number,abbreviation,word,index",
+                "1,park,park,1282",
+                "2,rema,remain,1452",
+            ]
+        );
+    }
+
+    #[test]
+    fn backup_template_layouts() {
+        let template: BackupTemplate = template();
+
+        let text: String = template.to_text(PlateLayout::TwoColumns);
+
+        assert_eq!(text.lines().count(), 6);
+        assert_eq!(text.lines().next(), Some("01 PARK    07 KNEE"));
+        assert_eq!(text.lines().last(), Some("06 SPEL    12 ANKL"));
+
+        let text: String = template.to_text(PlateLayout::Binary);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "   2048 1024  512  256  128   64   32   16    8    4    2    1"
+        );
+        // park is the word 1283 = 1024 + 256 + 2 + 1
+        assert_eq!(
+            lines[1],
+            "01    .    X    .    X    .    .    .    .    .    .    X    X"
+        );
+        assert_eq!(lines.len(), 13);
+    }
+}
@@ -0,0 +1,252 @@
+//! Parsing of the command line, without any dependency so that the binary stays small enough to
+//! audit before it is copied to an airgapped machine.
+
+use keyphrase::{KeyPhrase, KeyPhraseType, Language, Passphrase, Seed};
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+
+/// The flags of a subcommand and their values, in order
+pub struct Args {
+    flags: Vec<(String, Option<String>)>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    /// Parse the arguments of a subcommand, knowing which flags take a value
+    ///
+    /// Both `--flag value` and `--flag=value` are accepted. Returns a message for unknown
+    /// flags and missing values.
+    pub fn parse<I>(args: I, with_value: &[&str], without_value: &[&str]) -> Result<Args, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed: Args = Args {
+            flags: Vec::new(),
+            positional: Vec::new(),
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                parsed.positional.push(arg);
+                continue;
+            }
+
+            let (name, inline): (String, Option<String>) = match arg.find('=') {
+                Some(position) => (
+                    arg[..position].to_string(),
+                    Some(arg[position + 1..].to_string()),
+                ),
+                None => (arg, None),
+            };
+
+            if with_value.contains(&name.as_str()) {
+                let value: String = match inline {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| format!("{} needs a value", name))?,
+                };
+
+                parsed.flags.push((name, Some(value)));
+            } else if without_value.contains(&name.as_str()) && inline.is_none() {
+                parsed.flags.push((name, None));
+            } else {
+                return Err(format!("unknown option {}", name));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// The value of the last occurrence of a flag
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(flag, _): &&(String, Option<String>)| flag == name)
+            .and_then(|(_, value): &(String, Option<String>)| value.as_ref())
+            .map(|value: &String| value.as_str())
+    }
+
+    /// Whether a flag is present
+    pub fn has(&self, name: &str) -> bool {
+        self.flags
+            .iter()
+            .any(|(flag, _): &(String, Option<String>)| flag == name)
+    }
+
+    /// The arguments that aren't flags
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    /// The phrase given as arguments, read from the file of `--file`, or read from stdin when
+    /// there are neither, with its words separated by single spaces
+    ///
+    /// Reading it from stdin keeps it out of the shell history. When stdin is a terminal, the
+    /// phrase is prompted for on stderr and read from a single line.
+    pub fn phrase(&self) -> Result<String, String> {
+        let phrase: String = if let Some(path) = self.value("--file") {
+            if !self.positional.is_empty() {
+                return Err("the phrase is given both as arguments and with --file".to_string());
+            }
+
+            fs::read_to_string(path).map_err(|error| format!("can't read {}: {}", path, error))?
+        } else if self.positional.is_empty() {
+            let mut input: String = String::new();
+
+            let read: io::Result<usize> = if io::stdin().is_terminal() {
+                eprint!("phrase: ");
+                io::stderr().flush().ok();
+
+                io::stdin().read_line(&mut input)
+            } else {
+                io::stdin().read_to_string(&mut input)
+            };
+
+            read.map_err(|error| format!("can't read the phrase: {}", error))?;
+
+            input
+        } else {
+            self.positional.join(" ")
+        };
+
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+
+        if words.is_empty() {
+            return Err("no phrase given".to_string());
+        }
+
+        Ok(words.join(" "))
+    }
+
+    /// The language of `--lang`, English by default
+    pub fn language(&self) -> Result<Language, String> {
+        match self.value("--lang") {
+            Some(name) => LANGUAGES
+                .iter()
+                .find(|&&(known, _): &&(&str, Language)| known == name)
+                .map(|&(_, lang): &(&str, Language)| lang)
+                .ok_or_else(|| format!("unknown or disabled language {}", name)),
+            None => Ok(Language::English),
+        }
+    }
+
+    /// The number of words of `--words`, 24 by default
+    pub fn keyphrase_type(&self) -> Result<KeyPhraseType, String> {
+        let words: usize = match self.value("--words") {
+            Some(words) => words
+                .parse()
+                .map_err(|_| format!("invalid number of words {}", words))?,
+            None => 24,
+        };
+
+        KeyPhraseType::for_word_count(words).map_err(|error| error.to_string())
+    }
+
+    /// The seed derivation of `--scheme`, BIP39 by default
+    pub fn scheme(&self) -> Result<Scheme, String> {
+        match self.value("--scheme") {
+            None | Some("bip39") => Ok(Scheme::Bip39),
+            #[cfg(not(feature = "strict-bip39-only"))]
+            Some("keyphrase") => Ok(Scheme::KeyPhrase),
+            Some(scheme) => Err(format!("unknown or disabled scheme {}", scheme)),
+        }
+    }
+
+    /// The output format of `--format`, or `json` with `--json`
+    pub fn format(&self) -> Result<Format, String> {
+        if self.has("--json") {
+            return Ok(Format::Json);
+        }
+
+        match self.value("--format") {
+            None | Some("plain") => Ok(Format::Plain),
+            Some("numbered") => Ok(Format::Numbered),
+            Some("json") => Ok(Format::Json),
+            Some(format) => Err(format!("unknown format {}", format)),
+        }
+    }
+}
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The bare result, for shells
+    Plain,
+    /// One numbered word per line, to copy a phrase by hand
+    Numbered,
+    /// A JSON object, for scripts
+    Json,
+}
+
+/// How a seed is derived from a phrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// The seed of every BIP39 wallet
+    Bip39,
+    /// The legacy seed of this crate, salted with `keyphrase`
+    #[cfg(not(feature = "strict-bip39-only"))]
+    KeyPhrase,
+}
+
+impl Scheme {
+    /// Derive the seed of a keyphrase
+    pub fn seed(self, keyphrase: &KeyPhrase, passphrase: &Passphrase) -> Seed {
+        match self {
+            Scheme::Bip39 => Seed::new_bip39(keyphrase, passphrase),
+            #[cfg(not(feature = "strict-bip39-only"))]
+            Scheme::KeyPhrase => Seed::new(keyphrase, passphrase),
+        }
+    }
+
+    /// The name of the scheme on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Scheme::Bip39 => "bip39",
+            #[cfg(not(feature = "strict-bip39-only"))]
+            Scheme::KeyPhrase => "keyphrase",
+        }
+    }
+}
+
+/// The languages enabled in this build, by their name on the command line
+pub const LANGUAGES: &[(&str, Language)] = &[
+    ("english", Language::English),
+    #[cfg(feature = "chinese-simplified")]
+    ("chinese-simplified", Language::ChineseSimplified),
+    #[cfg(feature = "chinese-traditional")]
+    ("chinese-traditional", Language::ChineseTraditional),
+    #[cfg(feature = "french")]
+    ("french", Language::French),
+    #[cfg(feature = "italian")]
+    ("italian", Language::Italian),
+    #[cfg(feature = "japanese")]
+    ("japanese", Language::Japanese),
+    #[cfg(feature = "korean")]
+    ("korean", Language::Korean),
+    #[cfg(feature = "spanish")]
+    ("spanish", Language::Spanish),
+];
+
+/// The name of a language on the command line
+pub fn language_name(lang: Language) -> &'static str {
+    LANGUAGES
+        .iter()
+        .find(|&&(_, known): &&(&str, Language)| known == lang)
+        .map(|&(name, _): &(&str, Language)| name)
+        .unwrap_or("english")
+}
+
+/// Decode hex, in either case
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 || !hex.bytes().all(|byte: u8| byte.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex {}", hex));
+    }
+
+    Ok((0..hex.len())
+        .step_by(2)
+        .map(|i: usize| u8::from_str_radix(&hex[i..i + 2], 16).expect("checked hex digits"))
+        .collect())
+}
@@ -0,0 +1,61 @@
+//! `keyphrase generate`: a new phrase, from the randomness of the system or from given entropy.
+
+use crate::args::{self, Args, Format};
+use crate::output;
+use crate::Failure;
+use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+use zeroize::Zeroize;
+
+pub const USAGE: &str = "\
+usage: keyphrase generate [--words 12|15|18|21|24] [--lang LANGUAGE] [--entropy-hex HEX]
+                          [--format plain|numbered|json] [--json]
+
+Generate a new phrase, 24 English words by default. With --entropy-hex, the phrase of the given
+entropy is written instead, for entropy made offline with dice or coins. Its length gives the
+number of words, so --words can't be given with it.";
+
+/// Run the subcommand, returning the text to print
+pub fn run(args: Vec<String>) -> Result<String, Failure> {
+    let args: Args = Args::parse(
+        args,
+        &["--words", "--lang", "--entropy-hex", "--format"],
+        &["--json"],
+    )?;
+
+    if let Some(extra) = args.positional().first() {
+        Err(format!("unexpected argument {}", extra))?;
+    }
+
+    let lang: Language = args.language()?;
+    let format: Format = args.format()?;
+
+    let keyphrase: KeyPhrase = match args.value("--entropy-hex") {
+        Some(_) if args.value("--words").is_some() => {
+            Err("--words can't be given with --entropy-hex".to_string())?
+        }
+        Some(hex) => {
+            let mut entropy: Vec<u8> = args::parse_hex(hex)?;
+            let keyphrase: Result<KeyPhrase, _> = KeyPhrase::from_entropy(&entropy, lang);
+
+            entropy.zeroize();
+
+            keyphrase.map_err(Failure::rejected)?
+        }
+        None => {
+            let keyphrase_type: KeyPhraseType = args.keyphrase_type()?;
+
+            KeyPhrase::try_new(keyphrase_type, lang).map_err(Failure::rejected)?
+        }
+    };
+
+    Ok(match format {
+        Format::Plain => keyphrase.phrase().to_string(),
+        Format::Numbered => output::numbered(keyphrase.phrase()),
+        Format::Json => output::json_object(&[
+            ("phrase", output::json_string(keyphrase.phrase())),
+            ("words", keyphrase.phrase().split(' ').count().to_string()),
+            ("language", output::json_string(args::language_name(lang))),
+            ("entropy", output::json_string(&keyphrase.entropy_hex())),
+        ]),
+    })
+}
@@ -0,0 +1,110 @@
+//! The `keyphrase` command line tool, built with the `cli` feature.
+//!
+//! Every subcommand prints its result on stdout and its errors on stderr, and exits with 0 on
+//! success, 1 when the input is rejected and 2 on usage errors.
+
+mod args;
+mod generate;
+mod output;
+mod recover;
+mod seed;
+mod validate;
+#[cfg(feature = "tui")]
+mod wizard;
+
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+usage: keyphrase <command> [options]
+
+commands:
+    generate    generate a new phrase
+    validate    check a phrase and report its problems
+    recover     find the phrases matching one with missing or wrong words
+    seed        derive the seed of a phrase
+    wizard      recover a phrase interactively, with the tui feature
+
+Run `keyphrase <command> --help` for the options of a command.";
+
+/// A subcommand, run on its arguments
+type Command = fn(Vec<String>) -> Result<String, Failure>;
+
+/// Exit code of rejected input
+const EXIT_FAILURE: i32 = 1;
+
+/// Exit code of usage errors
+const EXIT_USAGE: i32 = 2;
+
+/// Why a subcommand failed
+#[derive(Debug)]
+pub enum Failure {
+    /// The arguments are wrong, the usage of the subcommand is printed after the message
+    Usage(String),
+    /// The input is rejected, such as an invalid phrase
+    Rejected(String),
+    /// The input is rejected, and the report explaining why is printed on stdout
+    Report(String),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Failure {
+        Failure::Usage(message)
+    }
+}
+
+impl Failure {
+    /// Reject the input with the message of an error of the library
+    pub fn rejected<E: ToString>(error: E) -> Failure {
+        Failure::Rejected(error.to_string())
+    }
+}
+
+fn main() {
+    better_panic::install();
+
+    let mut args = env::args().skip(1);
+    let command: String = args.next().unwrap_or_default();
+    let args: Vec<String> = args.collect();
+
+    let (run, usage): (Command, &str) = match command.as_str() {
+        "generate" => (generate::run, generate::USAGE),
+        "validate" => (validate::run, validate::USAGE),
+        "recover" => (recover::run, recover::USAGE),
+        "seed" => (seed::run, seed::USAGE),
+        #[cfg(feature = "tui")]
+        "wizard" => (wizard::run, wizard::USAGE),
+        "--help" | "-h" | "help" => {
+            println!("{}", USAGE);
+            return;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    };
+
+    if args
+        .iter()
+        .any(|arg: &String| arg == "--help" || arg == "-h")
+    {
+        println!("{}", usage);
+        return;
+    }
+
+    match run(args) {
+        Ok(output) => println!("{}", output),
+        Err(Failure::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, usage);
+            process::exit(EXIT_USAGE);
+        }
+        Err(Failure::Rejected(message)) => {
+            eprintln!("error: {}", message);
+            process::exit(EXIT_FAILURE);
+        }
+        Err(Failure::Report(report)) => {
+            println!("{}", report);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
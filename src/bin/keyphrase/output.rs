@@ -0,0 +1,74 @@
+//! Printing of results as plain text, JSON or QR codes.
+
+#[cfg(feature = "qr")]
+use keyphrase::qr::QrMatrix;
+
+/// Quote a string for JSON
+pub fn json_string(value: &str) -> String {
+    let mut quoted: String = String::with_capacity(value.len() + 2);
+
+    quoted.push('"');
+    value.chars().for_each(|c: char| match c {
+        '"' => quoted.push_str("\\\""),
+        '\\' => quoted.push_str("\\\\"),
+        '\n' => quoted.push_str("\\n"),
+        c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+        c => quoted.push(c),
+    });
+    quoted.push('"');
+
+    quoted
+}
+
+/// Write a JSON object of already encoded values
+pub fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value): &(&str, String)| format!("{}:{}", json_string(name), value))
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Write the words of a phrase one per line, numbered from 1
+pub fn numbered(phrase: &str) -> String {
+    phrase
+        .split(' ')
+        .enumerate()
+        .map(|(i, word): (usize, &str)| format!("{:2}. {}", i + 1, word))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Draw a QR code with half blocks, two rows of modules per line
+///
+/// Light modules and the quiet zone are drawn, dark ones are left blank, so that the code reads
+/// right on terminals with a dark background.
+#[cfg(feature = "qr")]
+pub fn qr(matrix: &QrMatrix) -> String {
+    const QUIET_ZONE: usize = 2;
+
+    let size: usize = matrix.width() + 2 * QUIET_ZONE;
+    let light = |x: usize, y: usize| -> bool {
+        if x < QUIET_ZONE || y < QUIET_ZONE || x >= size - QUIET_ZONE || y >= size - QUIET_ZONE {
+            return true;
+        }
+
+        !matrix.is_dark(x - QUIET_ZONE, y - QUIET_ZONE)
+    };
+
+    (0..size)
+        .step_by(2)
+        .map(|y: usize| {
+            (0..size)
+                .map(|x: usize| match (light(x, y), y + 1 < size && light(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
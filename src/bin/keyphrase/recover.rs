@@ -0,0 +1,69 @@
+//! `keyphrase recover`: the valid phrases matching one with missing or wrong words.
+
+use crate::args::{Args, Format};
+use crate::output;
+use crate::Failure;
+use keyphrase::{recovery, KeyPhrase, Language};
+
+pub const USAGE: &str = "\
+usage: keyphrase recover [PATTERN...] [--lang LANGUAGE] [--limit N] [--json]
+
+Find the valid phrases matching a pattern, read from stdin when it isn't given as arguments.
+Missing words are written `?`: every word of the wordlist is tried in their place, so more than
+two of them take very long. Without `?`, every phrase differing by a single word is listed, to
+fix a word written down wrong. Exits with 0 when there are candidates and 1 otherwise.";
+
+/// Run the subcommand, returning the candidates one per line
+pub fn run(args: Vec<String>) -> Result<String, Failure> {
+    let args: Args = Args::parse(args, &["--lang", "--limit"], &["--json"])?;
+    let lang: Language = args.language()?;
+    let format: Format = args.format()?;
+    let limit: usize = match args.value("--limit") {
+        Some(limit) => limit
+            .parse()
+            .map_err(|_| format!("invalid limit {}", limit))?,
+        None => usize::MAX,
+    };
+    let pattern: String = args.phrase()?;
+
+    let candidates: Vec<KeyPhrase> = if pattern
+        .split(' ')
+        .any(|word: &str| word == recovery::WILDCARD)
+    {
+        recovery::recover(&pattern, lang)
+            .map_err(Failure::rejected)?
+            .take(limit)
+            .collect()
+    } else {
+        recovery::correct_single_word(&pattern, lang)
+            .map_err(Failure::rejected)?
+            .into_iter()
+            .take(limit)
+            .collect()
+    };
+
+    let text: String = match format {
+        Format::Json => {
+            let phrases: Vec<String> = candidates
+                .iter()
+                .map(|keyphrase: &KeyPhrase| output::json_string(keyphrase.phrase()))
+                .collect();
+
+            output::json_object(&[
+                ("count", candidates.len().to_string()),
+                ("candidates", format!("[{}]", phrases.join(","))),
+            ])
+        }
+        Format::Plain | Format::Numbered => candidates
+            .iter()
+            .map(|keyphrase: &KeyPhrase| keyphrase.phrase())
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    };
+
+    if candidates.is_empty() {
+        Err(Failure::Report(text))
+    } else {
+        Ok(text)
+    }
+}
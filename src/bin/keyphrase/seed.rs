@@ -0,0 +1,95 @@
+//! `keyphrase seed`: the seed of a phrase and passphrase, as hex, base58 or a QR code.
+
+use crate::args::{Args, Format, Scheme};
+use crate::output;
+use crate::Failure;
+use keyphrase::{KeyPhrase, Language, Passphrase, Seed};
+use zeroize::Zeroize;
+
+pub const USAGE: &str = "\
+usage: keyphrase seed [PHRASE...] [--file PATH] [--passphrase PASSPHRASE] [--lang LANGUAGE]
+                      [--scheme bip39|keyphrase] [--encoding hex|base58|qr] [--json]
+
+Derive the seed of a phrase, given as arguments, read from a file with --file, or read from
+stdin, where it is prompted for on a terminal. The bip39 scheme, the default, gives the seed of
+every BIP39 wallet, the keyphrase scheme the legacy seed of this crate. The seed is written as hex
+by default; base58 and qr are only available when the tool is built with the encodings and qr
+features. The QR code is drawn with light blocks, for terminals with a dark background.";
+
+/// How the seed is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Hex,
+    #[cfg(feature = "encodings")]
+    Base58,
+    #[cfg(feature = "qr")]
+    Qr,
+}
+
+/// Run the subcommand, returning the encoded seed
+pub fn run(args: Vec<String>) -> Result<String, Failure> {
+    let args: Args = Args::parse(
+        args,
+        &[
+            "--file",
+            "--passphrase",
+            "--lang",
+            "--scheme",
+            "--encoding",
+        ],
+        &["--json"],
+    )?;
+    let lang: Language = args.language()?;
+    let format: Format = args.format()?;
+    let scheme: Scheme = args.scheme()?;
+    let encoding: Encoding = match args.value("--encoding") {
+        None | Some("hex") => Encoding::Hex,
+        #[cfg(feature = "encodings")]
+        Some("base58") => Encoding::Base58,
+        #[cfg(feature = "qr")]
+        Some("qr") => Encoding::Qr,
+        Some(encoding) => Err(format!("unknown or disabled encoding {}", encoding))?,
+    };
+
+    #[cfg(feature = "qr")]
+    {
+        if encoding == Encoding::Qr && format == Format::Json {
+            Err("a QR code can't be written as JSON".to_string())?;
+        }
+    }
+
+    let mut phrase: String = args.phrase()?;
+    let keyphrase: Result<KeyPhrase, _> = KeyPhrase::from_phrase(&phrase, lang);
+
+    phrase.zeroize();
+
+    let keyphrase: KeyPhrase = keyphrase.map_err(Failure::rejected)?;
+    let passphrase: Passphrase = Passphrase::new(args.value("--passphrase").unwrap_or(""));
+    let seed: Seed = scheme.seed(&keyphrase, &passphrase);
+
+    let encoded: String = match encoding {
+        Encoding::Hex => seed.to_hex(),
+        #[cfg(feature = "encodings")]
+        Encoding::Base58 => seed.to_base58(),
+        #[cfg(feature = "qr")]
+        Encoding::Qr => return Ok(output::qr(&seed.to_qr())),
+    };
+
+    Ok(match format {
+        Format::Plain | Format::Numbered => encoded,
+        Format::Json => output::json_object(&[
+            ("seed", output::json_string(&encoded)),
+            ("scheme", output::json_string(scheme.name())),
+            (
+                "encoding",
+                output::json_string(match encoding {
+                    Encoding::Hex => "hex",
+                    #[cfg(feature = "encodings")]
+                    Encoding::Base58 => "base58",
+                    #[cfg(feature = "qr")]
+                    Encoding::Qr => "qr",
+                }),
+            ),
+        ]),
+    })
+}
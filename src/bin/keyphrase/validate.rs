@@ -0,0 +1,114 @@
+//! `keyphrase validate`: check a phrase and report every problem at once.
+
+use crate::args::{self, Args, Format};
+use crate::output;
+use crate::Failure;
+use keyphrase::{KeyPhrase, Language, ValidationReport};
+
+pub const USAGE: &str = "\
+usage: keyphrase validate [PHRASE...] [--lang LANGUAGE] [--json]
+
+Check a phrase, read from stdin when it isn't given as arguments, and report the words that aren't
+in the wordlist, the languages the phrase could be in, and its checksum. Exits with 0 when the
+phrase is valid and 1 otherwise. Word positions start at 1.";
+
+/// Run the subcommand, returning the report of a valid phrase, or failing with the report of an
+/// invalid one
+pub fn run(args: Vec<String>) -> Result<String, Failure> {
+    let args: Args = Args::parse(args, &["--lang"], &["--json"])?;
+    let lang: Language = args.language()?;
+    let phrase: String = args.phrase()?;
+
+    let report: ValidationReport = KeyPhrase::validate_detailed(&phrase, lang);
+    let text: String = match args.format()? {
+        Format::Json => json(&report),
+        Format::Plain | Format::Numbered => plain(&report, lang),
+    };
+
+    if report.is_valid() {
+        Ok(text)
+    } else {
+        Err(Failure::Report(text))
+    }
+}
+
+/// Write a report as one problem per line
+fn plain(report: &ValidationReport, lang: Language) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    if !report.word_count_valid {
+        lines.push(format!(
+            "{} words, a phrase has 12, 15, 18, 21 or 24",
+            report.word_count
+        ));
+    }
+
+    report
+        .invalid_words
+        .iter()
+        .for_each(|(position, word): &(usize, String)| {
+            lines.push(format!(
+                "word {} \"{}\" isn't in the {} wordlist",
+                position + 1,
+                word,
+                args::language_name(lang)
+            ))
+        });
+
+    if !report.language_hints.is_empty() {
+        let hints: Vec<&str> = report
+            .language_hints
+            .iter()
+            .map(|&hint: &Language| args::language_name(hint))
+            .collect();
+
+        lines.push(format!(
+            "every word is in the {} wordlist",
+            hints.join(", ")
+        ));
+    }
+
+    lines.push(
+        match report.checksum_valid {
+            Some(true) => "valid",
+            Some(false) => "invalid checksum",
+            None => "checksum not checked",
+        }
+        .to_string(),
+    );
+
+    lines.join("\n")
+}
+
+/// Write a report as a JSON object
+fn json(report: &ValidationReport) -> String {
+    let invalid_words: Vec<String> = report
+        .invalid_words
+        .iter()
+        .map(|(position, word): &(usize, String)| {
+            output::json_object(&[
+                ("position", (position + 1).to_string()),
+                ("word", output::json_string(word)),
+            ])
+        })
+        .collect();
+    let language_hints: Vec<String> = report
+        .language_hints
+        .iter()
+        .map(|&hint: &Language| output::json_string(args::language_name(hint)))
+        .collect();
+
+    output::json_object(&[
+        ("valid", report.is_valid().to_string()),
+        ("words", report.word_count.to_string()),
+        ("word_count_valid", report.word_count_valid.to_string()),
+        ("invalid_words", format!("[{}]", invalid_words.join(","))),
+        ("language_hints", format!("[{}]", language_hints.join(","))),
+        (
+            "checksum_valid",
+            report
+                .checksum_valid
+                .map_or("null".to_string(), |valid: bool| valid.to_string()),
+        ),
+    ])
+}
@@ -0,0 +1,397 @@
+//! `keyphrase wizard`: the interactive recovery of a phrase, word by word, built with the `tui`
+//! feature.
+//!
+//! The wizard draws on the alternate screen of stderr, which is cleared when it exits, so the
+//! phrase doesn't stay in the scrollback of the terminal.
+
+use crate::args::{Args, Scheme};
+use crate::Failure;
+use crossterm::cursor::MoveTo;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+#[cfg(feature = "bip32")]
+use keyphrase::hd::Account;
+use keyphrase::{
+    KeyPhrase, KeyPhraseType, Language, Passphrase, PhraseValidator, Seed, WordStatus,
+};
+use std::io::{self, IsTerminal, Stderr, Write};
+use zeroize::Zeroize;
+
+pub const USAGE: &str = "\
+usage: keyphrase wizard [--words 12|15|18|21|24] [--lang LANGUAGE] [--scheme bip39|keyphrase]
+
+Recover a phrase interactively: every word is checked as soon as it is typed, with the words of
+the wordlist starting with it as suggestions, and the checksum once the last word is entered.
+The passphrase is then asked for, and the fingerprints of the seed are shown to compare with the
+wallet being recovered. Once confirmed, the phrase is printed on stdout.
+
+keys: Tab completes the word, Enter or Space accepts it, Backspace on an empty word goes back to
+the previous one, Esc quits.";
+
+/// Most suggestions shown under the word being typed
+const SUGGESTIONS: usize = 8;
+
+/// Words per line in the list of entered words
+const COLUMNS: usize = 4;
+
+/// Run the subcommand, returning the confirmed phrase
+pub fn run(args: Vec<String>) -> Result<String, Failure> {
+    let args: Args = Args::parse(args, &["--words", "--lang", "--scheme"], &[])?;
+
+    if let Some(extra) = args.positional().first() {
+        Err(format!("unexpected argument {}", extra))?;
+    }
+
+    let keyphrase_type: KeyPhraseType = args.keyphrase_type()?;
+    let lang: Language = args.language()?;
+    let scheme: Scheme = args.scheme()?;
+
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(Failure::Rejected(
+            "the wizard needs a terminal, use validate or recover in scripts".to_string(),
+        ));
+    }
+
+    let mut screen: Screen = Screen::enter().map_err(Failure::rejected)?;
+
+    let keyphrase: KeyPhrase = match enter_words(&mut screen, keyphrase_type, lang)? {
+        Some(keyphrase) => keyphrase,
+        None => return Err(Failure::Rejected("cancelled".to_string())),
+    };
+
+    loop {
+        let passphrase: Passphrase = match enter_passphrase(&mut screen)? {
+            Some(passphrase) => passphrase,
+            None => return Err(Failure::Rejected("cancelled".to_string())),
+        };
+
+        screen
+            .draw(&["Deriving the seed...".to_string()])
+            .map_err(Failure::rejected)?;
+
+        let seed: Seed = scheme.seed(&keyphrase, &passphrase);
+
+        match confirm(&mut screen, &keyphrase, &seed, scheme)? {
+            Some(true) => return Ok(keyphrase.phrase().to_string()),
+            Some(false) => continue,
+            None => return Err(Failure::Rejected("cancelled".to_string())),
+        }
+    }
+}
+
+/// Read words until they make a valid phrase, or `None` if the user quits
+fn enter_words(
+    screen: &mut Screen,
+    keyphrase_type: KeyPhraseType,
+    lang: Language,
+) -> Result<Option<KeyPhrase>, Failure> {
+    let mut validator: PhraseValidator = PhraseValidator::with_type(keyphrase_type, lang);
+    let mut entry: Entry = Entry::default();
+    let mut message: String = String::new();
+
+    loop {
+        let suggestions: Vec<&str> = if entry.input.is_empty() {
+            Vec::new()
+        } else {
+            lang.words_with_prefix(&entry.input)
+        };
+
+        screen
+            .draw(&words_screen(
+                &entry,
+                &suggestions,
+                &message,
+                keyphrase_type,
+            ))
+            .map_err(Failure::rejected)?;
+
+        match read_key().map_err(Failure::rejected)? {
+            Key::Cancel => return Ok(None),
+            Key::Char(c) if !c.is_whitespace() => {
+                entry.input.extend(c.to_lowercase());
+                message.clear();
+            }
+            Key::Backspace => {
+                if entry.input.is_empty() {
+                    if let Some(word) = entry.words.pop() {
+                        validator.pop_word();
+                        entry.input = word;
+                    }
+                } else {
+                    entry.input.pop();
+                }
+                message.clear();
+            }
+            Key::Tab => {
+                if let Some(&word) = suggestions.first() {
+                    entry.input.zeroize();
+                    entry.input = word.to_string();
+                }
+            }
+            Key::Char(_) | Key::Enter => {
+                if entry.input.is_empty() {
+                    continue;
+                }
+
+                // A unique prefix is enough, like the four letters of English words
+                let word: String = if suggestions.len() == 1 {
+                    suggestions[0].to_string()
+                } else {
+                    entry.input.clone()
+                };
+
+                match validator.push_word(&word) {
+                    WordStatus::Unknown => {
+                        message = format!("\"{}\" isn't in the wordlist", entry.input);
+                    }
+                    WordStatus::TooManyWords => {
+                        message = "every word is entered, go back to the wrong one with Backspace"
+                            .to_string();
+                    }
+                    WordStatus::Accepted { .. } => {
+                        entry.accept(word);
+                        message.clear();
+                    }
+                    WordStatus::ChecksumMismatch => {
+                        entry.accept(word);
+                        message = "the checksum doesn't match: a word is wrong, go back to it \
+                                   with Backspace"
+                            .to_string();
+                    }
+                    WordStatus::Complete => {
+                        entry.accept(word);
+
+                        return validator.finish().map(Some).map_err(Failure::rejected);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Read the passphrase, hidden as it is typed, or `None` if the user quits
+fn enter_passphrase(screen: &mut Screen) -> Result<Option<Passphrase>, Failure> {
+    let mut input: String = String::new();
+
+    loop {
+        screen
+            .draw(&[
+                "The checksum is valid.".to_string(),
+                String::new(),
+                "Enter the passphrase of the wallet, or nothing if it has none.".to_string(),
+                format!("passphrase: {}", "*".repeat(input.chars().count())),
+            ])
+            .map_err(Failure::rejected)?;
+
+        match read_key().map_err(Failure::rejected)? {
+            Key::Cancel => {
+                input.zeroize();
+
+                return Ok(None);
+            }
+            Key::Enter => return Ok(Some(Passphrase::new(input))),
+            Key::Backspace => {
+                input.pop();
+            }
+            Key::Char(c) => input.push(c),
+            Key::Tab => {}
+        }
+    }
+}
+
+/// Show the fingerprints of the seed and ask whether they match the wallet, `Some(false)` to
+/// enter another passphrase and `None` if the user quits
+fn confirm(
+    screen: &mut Screen,
+    keyphrase: &KeyPhrase,
+    seed: &Seed,
+    scheme: Scheme,
+) -> Result<Option<bool>, Failure> {
+    let mut lines: Vec<String> = vec![
+        format!("{} seed of the phrase", scheme.name()),
+        String::new(),
+        format!("phrase fingerprint:     {}", keyphrase.fingerprint()),
+    ];
+
+    #[cfg(feature = "bip32")]
+    {
+        let fingerprint: [u8; 4] = seed.fingerprint().map_err(Failure::rejected)?;
+        let account: Account = Account::bip84(seed, 0).map_err(Failure::rejected)?;
+
+        lines.push(format!(
+            "master key fingerprint: {}",
+            fingerprint
+                .iter()
+                .map(|byte: &u8| format!("{:02x}", byte))
+                .collect::<String>()
+        ));
+        lines.push(format!("first BIP84 account:    {}", account.xpub().to_xpub()));
+    }
+    #[cfg(not(feature = "bip32"))]
+    let _ = seed;
+
+    lines.push(String::new());
+    lines.push(
+        "Does this match the wallet? y: yes, n: enter another passphrase, Esc: quit".to_string(),
+    );
+
+    screen.draw(&lines).map_err(Failure::rejected)?;
+
+    loop {
+        match read_key().map_err(Failure::rejected)? {
+            Key::Char('y') | Key::Char('Y') => return Ok(Some(true)),
+            Key::Char('n') | Key::Char('N') => return Ok(Some(false)),
+            Key::Cancel => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Draw the words entered so far, the word being typed and its suggestions
+fn words_screen(
+    entry: &Entry,
+    suggestions: &[&str],
+    message: &str,
+    keyphrase_type: KeyPhraseType,
+) -> Vec<String> {
+    let mut lines: Vec<String> = vec![
+        format!(
+            "Recovering a {} word phrase. Tab completes, Enter accepts, Esc quits.",
+            keyphrase_type.word_count()
+        ),
+        String::new(),
+    ];
+
+    lines.extend(entry.words.chunks(COLUMNS).enumerate().map(
+        |(row, words): (usize, &[String])| {
+            words
+                .iter()
+                .enumerate()
+                .map(|(column, word): (usize, &String)| {
+                    format!("{:2}. {:<12}", row * COLUMNS + column + 1, word)
+                })
+                .collect::<String>()
+        },
+    ));
+    lines.push(String::new());
+
+    if suggestions.len() > SUGGESTIONS {
+        lines.push(format!(
+            "{} ... ({} words)",
+            suggestions[..SUGGESTIONS].join(" "),
+            suggestions.len()
+        ));
+    } else {
+        lines.push(suggestions.join(" "));
+    }
+
+    lines.push(message.to_string());
+    lines.push(format!(
+        "word {}: {}",
+        entry.words.len() + 1,
+        entry.input
+    ));
+
+    lines
+}
+
+/// The words entered so far and the one being typed, wiped when dropped
+#[derive(Default)]
+struct Entry {
+    words: Vec<String>,
+    input: String,
+}
+
+impl Entry {
+    /// Add a word accepted by the validator and start the next one
+    fn accept(&mut self, word: String) {
+        self.words.push(word);
+        self.input.zeroize();
+    }
+}
+
+impl Drop for Entry {
+    fn drop(&mut self) {
+        self.words.zeroize();
+        self.input.zeroize();
+    }
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped
+struct Screen {
+    out: Stderr,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        let mut out: Stderr = io::stderr();
+
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen)?;
+
+        Ok(Screen { out })
+    }
+
+    /// Replace the content of the screen with lines of text
+    fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        queue!(self.out, Clear(ClearType::All))?;
+
+        for (row, line) in lines.iter().enumerate() {
+            queue!(self.out, MoveTo(0, row as u16), Print(line))?;
+        }
+
+        self.out.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        execute!(self.out, Clear(ClearType::All), LeaveAlternateScreen).ok();
+        terminal::disable_raw_mode().ok();
+    }
+}
+
+/// A key pressed by the user
+enum Key {
+    Char(char),
+    Backspace,
+    Tab,
+    Enter,
+    /// Esc, Ctrl-C or Ctrl-D
+    Cancel,
+}
+
+/// Wait for the next key the wizard knows about
+fn read_key() -> io::Result<Key> {
+    loop {
+        let KeyEvent {
+            code,
+            modifiers,
+            kind,
+            ..
+        } = match event::read()? {
+            Event::Key(key) => key,
+            _ => continue,
+        };
+
+        if kind != KeyEventKind::Press {
+            continue;
+        }
+
+        return Ok(match code {
+            KeyCode::Char('c') | KeyCode::Char('d')
+                if modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                Key::Cancel
+            }
+            KeyCode::Esc => Key::Cancel,
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::Enter => Key::Enter,
+            _ => continue,
+        });
+    }
+}
@@ -0,0 +1,138 @@
+//! Conversions between [`KeyPhrase`][KeyPhrase] and the `Mnemonic` of the `bip39` crate, enabled
+//! by the `bip39` feature.
+//!
+//! The entropy is preserved exactly, so both represent the same words and derive the same BIP39
+//! seed. The conversions are fallible: the `bip39` crate supports languages this crate doesn't
+//! (such as Czech and Portuguese), and this crate supports lengths `bip39` doesn't (see
+//! [`KeyPhrase::from_entropy_nonstandard()`][KeyPhrase::from_entropy_nonstandard()]).
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+//! use std::convert::TryFrom;
+//!
+//! let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+//!
+//! let mnemonic = bip39::Mnemonic::try_from(keyphrase.clone()).unwrap();
+//! let restored = KeyPhrase::try_from(mnemonic).unwrap();
+//!
+//! assert_eq!(keyphrase.phrase(), restored.phrase());
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//! [KeyPhrase::from_entropy_nonstandard()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_nonstandard
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use std::convert::TryFrom;
+
+impl From<Language> for bip39::Language {
+    fn from(lang: Language) -> bip39::Language {
+        match lang {
+            Language::English => bip39::Language::English,
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => bip39::Language::SimplifiedChinese,
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => bip39::Language::TraditionalChinese,
+            #[cfg(feature = "french")]
+            Language::French => bip39::Language::French,
+            #[cfg(feature = "italian")]
+            Language::Italian => bip39::Language::Italian,
+            #[cfg(feature = "japanese")]
+            Language::Japanese => bip39::Language::Japanese,
+            #[cfg(feature = "korean")]
+            Language::Korean => bip39::Language::Korean,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => bip39::Language::Spanish,
+        }
+    }
+}
+
+/// Returns an `Error` of kind `ErrorKind::UnsupportedLanguage` for the languages this crate
+/// doesn't have, or whose feature is not enabled
+impl TryFrom<bip39::Language> for Language {
+    type Error = Error;
+
+    fn try_from(lang: bip39::Language) -> Result<Language, Error> {
+        // Other crates may enable more languages of `bip39` than this crate knows about
+        #[allow(unreachable_patterns)]
+        let lang: Language = match lang {
+            bip39::Language::English => Language::English,
+            #[cfg(feature = "chinese-simplified")]
+            bip39::Language::SimplifiedChinese => Language::ChineseSimplified,
+            #[cfg(feature = "chinese-traditional")]
+            bip39::Language::TraditionalChinese => Language::ChineseTraditional,
+            #[cfg(feature = "french")]
+            bip39::Language::French => Language::French,
+            #[cfg(feature = "italian")]
+            bip39::Language::Italian => Language::Italian,
+            #[cfg(feature = "japanese")]
+            bip39::Language::Japanese => Language::Japanese,
+            #[cfg(feature = "korean")]
+            bip39::Language::Korean => Language::Korean,
+            #[cfg(feature = "spanish")]
+            bip39::Language::Spanish => Language::Spanish,
+            _ => Err(ErrorKind::UnsupportedLanguage)?,
+        };
+
+        Ok(lang)
+    }
+}
+
+/// Returns an `Error` of kind `ErrorKind::UnsupportedLanguage` if the language of the mnemonic
+/// is not supported
+impl TryFrom<bip39::Mnemonic> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from(mnemonic: bip39::Mnemonic) -> Result<KeyPhrase, Error> {
+        let lang: Language = Language::try_from(mnemonic.language())?;
+
+        KeyPhrase::from_entropy(&mnemonic.to_entropy(), lang)
+    }
+}
+
+/// Returns an `Error` of kind `ErrorKind::InvalidKeysize` if the keyphrase has a length that
+/// `bip39` doesn't support
+impl TryFrom<KeyPhrase> for bip39::Mnemonic {
+    type Error = Error;
+
+    fn try_from(keyphrase: KeyPhrase) -> Result<bip39::Mnemonic, Error> {
+        let mnemonic: bip39::Mnemonic =
+            bip39::Mnemonic::from_entropy_in(keyphrase.language().into(), keyphrase.entropy())
+                .map_err(|_| ErrorKind::InvalidKeysize(keyphrase.entropy().len() * 8))?;
+
+        Ok(mnemonic)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+
+    #[test]
+    fn bip39_roundtrip() {
+        for lang in Language::all() {
+            let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, lang);
+
+            let mnemonic: bip39::Mnemonic = bip39::Mnemonic::try_from(keyphrase.clone()).unwrap();
+
+            assert_eq!(mnemonic.to_entropy(), keyphrase.entropy());
+
+            let restored: KeyPhrase = KeyPhrase::try_from(mnemonic).unwrap();
+
+            assert_eq!(restored.phrase(), keyphrase.phrase());
+            assert_eq!(restored.language(), lang);
+        }
+    }
+
+    #[test]
+    fn bip39_nonstandard_length() {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy_nonstandard(&[0; 12], Language::English).unwrap();
+
+        assert!(bip39::Mnemonic::try_from(keyphrase).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+//! Blockchain Commons Bytewords, writing every byte as one of 256 four letters words.
+//!
+//! A CRC32 of the data is appended before encoding. In the minimal style, which UR uses, every
+//! word is shortened to its first and last letters, which are unique among the words.
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use once_cell::sync::Lazy;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The 256 bytewords, in the order of the bytes they encode
+static WORDLIST: Lazy<Vec<&'static str>> =
+    sync_lazy! { include_str!("langs/bytewords.txt").split_whitespace().collect() };
+
+/// Bytes of the checksum appended to the data
+const CHECKSUM_BYTES: usize = 4;
+
+/// The ways of writing bytewords
+///
+/// See [`KeyPhrase::to_bytewords()`][KeyPhrase::to_bytewords()].
+///
+/// [KeyPhrase::to_bytewords()]: ./struct.KeyPhrase.html#method.to_bytewords
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BytewordsStyle {
+    /// Whole words separated by spaces, to write down or read aloud
+    Standard,
+    /// Whole words separated by hyphens, to use in URIs
+    Uri,
+    /// The first and last letters of every word, without separators, for QR codes
+    Minimal,
+}
+
+impl BytewordsStyle {
+    /// The separator between words in this style
+    fn separator(self) -> Option<char> {
+        match self {
+            BytewordsStyle::Standard => Some(' '),
+            BytewordsStyle::Uri => Some('-'),
+            BytewordsStyle::Minimal => None,
+        }
+    }
+}
+
+impl KeyPhrase {
+    /// Get the entropy of the keyphrase as bytewords in the given style
+    ///
+    /// Bytewords are an alternative to the keyphrase itself: every byte of the entropy is one of
+    /// 256 four letters words, followed by 4 words of a CRC32 checksum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{BytewordsStyle, KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let encoded = keyphrase.to_bytewords(BytewordsStyle::Standard);
+    ///
+    /// assert_eq!(encoded.split(' ').count(), 20);
+    ///
+    /// let decoded = KeyPhrase::from_bytewords(&encoded, BytewordsStyle::Standard, Language::English)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(decoded.phrase(), keyphrase.phrase());
+    /// ```
+    pub fn to_bytewords(&self, style: BytewordsStyle) -> String {
+        encode(self.entropy(), style)
+    }
+
+    /// Parse a keyphrase in `lang` from bytewords, in either case, written in the given style
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the words are not bytewords of
+    /// this style, `ErrorKind::InvalidChecksum` if their checksum doesn't match, or
+    /// `ErrorKind::InvalidKeysize` if the bytes can't be the entropy of a keyphrase.
+    pub fn from_bytewords(
+        encoded: &str,
+        style: BytewordsStyle,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        #[allow(unused_mut)]
+        let mut entropy: Vec<u8> = decode(encoded, style)?;
+        let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&entropy, lang);
+
+        #[cfg(feature = "zeroize")]
+        entropy.zeroize();
+
+        keyphrase
+    }
+}
+
+/// Encode bytes and their checksum as bytewords in the given style
+pub(crate) fn encode(data: &[u8], style: BytewordsStyle) -> String {
+    let mut encoded: String = String::with_capacity((data.len() + CHECKSUM_BYTES) * 5);
+
+    data.iter()
+        .chain(&crc32(data).to_be_bytes())
+        .enumerate()
+        .for_each(|(i, &byte): (usize, &u8)| {
+            let word: &str = WORDLIST[byte as usize];
+
+            match style.separator() {
+                Some(separator) => {
+                    if i > 0 {
+                        encoded.push(separator);
+                    }
+
+                    encoded.push_str(word);
+                }
+                None => {
+                    encoded.push_str(&word[..1]);
+                    encoded.push_str(&word[3..]);
+                }
+            }
+        });
+
+    encoded
+}
+
+/// Decode bytewords in the given style, in either case, and verify their checksum
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if a word isn't a byteword, or
+/// `ErrorKind::InvalidChecksum` if the checksum doesn't match.
+pub(crate) fn decode(encoded: &str, style: BytewordsStyle) -> Result<Vec<u8>, Error> {
+    let encoded: String = encoded.trim().to_ascii_lowercase();
+
+    let bytes: Vec<u8> = match style.separator() {
+        Some(separator) => encoded
+            .split(separator)
+            .map(|word: &str| {
+                WORDLIST
+                    .binary_search(&word)
+                    .map(|position: usize| position as u8)
+                    .map_err(|_| ErrorKind::InvalidEncoding("invalid byteword"))
+            })
+            .collect::<Result<Vec<u8>, ErrorKind>>()?,
+        None => {
+            if !encoded.is_ascii() || encoded.len() % 2 != 0 {
+                Err(ErrorKind::InvalidEncoding("invalid bytewords length"))?;
+            }
+
+            encoded
+                .as_bytes()
+                .chunks(2)
+                .map(|pair: &[u8]| {
+                    WORDLIST
+                        .iter()
+                        .position(|word: &&str| {
+                            word.as_bytes()[0] == pair[0] && word.as_bytes()[3] == pair[1]
+                        })
+                        .map(|position: usize| position as u8)
+                        .ok_or(ErrorKind::InvalidEncoding("invalid byteword"))
+                })
+                .collect::<Result<Vec<u8>, ErrorKind>>()?
+        }
+    };
+
+    strip_checksum(bytes)
+}
+
+/// Split the checksum off decoded bytes and verify it
+fn strip_checksum(mut bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+    if bytes.len() < CHECKSUM_BYTES {
+        Err(ErrorKind::InvalidEncoding("missing bytewords checksum"))?;
+    }
+
+    let checksum: Vec<u8> = bytes.split_off(bytes.len() - CHECKSUM_BYTES);
+
+    if crc32(&bytes).to_be_bytes()[..] != checksum[..] {
+        Err(ErrorKind::InvalidChecksum)?;
+    }
+
+    Ok(bytes)
+}
+
+/// Compute the CRC32 (ISO-HDLC, as zlib) of the data
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc: u32, &byte: &u8| {
+        (0..8).fold(crc ^ u32::from(byte), |crc: u32, _| {
+            if crc & 1 == 1 {
+                crc >> 1 ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bytewords_styles() {
+        let data: [u8; 5] = [0, 1, 2, 128, 255];
+        let vectors: [(BytewordsStyle, &str); 3] = [
+            (
+                BytewordsStyle::Standard,
+                "able acid also lava zoom jade need echo taxi",
+            ),
+            (
+                BytewordsStyle::Uri,
+                "able-acid-also-lava-zoom-jade-need-echo-taxi",
+            ),
+            (BytewordsStyle::Minimal, "aeadaolazmjendeoti"),
+        ];
+
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+
+        vectors
+            .iter()
+            .for_each(|&(style, encoded): &(BytewordsStyle, &str)| {
+                assert_eq!(encode(&data, style), encoded);
+                assert_eq!(decode(encoded, style).unwrap(), data);
+                assert_eq!(decode(&encoded.to_uppercase(), style).unwrap(), data);
+            });
+    }
+
+    #[test]
+    fn bytewords_invalid() {
+        // wrong checksum, not a word, odd length, wrong separator
+        assert!(decode("aeadaolazmjendeota", BytewordsStyle::Minimal).is_err());
+        assert!(decode("aeadaolazmjendeoxx", BytewordsStyle::Minimal).is_err());
+        assert!(decode("aeadaolazmjendeot", BytewordsStyle::Minimal).is_err());
+        assert!(decode(
+            "able acid also lava zoom jade need echo tuna",
+            BytewordsStyle::Standard
+        )
+        .is_err());
+        assert!(decode(
+            "able acid also lava zoom jade need echo taxx",
+            BytewordsStyle::Standard
+        )
+        .is_err());
+        assert!(decode(
+            "able acid also lava zoom jade need echo taxi",
+            BytewordsStyle::Uri
+        )
+        .is_err());
+        assert!(decode("", BytewordsStyle::Minimal).is_err());
+        assert!(decode("", BytewordsStyle::Standard).is_err());
+    }
+
+    #[test]
+    fn keyphrase_bytewords() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(
+            &[
+                0x59, 0xf2, 0x29, 0x3a, 0x5b, 0xce, 0x7d, 0x4d, 0xe5, 0x9e, 0x71, 0xb4, 0x20, 0x7a,
+                0xc5, 0xd2,
+            ],
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(
+            keyphrase.to_bytewords(BytewordsStyle::Standard),
+            "hawk whiz diet fact help taco kiwi gift view noon jugs quiz crux kiln silk tied omit keno lung jade"
+        );
+        assert_eq!(
+            keyphrase.to_bytewords(BytewordsStyle::Minimal),
+            "hkwzdtfthptokigtvwnnjsqzcxknsktdotkolgje"
+        );
+        assert_eq!(
+            KeyPhrase::from_bytewords(
+                &keyphrase.to_bytewords(BytewordsStyle::Uri),
+                BytewordsStyle::Uri,
+                Language::English
+            )
+            .unwrap()
+            .phrase(),
+            keyphrase.phrase()
+        );
+
+        // 15 bytes are valid bytewords, but not the entropy of a keyphrase
+        assert!(KeyPhrase::from_bytewords(
+            &encode(&[0; 15], BytewordsStyle::Minimal),
+            BytewordsStyle::Minimal,
+            Language::English
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::crypto::sha256_first_byte;
+
+/// The checksum appended to the entropy of a [`KeyPhrase`][KeyPhrase]
+///
+/// The standard scheme is [`Sha256Checksum`][Sha256Checksum], which is what every constructor
+/// uses unless one is given explicitly, e.g. through
+/// [`KeyPhrase::from_entropy_with_checksum()`][KeyPhrase::from_entropy_with_checksum()].
+///
+/// Implementing this trait allows experimenting with alternative checksums without having to
+/// vendor a modified copy of the crate. Phrases using any other scheme are **not** compatible
+/// with BIP39 wallets.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{ChecksumScheme, KeyPhrase, Language};
+///
+/// struct XorChecksum;
+///
+/// impl ChecksumScheme for XorChecksum {
+///     fn checksum_byte(&self, entropy: &[u8]) -> u8 {
+///         entropy.iter().fold(0, |acc, byte| acc ^ byte)
+///     }
+/// }
+///
+/// let entropy = &[0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84, 0x6A, 0x79];
+/// let keyphrase = KeyPhrase::from_entropy_with_checksum(entropy, Language::English, &XorChecksum).unwrap();
+///
+/// assert!(KeyPhrase::from_phrase_with_checksum(keyphrase.phrase(), Language::English, &XorChecksum).is_ok());
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Sha256Checksum]: ./struct.Sha256Checksum.html
+/// [KeyPhrase::from_entropy_with_checksum()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_with_checksum
+pub trait ChecksumScheme {
+    /// Compute the checksum byte for the given entropy
+    ///
+    /// Only the leading [`KeyPhraseType::checksum_bits()`][KeyPhraseType::checksum_bits()] bits
+    /// of the byte end up in the phrase.
+    ///
+    /// [KeyPhraseType::checksum_bits()]: ../keyphrase_type/enum.KeyPhraseType.html#method.checksum_bits
+    fn checksum_byte(&self, entropy: &[u8]) -> u8;
+}
+
+/// The standard BIP39 checksum: the first bits of the SHA256 hash of the entropy
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha256Checksum;
+
+impl ChecksumScheme for Sha256Checksum {
+    fn checksum_byte(&self, entropy: &[u8]) -> u8 {
+        sha256_first_byte(entropy)
+    }
+}
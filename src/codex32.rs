@@ -0,0 +1,296 @@
+//! codex32 (BIP-93) backups of a [`KeyPhrase`][KeyPhrase], enabled by the `codex32` feature.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The bech32 characters, in the order of their values
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Human readable part and separator of every codex32 string
+const PREFIX: &str = "ms1";
+
+/// Characters of the threshold, the identifier and the share index
+const HEADER_CHARS: usize = 6;
+
+/// Characters of the checksum of codex32 strings up to 93 characters long
+const CHECKSUM_CHARS: usize = 13;
+
+/// Longest data part with a 13 characters checksum
+const MAX_DATA_CHARS: usize = 93;
+
+/// Share index of the secret itself
+const SECRET_INDEX: u8 = b's';
+
+/// The residue of a valid checksum
+const CHECKSUM_CONST: u128 = 0x1_0ce0_795c_2fd1_e62a;
+
+impl KeyPhrase {
+    /// Get the entropy as an unshared codex32 secret, named with a 4 characters `identifier`
+    ///
+    /// codex32 strings are bech32 strings with a stronger checksum, which can be verified by
+    /// hand with paper worksheets. The result can be parsed back with
+    /// [`KeyPhrase::from_codex32()`][KeyPhrase::from_codex32()]. Returns an `Error` of kind
+    /// `ErrorKind::InvalidEncoding` if the identifier is not 4 bech32 characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let encoded = keyphrase.to_codex32("cash").unwrap();
+    ///
+    /// assert!(encoded.starts_with("ms10cashs"));
+    ///
+    /// let decoded = KeyPhrase::from_codex32(&encoded, Language::English).unwrap();
+    ///
+    /// assert_eq!(decoded.phrase(), keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase::from_codex32()]: ../keyphrase/struct.KeyPhrase.html#method.from_codex32
+    pub fn to_codex32(&self, identifier: &str) -> Result<String, Error> {
+        let identifier: Vec<u8> = identifier
+            .bytes()
+            .map(value)
+            .collect::<Option<Vec<u8>>>()
+            .filter(|identifier: &Vec<u8>| identifier.len() == 4)
+            .ok_or(ErrorKind::InvalidEncoding(
+                "the identifier must be 4 bech32 characters",
+            ))?;
+
+        let mut data: Vec<u8> = Vec::with_capacity(MAX_DATA_CHARS);
+        data.push(value(b'0').expect("0 is a bech32 character"));
+        data.extend_from_slice(&identifier);
+        data.push(value(SECRET_INDEX).expect("s is a bech32 character"));
+        data.extend(bytes_to_values(self.entropy()));
+
+        let residue: u128 = polymod(data.iter().chain(&[0; CHECKSUM_CHARS])) ^ CHECKSUM_CONST;
+        data.extend(
+            (0..CHECKSUM_CHARS)
+                .rev()
+                .map(|i: usize| ((residue >> (5 * i)) & 31) as u8),
+        );
+
+        let encoded: String = data
+            .iter()
+            .map(|&value: &u8| CHARSET[value as usize] as char)
+            .collect();
+
+        #[cfg(feature = "zeroize")]
+        data.zeroize();
+
+        Ok(String::from(PREFIX) + &encoded)
+    }
+
+    /// Parse a keyphrase previously written as a codex32 secret
+    ///
+    /// The string can be all lowercase or all uppercase. Only the secret itself, with the share
+    /// index `s`, can be parsed: shares of a split secret must be combined first.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChecksum` if the checksum doesn't match,
+    /// `ErrorKind::InvalidEncoding` if the string is not a codex32 secret, or
+    /// `ErrorKind::InvalidKeysize` if the secret can't be the entropy of a keyphrase.
+    pub fn from_codex32(encoded: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        if encoded.bytes().any(|byte: u8| byte.is_ascii_lowercase())
+            && encoded.bytes().any(|byte: u8| byte.is_ascii_uppercase())
+        {
+            Err(ErrorKind::InvalidEncoding("mixed case codex32 string"))?;
+        }
+
+        let encoded: String = encoded.to_ascii_lowercase();
+
+        if !encoded.starts_with(PREFIX) {
+            Err(ErrorKind::InvalidEncoding("codex32 strings start with ms1"))?;
+        }
+
+        #[allow(unused_mut)]
+        let mut data: Vec<u8> = encoded[PREFIX.len()..]
+            .bytes()
+            .map(value)
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(ErrorKind::InvalidEncoding("invalid codex32 character"))?;
+
+        if data.len() < HEADER_CHARS + CHECKSUM_CHARS || data.len() > MAX_DATA_CHARS {
+            Err(ErrorKind::InvalidEncoding("invalid codex32 length"))?;
+        }
+
+        if polymod(data.iter()) != CHECKSUM_CONST {
+            Err(ErrorKind::InvalidChecksum)?;
+        }
+
+        let header: &[u8] = &encoded.as_bytes()[PREFIX.len()..PREFIX.len() + HEADER_CHARS];
+
+        if header[0] != b'0' && !(b'2'..=b'9').contains(&header[0]) {
+            Err(ErrorKind::InvalidEncoding("invalid codex32 threshold"))?;
+        }
+
+        if header[5] != SECRET_INDEX {
+            Err(ErrorKind::InvalidEncoding(
+                "this is a codex32 share, not the secret",
+            ))?;
+        }
+
+        let payload: &[u8] = &data[HEADER_CHARS..data.len() - CHECKSUM_CHARS];
+
+        if payload.len() * 5 % 8 > 4 {
+            Err(ErrorKind::InvalidEncoding("invalid codex32 padding"))?;
+        }
+
+        #[allow(unused_mut)]
+        let mut entropy: Vec<u8> = values_to_bytes(payload);
+        let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&entropy, lang);
+
+        #[cfg(feature = "zeroize")]
+        {
+            entropy.zeroize();
+            data.zeroize();
+        }
+
+        keyphrase
+    }
+}
+
+/// Get the value of a lowercase bech32 character
+fn value(character: u8) -> Option<u8> {
+    CHARSET
+        .iter()
+        .position(|&c: &u8| c == character)
+        .map(|position: usize| position as u8)
+}
+
+/// Compute the residue of the codex32 BCH code over the values
+fn polymod<'a, I: Iterator<Item = &'a u8>>(values: I) -> u128 {
+    const GENERATOR: [u128; 5] = [
+        0x1_9dc5_00ce_73fd_e210,
+        0x1_bfae_00de_f77f_e529,
+        0x1_fbd9_20ff_fe7b_ee52,
+        0x1_7396_40bd_eee3_fdad,
+        0x0_7729_a039_cfc7_5f5a,
+    ];
+
+    values.fold(0x0231_81b3, |residue: u128, &value: &u8| {
+        let top: u128 = residue >> 60;
+
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|&(i, _): &(usize, &u128)| (top >> i) & 1 == 1)
+            .fold(
+                (residue & 0x0fff_ffff_ffff_ffff) << 5 ^ value as u128,
+                |residue: u128, (_, generator): (usize, &u128)| residue ^ generator,
+            )
+    })
+}
+
+/// Split bytes into 5 bits values, padding the last one with zero bits
+fn bytes_to_values(bytes: &[u8]) -> Vec<u8> {
+    let mut values: Vec<u8> = Vec::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut accumulator: u16 = 0;
+    let mut bits: usize = 0;
+
+    for &byte in bytes {
+        accumulator = accumulator << 8 | byte as u16;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            values.push((accumulator >> bits) as u8 & 31);
+        }
+
+        accumulator &= (1 << bits) - 1;
+    }
+
+    if bits > 0 {
+        values.push((accumulator << (5 - bits)) as u8 & 31);
+    }
+
+    values
+}
+
+/// Join 5 bits values into bytes, dropping the padding bits
+fn values_to_bytes(values: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(values.len() * 5 / 8);
+    let mut accumulator: u16 = 0;
+    let mut bits: usize = 0;
+
+    for &value in values {
+        accumulator = accumulator << 5 | value as u16;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codex32_test_vectors() {
+        // BIP-93 test vector 1, with non-zero padding bits
+        let keyphrase: KeyPhrase = KeyPhrase::from_codex32(
+            "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw",
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(keyphrase.entropy_hex(), "318c6318c6318c6318c6318c6318c631");
+        assert_eq!(
+            KeyPhrase::from_codex32(
+                "MS10TESTSXXXXXXXXXXXXXXXXXXXXXXXXXX4NZVCA9CMCZLW",
+                Language::English
+            )
+            .unwrap()
+            .phrase(),
+            keyphrase.phrase()
+        );
+
+        // BIP-93 test vector 4
+        let encoded: &str =
+            "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqqtum9pgv99ycma";
+        let keyphrase: KeyPhrase = KeyPhrase::from_codex32(encoded, Language::English).unwrap();
+
+        assert_eq!(
+            keyphrase.entropy_hex(),
+            "ffeeddccbbaa99887766554433221100ffeeddccbbaa99887766554433221100"
+        );
+        assert_eq!(keyphrase.to_codex32("leet").unwrap(), encoded);
+    }
+
+    #[test]
+    fn codex32_invalid() {
+        let encoded: &str = "ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw";
+
+        // one character off
+        assert!(
+            KeyPhrase::from_codex32(&encoded.replacen("xxx", "xyx", 1), Language::English).is_err()
+        );
+        // mixed case, other prefix, or a share instead of the secret
+        assert!(
+            KeyPhrase::from_codex32(&encoded.replacen("ms1", "MS1", 1), Language::English).is_err()
+        );
+        assert!(KeyPhrase::from_codex32(&encoded[1..], Language::English).is_err());
+        assert!(KeyPhrase::from_codex32(
+            "ms12nameaxxxxxxxxxxxxxxxxxxxxxxxxxxce43r337jkvtz",
+            Language::English
+        )
+        .is_err());
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_codex32(encoded, Language::English).unwrap();
+
+        assert!(keyphrase.to_codex32("tes").is_err());
+        assert!(keyphrase.to_codex32("test1").is_err());
+        assert!(keyphrase.to_codex32("b1ob").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! A [`KeyPhrase`][KeyPhrase] representation that doesn't keep the phrase in memory.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::sha256_first_byte;
+use crate::crypto::sha256_tagged;
+use crate::error::Error;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordList};
+use crate::util::{BitWriter, Bits11, IterExt};
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// A [`KeyPhrase`][KeyPhrase] that only stores its entropy and checksum, and renders the phrase
+/// on demand
+///
+/// A [`KeyPhrase`][KeyPhrase] keeps both the phrase and the entropy for its whole lifetime,
+/// doubling the footprint of the secret in memory. This instead writes the phrase into a buffer
+/// owned by the caller with [`CompactKeyPhrase::write_phrase()`][CompactKeyPhrase::write_phrase()],
+/// which can be wiped as soon as the phrase has been shown.
+///
+/// Converting from a [`KeyPhrase`][KeyPhrase] keeps its exact words, whichever checksum scheme
+/// and length it was created with.
+///
+/// With the `zeroize` feature enabled, the entropy is wiped from memory when the
+/// [`CompactKeyPhrase`][CompactKeyPhrase] is dropped.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{CompactKeyPhrase, KeyPhrase, KeyPhraseType, Language};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+/// let phrase = keyphrase.phrase().to_string();
+///
+/// let compact = CompactKeyPhrase::from(keyphrase);
+/// let mut buffer = String::new();
+///
+/// compact.write_phrase(&mut buffer);
+///
+/// assert_eq!(buffer, phrase);
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [CompactKeyPhrase]: ./struct.CompactKeyPhrase.html
+/// [CompactKeyPhrase::write_phrase()]: ./struct.CompactKeyPhrase.html#method.write_phrase
+#[derive(Clone)]
+pub struct CompactKeyPhrase {
+    lang: Language,
+    words: usize,
+    entropy_len: usize,
+    /// The wordlist indices of the words packed together, starting with the entropy
+    bits: Vec<u8>,
+}
+
+impl CompactKeyPhrase {
+    /// Create a [`CompactKeyPhrase`][CompactKeyPhrase] from pre-generated entropy
+    ///
+    /// Accepts the same lengths as [`KeyPhrase::from_entropy()`][KeyPhrase::from_entropy()],
+    /// without ever creating the phrase.
+    ///
+    /// [CompactKeyPhrase]: ./struct.CompactKeyPhrase.html
+    /// [KeyPhrase::from_entropy()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy
+    pub fn from_entropy(entropy: &[u8], lang: Language) -> Result<CompactKeyPhrase, Error> {
+        let mtype: KeyPhraseType = KeyPhraseType::for_key_size(entropy.len() * 8)?;
+
+        let mut bits: Vec<u8> = Vec::with_capacity(entropy.len() + 1);
+
+        bits.extend_from_slice(entropy);
+        bits.push(sha256_first_byte(entropy));
+
+        Ok(CompactKeyPhrase {
+            lang,
+            words: mtype.word_count(),
+            entropy_len: entropy.len(),
+            bits,
+        })
+    }
+
+    /// Write the phrase into `buffer`, replacing its content
+    ///
+    /// The buffer is grown to the exact size of the phrase before writing, so that no partial
+    /// copy of the phrase is left behind by a reallocation.
+    pub fn write_phrase(&self, buffer: &mut String) {
+        #[cfg(feature = "zeroize")]
+        buffer.zeroize();
+
+        buffer.clear();
+        buffer.reserve_exact(self.phrase_len());
+
+        for (i, word) in self.words().enumerate() {
+            if i != 0 {
+                buffer.push(' ');
+            }
+
+            buffer.push_str(word);
+        }
+    }
+
+    /// Length in bytes of the phrase written by
+    /// [`CompactKeyPhrase::write_phrase()`][CompactKeyPhrase::write_phrase()]
+    ///
+    /// [CompactKeyPhrase::write_phrase()]: ./struct.CompactKeyPhrase.html#method.write_phrase
+    pub fn phrase_len(&self) -> usize {
+        self.words().map(str::len).sum::<usize>() + self.words - 1
+    }
+
+    /// Get the original entropy value of the keyphrase as a slice
+    pub fn entropy(&self) -> &[u8] {
+        &self.bits[..self.entropy_len]
+    }
+
+    /// Get the [`Language`][Language]
+    ///
+    /// [Language]: ../language/struct.Language.html
+    pub fn language(&self) -> Language {
+        self.lang
+    }
+
+    /// Render the full [`KeyPhrase`][KeyPhrase] again
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn to_keyphrase(&self) -> KeyPhrase {
+        KeyPhrase::from_parts(self.words().join(" "), self.lang, self.entropy().to_vec())
+    }
+
+    fn words(&self) -> impl Iterator<Item = &'static str> + Clone + '_ {
+        let wordlist: &WordList = self.lang.wordlist();
+
+        self.bits
+            .iter()
+            .bits()
+            .take(self.words)
+            .map(move |bits: Bits11| wordlist.get_word(bits))
+    }
+}
+
+impl From<KeyPhrase> for CompactKeyPhrase {
+    fn from(keyphrase: KeyPhrase) -> CompactKeyPhrase {
+        let indices: Vec<u16> = keyphrase.word_indices();
+        let mut bits = BitWriter::with_capacity(indices.len() * 11);
+
+        for &index in &indices {
+            bits.push(Bits11::from(index));
+        }
+
+        CompactKeyPhrase {
+            lang: keyphrase.language(),
+            words: indices.len(),
+            entropy_len: keyphrase.entropy().len(),
+            bits: bits.into_bytes(),
+        }
+    }
+}
+
+impl From<CompactKeyPhrase> for KeyPhrase {
+    fn from(compact: CompactKeyPhrase) -> KeyPhrase {
+        compact.to_keyphrase()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for CompactKeyPhrase {
+    fn drop(&mut self) {
+        self.bits.zeroize();
+    }
+}
+
+/// Redacted the same way as the `Debug` output of [`KeyPhrase`][KeyPhrase], use
+/// [`KeyPhrase::reveal()`][KeyPhrase::reveal()] on the keyphrase it gives back to print the
+/// phrase itself
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::reveal()]: ../keyphrase/struct.KeyPhrase.html#method.reveal
+impl fmt::Debug for CompactKeyPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hash: Vec<u8> = sha256_tagged("keyphrase-debug", self.entropy());
+
+        f.debug_struct("CompactKeyPhrase")
+            .field("words", &self.words)
+            .field("language", &self.lang)
+            .field(
+                "hash",
+                &format_args!(
+                    "{:02x}{:02x}{:02x}{:02x}",
+                    hash[0], hash[1], hash[2], hash[3]
+                ),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checksum::ChecksumScheme;
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn compact_roundtrip() {
+        for &ty in &[KeyPhraseType::Words12, KeyPhraseType::Words24] {
+            let keyphrase: KeyPhrase = KeyPhrase::new(ty, Language::English);
+            let compact: CompactKeyPhrase = CompactKeyPhrase::from(keyphrase.clone());
+            let mut buffer: String = String::from("previous content");
+
+            compact.write_phrase(&mut buffer);
+
+            assert_eq!(buffer, keyphrase.phrase());
+            assert_eq!(compact.phrase_len(), keyphrase.phrase().len());
+            assert_eq!(compact.entropy(), keyphrase.entropy());
+            assert_eq!(compact.to_keyphrase().phrase(), keyphrase.phrase());
+
+            let compact: CompactKeyPhrase =
+                CompactKeyPhrase::from_entropy(keyphrase.entropy(), Language::English).unwrap();
+
+            assert_eq!(compact.to_keyphrase().phrase(), keyphrase.phrase());
+        }
+
+        assert!(CompactKeyPhrase::from_entropy(&[0; 15], Language::English).is_err());
+    }
+
+    #[test]
+    fn compact_keeps_checksum() {
+        struct ZeroChecksum;
+
+        impl ChecksumScheme for ZeroChecksum {
+            fn checksum_byte(&self, _: &[u8]) -> u8 {
+                0
+            }
+        }
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy_with_checksum(&[0x33; 16], Language::English, &ZeroChecksum)
+                .unwrap();
+        let compact: CompactKeyPhrase = CompactKeyPhrase::from(keyphrase.clone());
+
+        assert_eq!(compact.to_keyphrase().phrase(), keyphrase.phrase());
+
+        let nonstandard: KeyPhrase =
+            KeyPhrase::from_entropy_nonstandard(&[0x33; 12], Language::English).unwrap();
+        let compact: CompactKeyPhrase = CompactKeyPhrase::from(nonstandard.clone());
+
+        assert_eq!(compact.to_keyphrase().phrase(), nonstandard.phrase());
+    }
+}
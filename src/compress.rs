@@ -0,0 +1,233 @@
+//! Compact binary encoding for large sets of [`KeyPhrase`][KeyPhrase]s, such as test fixtures
+//! or canary sets.
+//!
+//! The phrases are stored as their wordlist indices, each delta-encoded against the previous
+//! index of the stream, which takes about 2 bytes per word instead of a whole word of text.
+//!
+//! The format is:
+//!
+//! - a header made of the magic bytes `KPZ`, a version byte and the number of phrases
+//! - for every phrase, a byte holding the language code in the upper bits and
+//!   `(word count - 12) / 3` in the lower 3 bits, followed by the word indices
+//!
+//! All integers are LEB128 varints, and the index deltas are zigzag encoded.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{compress, KeyPhrase, KeyPhraseType, Language};
+//!
+//! let phrases: Vec<KeyPhrase> = (0..100)
+//!     .map(|_| KeyPhrase::new(KeyPhraseType::Words12, Language::English))
+//!     .collect();
+//!
+//! let bytes: Vec<u8> = compress::encode(&phrases).unwrap();
+//! let decoded: Vec<KeyPhrase> = compress::decode(&bytes).unwrap();
+//!
+//! assert_eq!(phrases[0].phrase(), decoded[0].phrase());
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+
+const MAGIC: &[u8; 3] = b"KPZ";
+const VERSION: u8 = 1;
+
+/// Encode a set of [`KeyPhrase`][KeyPhrase]s
+///
+/// The phrases may be of different lengths and languages. Phrases created with a non-standard
+/// length cannot be encoded, and make this return an `Error` of kind `ErrorKind::InvalidWordLength`.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+pub fn encode(phrases: &[KeyPhrase]) -> Result<Vec<u8>, Error> {
+    let mut out: Vec<u8> = Vec::with_capacity(8 + phrases.len() * 50);
+
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    write_varint(&mut out, phrases.len() as u32);
+
+    let mut previous: i32 = 0;
+
+    for keyphrase in phrases {
+        let indices: Vec<u16> = keyphrase.word_indices();
+        let word_count: usize = KeyPhraseType::for_word_count(indices.len())?.word_count();
+        let size: u8 = ((word_count - 12) / 3) as u8;
+
+        out.push(keyphrase.language().code() << 3 | size);
+
+        for index in indices {
+            let delta: i32 = i32::from(index) - previous;
+
+            write_varint(&mut out, ((delta << 1) ^ (delta >> 31)) as u32);
+            previous = i32::from(index);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Decode a set of [`KeyPhrase`][KeyPhrase]s produced by [`encode()`][encode()]
+///
+/// Every phrase is validated, including its checksum.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [encode()]: ./fn.encode.html
+pub fn decode(bytes: &[u8]) -> Result<Vec<KeyPhrase>, Error> {
+    let mut reader = Reader { bytes, offset: 0 };
+
+    if reader.take(MAGIC.len())? != MAGIC {
+        Err(ErrorKind::InvalidEncoding("missing header"))?;
+    }
+
+    if reader.byte()? != VERSION {
+        Err(ErrorKind::InvalidEncoding("unsupported version"))?;
+    }
+
+    let count: usize = reader.varint()? as usize;
+
+    // Don't trust the count for the allocation, every phrase takes at least 13 bytes
+    let mut phrases: Vec<KeyPhrase> = Vec::with_capacity(count.min(bytes.len() / 13));
+    let mut previous: i32 = 0;
+
+    for _ in 0..count {
+        let header: u8 = reader.byte()?;
+
+        let lang: Language = match Language::from_code(header >> 3) {
+            Some(lang) => lang,
+            None => Err(ErrorKind::InvalidEncoding("unknown language"))?,
+        };
+        let word_count: usize =
+            KeyPhraseType::for_word_count(12 + 3 * (header & 0b111) as usize)?.word_count();
+
+        let mut indices: Vec<u16> = Vec::with_capacity(word_count);
+
+        for _ in 0..word_count {
+            let zigzag: u32 = reader.varint()?;
+            let delta: i32 = (zigzag >> 1) as i32 ^ -((zigzag & 1) as i32);
+            let index: i32 = previous.wrapping_add(delta);
+
+            if !(0..2048).contains(&index) {
+                Err(ErrorKind::InvalidEncoding("word index out of range"))?;
+            }
+
+            indices.push(index as u16);
+            previous = index;
+        }
+
+        phrases.push(KeyPhrase::from_word_indices(&indices, lang)?);
+    }
+
+    if reader.offset != bytes.len() {
+        Err(ErrorKind::InvalidEncoding("trailing bytes"))?;
+    }
+
+    Ok(phrases)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    out.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() - self.offset < len {
+            Err(ErrorKind::InvalidEncoding("unexpected end of input"))?;
+        }
+
+        let slice: &'a [u8] = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u32, Error> {
+        let mut value: u32 = 0;
+
+        for shift in (0..35).step_by(7) {
+            let byte: u8 = self.byte()?;
+
+            value |= u32::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ErrorKind::InvalidEncoding("varint overflow"))?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn roundtrip() {
+        let mut phrases: Vec<KeyPhrase> = vec![
+            KeyPhrase::new(KeyPhraseType::Words12, Language::English),
+            KeyPhrase::new(KeyPhraseType::Words21, Language::English),
+        ];
+
+        #[cfg(feature = "japanese")]
+        phrases.push(KeyPhrase::new(KeyPhraseType::Words24, Language::Japanese));
+        #[cfg(feature = "spanish")]
+        phrases.push(KeyPhrase::new(KeyPhraseType::Words15, Language::Spanish));
+
+        let bytes: Vec<u8> = encode(&phrases).unwrap();
+        let decoded: Vec<KeyPhrase> = decode(&bytes).unwrap();
+
+        assert_eq!(phrases.len(), decoded.len());
+
+        for (original, decoded) in phrases.iter().zip(decoded.iter()) {
+            assert_eq!(original.phrase(), decoded.phrase());
+            assert_eq!(original.entropy(), decoded.entropy());
+        }
+
+        let text: usize = phrases.iter().map(|m: &KeyPhrase| m.phrase().len()).sum();
+
+        assert!(bytes.len() < text / 2);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(decode(&encode(&[]).unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_input() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        let bytes: Vec<u8> = encode(&[keyphrase]).unwrap();
+
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode(&[&bytes[..], &[0]].concat()).is_err());
+        assert!(decode(&bytes[1..]).is_err());
+
+        // Flip the last index, which breaks the checksum
+        let mut corrupted: Vec<u8> = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 0x02;
+
+        assert!(decode(&corrupted).is_err());
+    }
+}
@@ -5,39 +5,522 @@
 //! [Seed]: ../seed/struct.Seed.html
 //!
 
-extern crate rand;
-use self::rand::{thread_rng, RngCore};
+use crate::error::{Error, ErrorKind};
+#[cfg(feature = "argon2")]
+use argon2::{Algorithm, Argon2, Params, Version};
 use hmac::Hmac;
+use hmac::Mac;
+#[cfg(all(feature = "rand", not(feature = "getrandom")))]
+use rand::rngs::OsRng;
+#[cfg(all(feature = "rand", not(feature = "getrandom")))]
+use rand::thread_rng;
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+use rand_core::impls;
+use rand_core::{CryptoRng, Error as RngError, RngCore};
+#[cfg(feature = "bip32")]
+use ripemd160::Ripemd160;
+#[cfg(feature = "scrypt")]
+use scrypt::ScryptParams;
 use sha2::Digest;
 
 const PBKDF2_ROUNDS: usize = 2048;
 const PBKDF2_BYTES: usize = 64;
 
+/// Most PBKDF2 rounds, scrypt memory, `128 * n * r` bytes, and parallel scrypt runs a `Kdf` may
+/// ask for, the same as the keystores, so that stored parameters can't keep the CPU busy for hours
+/// or exhaust the memory
+const MAX_PBKDF2_ROUNDS: usize = 10_000_000;
+#[cfg(feature = "scrypt")]
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+#[cfg(feature = "scrypt")]
+const MAX_SCRYPT_P: u32 = 16;
+
+/// Most Argon2 memory, iterations and lanes a `Kdf` may ask for, the same as encrypted exports
+#[cfg(feature = "argon2")]
+const MAX_ARGON2_MEMORY_KIB: u32 = 1024 * 1024;
+#[cfg(feature = "argon2")]
+const MAX_ARGON2_ITERATIONS: u32 = 64;
+#[cfg(feature = "argon2")]
+const MAX_ARGON2_LANES: u32 = 16;
+
+/// Key derivation function turning a keyphrase into a [`Seed`][Seed], used by
+/// [`Seed::new_with_params()`][Seed::new_with_params()]
+///
+/// Only `Kdf::default()`, PBKDF2 with 2048 rounds, gives seeds compatible with BIP39 wallets. The
+/// other functions are meant for seeds that never leave your own systems.
+///
+/// PBKDF2 is limited to 10,000,000 rounds, scrypt to 1 GiB of memory (`128 * n * r` bytes) with
+/// `p` up to 16, and Argon2id to 1 GiB of memory, 64 iterations and 16 lanes.
+///
+/// The variants depend on the features enabled anywhere in the build, so matches on `Kdf` need a
+/// wildcard arm.
+///
+/// [Seed]: ../seed/struct.Seed.html
+/// [Seed::new_with_params()]: ../seed/struct.Seed.html#method.new_with_params
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Kdf {
+    /// PBKDF2-HMAC-SHA512 with the given number of rounds
+    Pbkdf2 { rounds: u32 },
+    /// Memory-hard scrypt, `n` must be a power of two greater than 1
+    #[cfg(feature = "scrypt")]
+    Scrypt { n: u64, r: u32, p: u32 },
+    /// Memory-hard Argon2id, `memory_kib` must be at least 8 times `lanes`
+    #[cfg(feature = "argon2")]
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        lanes: u32,
+    },
+}
+
+impl Default for Kdf {
+    fn default() -> Kdf {
+        Kdf::Pbkdf2 {
+            rounds: PBKDF2_ROUNDS as u32,
+        }
+    }
+}
+
+impl Kdf {
+    /// Derive a 64 byte seed from `input` and `salt` directly into `seed`, so that no copy of it
+    /// is left behind
+    pub(crate) fn derive(
+        &self,
+        input: &[u8],
+        salt: &str,
+        seed: &mut [u8; PBKDF2_BYTES],
+    ) -> Result<(), Error> {
+        match *self {
+            Kdf::Pbkdf2 { rounds } => {
+                check_pbkdf2_rounds(rounds as usize)?;
+
+                pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt.as_bytes(), rounds as usize, seed);
+            }
+            #[cfg(feature = "scrypt")]
+            Kdf::Scrypt { n, r, p } => {
+                if n < 2 || !n.is_power_of_two() {
+                    Err(ErrorKind::InvalidKdfParams(
+                        "scrypt n must be a power of two greater than 1",
+                    ))?;
+                }
+
+                if u128::from(n) * 128 * u128::from(r) > u128::from(MAX_SCRYPT_MEMORY) {
+                    Err(ErrorKind::InvalidKdfParams("too much scrypt memory"))?;
+                }
+
+                if p > MAX_SCRYPT_P {
+                    Err(ErrorKind::InvalidKdfParams("too many parallel scrypt runs"))?;
+                }
+
+                let params: ScryptParams = ScryptParams::new(n.trailing_zeros() as u8, r, p)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt parameters"))?;
+
+                scrypt::scrypt(input, salt.as_bytes(), &params, seed)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt output length"))?;
+            }
+            #[cfg(feature = "argon2")]
+            Kdf::Argon2id {
+                memory_kib,
+                iterations,
+                lanes,
+            } => {
+                if memory_kib > MAX_ARGON2_MEMORY_KIB {
+                    Err(ErrorKind::InvalidKdfParams("too much Argon2 memory"))?;
+                }
+
+                if iterations > MAX_ARGON2_ITERATIONS {
+                    Err(ErrorKind::InvalidKdfParams("too many Argon2 iterations"))?;
+                }
+
+                if lanes > MAX_ARGON2_LANES {
+                    Err(ErrorKind::InvalidKdfParams("too many Argon2 lanes"))?;
+                }
+
+                let params: Params = Params::new(memory_kib, iterations, lanes, Some(PBKDF2_BYTES))
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid Argon2 parameters"))?;
+
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(input, salt.as_bytes(), seed)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid Argon2 parameters"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that a number of PBKDF2 rounds is between 1 and `MAX_PBKDF2_ROUNDS`
+fn check_pbkdf2_rounds(rounds: usize) -> Result<(), Error> {
+    if rounds == 0 {
+        Err(ErrorKind::InvalidKdfParams(
+            "PBKDF2 needs at least one round",
+        ))?;
+    }
+
+    if rounds > MAX_PBKDF2_ROUNDS {
+        Err(ErrorKind::InvalidKdfParams("too many PBKDF2 rounds"))?;
+    }
+
+    Ok(())
+}
+
 /// SHA256 helper function, internal to the crate
 ///
 pub(crate) fn sha256_first_byte(input: &[u8]) -> u8 {
     sha2::Sha256::digest(input).as_ref()[0]
 }
 
-/// Random byte generator, used to create new keyphrases
+/// SHA256 helper function returning the whole hash, internal to the crate
 ///
+pub(crate) fn sha256(input: &[u8]) -> Vec<u8> {
+    sha2::Sha256::digest(input).to_vec()
+}
+
+/// HMAC-SHA512 helper function, internal to the crate
+///
+pub(crate) fn hmac_sha512(key: &[u8], input: &[u8]) -> [u8; 64] {
+    let mut mac: Hmac<sha2::Sha512> =
+        Hmac::new_varkey(key).expect("HMAC accepts keys of any length");
+    let mut output: [u8; 64] = [0; 64];
+
+    mac.input(input);
+    output.copy_from_slice(&mac.result().code());
+
+    output
+}
+
+/// HMAC-SHA256 helper function, internal to the crate
+///
+#[cfg(feature = "slip39")]
+pub(crate) fn hmac_sha256(key: &[u8], input: &[u8]) -> [u8; 32] {
+    let mut mac: Hmac<sha2::Sha256> =
+        Hmac::new_varkey(key).expect("HMAC accepts keys of any length");
+    let mut output: [u8; 32] = [0; 32];
+
+    mac.input(input);
+    output.copy_from_slice(&mac.result().code());
+
+    output
+}
+
+/// PBKDF2-HMAC-SHA256 helper filling `output`, internal to the crate
+///
+#[cfg(any(feature = "slip39", feature = "keystore", feature = "protect"))]
+pub(crate) fn pbkdf2_sha256(input: &[u8], salt: &[u8], rounds: usize, output: &mut [u8]) {
+    pbkdf2::pbkdf2::<Hmac<sha2::Sha256>>(input, salt, rounds, output);
+}
+
+/// HKDF-SHA512 (RFC 5869) helper filling `output`, internal to the crate
+///
+/// `output` must be at most 255 times 64 bytes long.
+pub(crate) fn hkdf_sha512(salt: &[u8], input: &[u8], info: &[u8], output: &mut [u8]) {
+    #[allow(unused_mut)]
+    let mut prk: [u8; 64] = hmac_sha512(salt, input);
+    let mut block: Vec<u8> = Vec::with_capacity(64 + info.len() + 1);
+
+    for (i, chunk) in output.chunks_mut(64).enumerate() {
+        block.extend_from_slice(info);
+        block.push(i as u8 + 1);
+
+        let t: [u8; 64] = hmac_sha512(&prk, &block);
+
+        chunk.copy_from_slice(&t[..chunk.len()]);
+        block.clear();
+        block.extend_from_slice(&t);
+    }
+
+    #[cfg(feature = "zeroize")]
+    {
+        zeroize::Zeroize::zeroize(&mut prk);
+        zeroize::Zeroize::zeroize(&mut block);
+    }
+}
+
+/// RIPEMD160 of SHA256, used for BIP32 fingerprints, internal to the crate
+///
+#[cfg(feature = "bip32")]
+pub(crate) fn hash160(input: &[u8]) -> [u8; 20] {
+    let mut output: [u8; 20] = [0; 20];
+
+    output.copy_from_slice(&Ripemd160::digest(&sha2::Sha256::digest(input)));
+
+    output
+}
+
+/// SHA256 helper function prefixing the input with a domain tag, internal to the crate
+///
+/// Used to derive non-secret identifiers from secret material without them being usable for
+/// anything else.
+pub(crate) fn sha256_tagged(tag: &str, input: &[u8]) -> Vec<u8> {
+    sha2::Sha256::new()
+        .chain(tag.as_bytes())
+        .chain(input)
+        .result()
+        .to_vec()
+}
+
+/// Compares two byte slices in time that only depends on their lengths, internal to the crate
+///
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let difference: u8 = a
+        .iter()
+        .zip(b)
+        .fold(0, |acc: u8, (x, y): (&u8, &u8)| acc | (x ^ y));
+
+    // Keep the optimizer from short-circuiting the fold
+    std::hint::black_box(difference) == 0
+}
+
+/// Combines OS entropy with user provided entropy, internal to the crate
+///
+/// The result is as strong as the strongest of the two inputs, the OS entropy must have a fixed
+/// length for the encoding to be unambiguous.
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+pub(crate) fn mix_entropy(os_entropy: &[u8], user_entropy: &[u8]) -> Vec<u8> {
+    sha2::Sha256::new()
+        .chain(b"keyphrase-mix")
+        .chain(os_entropy)
+        .chain(user_entropy)
+        .result()
+        .to_vec()
+}
+
+/// The RNG of every function of the crate that isn't given one, such as
+/// [`KeyPhrase::new()`][KeyPhrase::new()]
+///
+/// The features of the crate select where it draws from:
+///
+/// * `getrandom`: the entropy source of the OS, read directly with the `getrandom` crate. It
+///   takes over from `rand` when both features are enabled.
+/// * `rand`, enabled by default: the `ThreadRng` of `rand`, a CSPRNG seeded from the OS.
+/// * neither: nowhere, the crate then only depends on `rand_core`. `DefaultRng` doesn't implement
+///   `RngCore`, and the functions drawing from it, such as [`KeyPhrase::new()`][KeyPhrase::new()],
+///   aren't compiled in. Generate keyphrases with an RNG of your own instead, given to
+///   [`KeyPhrase::new_with_rng()`][KeyPhrase::new_with_rng()] or
+///   [`KeyPhraseBuilder::rng()`][KeyPhraseBuilder::rng()].
+///
+/// [KeyPhrase::new()]: ../keyphrase/struct.KeyPhrase.html#method.new
+/// [KeyPhrase::new_with_rng()]: ../keyphrase/struct.KeyPhrase.html#method.new_with_rng
+/// [KeyPhraseBuilder::rng()]: ../keyphrase_builder/struct.KeyPhraseBuilder.html#method.rng
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRng;
+
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+impl RngCore for DefaultRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(error) = self.try_fill_bytes(dest) {
+            panic!("{}", error);
+        }
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        fill_from_os(dest)
+    }
+
+    #[cfg(all(feature = "rand", not(feature = "getrandom")))]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        thread_rng().try_fill_bytes(dest)
+    }
+}
+
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+impl CryptoRng for DefaultRng {}
+
+/// Fill `dest` from the entropy source of the OS, with `getrandom`
+#[cfg(feature = "getrandom")]
+fn fill_from_os(dest: &mut [u8]) -> Result<(), RngError> {
+    getrandom::getrandom(dest)
+        .map_err(|_| RngError::new(rand_core::ErrorKind::Unavailable, "getrandom failed"))
+}
+
+/// Fill `dest` from the entropy source of the OS, with the `OsRng` of `rand`
+#[cfg(all(feature = "rand", not(feature = "getrandom")))]
+fn fill_from_os(dest: &mut [u8]) -> Result<(), RngError> {
+    OsRng::new()?.try_fill_bytes(dest)
+}
+
+/// Without the `rand` and `getrandom` features, there is no entropy source to fill `dest` from.
+/// Builders only get here with a `DefaultRng`, whose `build()` isn't available then.
+#[cfg(not(any(feature = "rand", feature = "getrandom")))]
+fn fill_from_os(_: &mut [u8]) -> Result<(), RngError> {
+    Err(RngError::new(
+        rand_core::ErrorKind::Unavailable,
+        "no entropy source, enable the rand or getrandom feature of keyphrase or give it an RNG",
+    ))
+}
+
+/// Random byte generator drawing from the [`DefaultRng`][DefaultRng], used to create new
+/// keyphrases
+///
+/// [DefaultRng]: ./struct.DefaultRng.html
+#[cfg(any(feature = "rand", feature = "getrandom"))]
 pub(crate) fn gen_random_bytes(byte_length: usize) -> Vec<u8> {
-    let mut rng = thread_rng();
+    gen_random_bytes_from(&mut DefaultRng, byte_length)
+}
+
+/// Random byte generator drawing from the given RNG, used to create new keyphrases
+///
+pub(crate) fn gen_random_bytes_from<R>(rng: &mut R, byte_length: usize) -> Vec<u8>
+where
+    R: RngCore + CryptoRng,
+{
     let mut bytes = vec![0u8; byte_length];
 
     rng.fill_bytes(&mut bytes);
 
     bytes
 }
+
+/// Fallible random byte generator, reading directly from the OS entropy source
+///
+/// Returns an `Error` of kind `ErrorKind::EntropyUnavailable` instead of panicking if the OS
+/// entropy source fails.
+pub(crate) fn try_gen_random_bytes(byte_length: usize) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0u8; byte_length];
+
+    fill_from_os(&mut bytes).map_err(|_| ErrorKind::EntropyUnavailable)?;
+
+    Ok(bytes)
+}
+
+/// Fallible random byte generator drawing from the given RNG
+///
+/// Returns an `Error` of kind `ErrorKind::EntropySourceFailed` with the message of the RNG if
+/// it fails.
+pub(crate) fn try_gen_random_bytes_from<R>(
+    rng: &mut R,
+    byte_length: usize,
+) -> Result<Vec<u8>, Error>
+where
+    R: RngCore + CryptoRng,
+{
+    let mut bytes = vec![0u8; byte_length];
+
+    rng.try_fill_bytes(&mut bytes)
+        .map_err(|error| ErrorKind::EntropySourceFailed(error.to_string()))?;
+
+    Ok(bytes)
+}
 /// PBKDF2 helper, used to generate [`Seed`][Seed] from [`KeyPhrase`][KeyPhrase]
 ///
 /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
 /// [Seed]: ../seed/struct.Seed.html
 ///
-pub(crate) fn pbkdf2(input: &[u8], salt: &str) -> Vec<u8> {
-    let mut seed = vec![0u8; PBKDF2_BYTES];
+pub(crate) fn pbkdf2(input: &[u8], salt: &str, seed: &mut [u8; PBKDF2_BYTES]) {
+    pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt.as_bytes(), PBKDF2_ROUNDS, seed);
+}
+
+/// PBKDF2 helper reporting its progress, used by
+/// [`Seed::new_with_progress()`][Seed::new_with_progress()]
+///
+/// `progress` is called with the fraction of the rounds computed every `rounds_per_call`
+/// rounds, and with `1.0` once the derivation is finished.
+///
+/// [Seed::new_with_progress()]: ../seed/struct.Seed.html#method.new_with_progress
+pub(crate) fn pbkdf2_with_progress<F>(
+    input: &[u8],
+    salt: &str,
+    rounds: usize,
+    rounds_per_call: usize,
+    seed: &mut [u8; PBKDF2_BYTES],
+    mut progress: F,
+) -> Result<(), Error>
+where
+    F: FnMut(f32),
+{
+    let mut derivation: Pbkdf2Rounds = Pbkdf2Rounds::new(input, salt, rounds, seed)?;
+
+    while !derivation.run(rounds_per_call, seed) {
+        progress(derivation.progress());
+    }
+
+    progress(1.0);
+
+    Ok(())
+}
+
+/// PBKDF2-HMAC-SHA512 of a single 64 byte block, computed a few rounds at a time so that the
+/// caller can yield in between, internal to the crate
+///
+/// Gives the same result as [`pbkdf2()`][pbkdf2()].
+///
+/// [pbkdf2()]: ./fn.pbkdf2.html
+pub(crate) struct Pbkdf2Rounds {
+    mac: Hmac<sha2::Sha512>,
+    block: [u8; PBKDF2_BYTES],
+    rounds: usize,
+    done: usize,
+}
+
+impl Pbkdf2Rounds {
+    /// Start a derivation, `seed` receives the output of the first round
+    pub(crate) fn new(
+        input: &[u8],
+        salt: &str,
+        rounds: usize,
+        seed: &mut [u8; PBKDF2_BYTES],
+    ) -> Result<Pbkdf2Rounds, Error> {
+        check_pbkdf2_rounds(rounds)?;
+
+        let mac: Hmac<sha2::Sha512> =
+            Hmac::new_varkey(input).expect("HMAC accepts keys of any length");
+        let mut first: Hmac<sha2::Sha512> = mac.clone();
+
+        first.input(salt.as_bytes());
+        first.input(&1u32.to_be_bytes());
+        seed.copy_from_slice(&first.result().code());
+
+        Ok(Pbkdf2Rounds {
+            mac,
+            block: *seed,
+            rounds,
+            done: 1,
+        })
+    }
 
-    pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(input, salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+    /// Run up to `rounds` more rounds into `seed`, returns whether the derivation is finished
+    pub(crate) fn run(&mut self, rounds: usize, seed: &mut [u8; PBKDF2_BYTES]) -> bool {
+        let end: usize = self.rounds.min(self.done.saturating_add(rounds));
+
+        for _ in self.done..end {
+            let mut mac: Hmac<sha2::Sha512> = self.mac.clone();
+
+            mac.input(&self.block);
+            self.block.copy_from_slice(&mac.result().code());
+
+            seed.iter_mut()
+                .zip(self.block.iter())
+                .for_each(|(byte, block): (&mut u8, &u8)| *byte ^= block);
+        }
+
+        self.done = end;
+
+        self.done == self.rounds
+    }
+
+    /// Fraction of the rounds already computed, between 0 and 1
+    pub(crate) fn progress(&self) -> f32 {
+        self.done as f32 / self.rounds as f32
+    }
+}
 
-    seed
+#[cfg(feature = "zeroize")]
+impl Drop for Pbkdf2Rounds {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.block);
+    }
 }
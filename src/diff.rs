@@ -0,0 +1,304 @@
+//! Comparing a phrase typed by the user with the original [`KeyPhrase`][KeyPhrase].
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::keyphrase::KeyPhrase;
+use crate::language::{WordList, WordMap};
+use crate::util::Bits11;
+
+/// A difference between a [`KeyPhrase`][KeyPhrase] and a transcription of it, returned by
+/// [`KeyPhrase::diff()`][KeyPhrase::diff()]
+///
+/// Positions start at 0 and refer to the words of the original phrase, except for
+/// `WordDiff::Extra` which refers to the words of the transcription.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::diff()]: ../keyphrase/struct.KeyPhrase.html#method.diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordDiff {
+    /// The typed word is not in the wordlist, `suggestion` is the word of the original phrase
+    Typo {
+        position: usize,
+        typed: String,
+        suggestion: &'static str,
+    },
+    /// The typed word is in the wordlist, but is not the one of the original phrase
+    Wrong {
+        position: usize,
+        typed: String,
+        expected: &'static str,
+    },
+    /// The words at both positions were typed in each other's place
+    Swapped { first: usize, second: usize },
+    /// The word of the original phrase wasn't typed
+    Missing {
+        position: usize,
+        expected: &'static str,
+    },
+    /// A word was typed that isn't in the original phrase
+    Extra { position: usize, typed: String },
+}
+
+/// One step of the alignment of the original words with the typed words
+enum Step {
+    Same,
+    Substitute(usize, usize),
+    Transpose(usize),
+    Missing(usize),
+    Extra(usize),
+}
+
+impl KeyPhrase {
+    /// List the differences between the keyphrase and a transcription of it
+    ///
+    /// Meant to show users exactly what to fix when the backup they typed again doesn't match.
+    /// The transcription is compared word by word, ignoring case and surrounding or repeated
+    /// whitespace, and aligned so that a missing or extra word doesn't make all the following
+    /// words mismatch. An empty list means the transcription matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, WordDiff};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let diff = keyphrase.diff("park remain persn kitchen mule spell knee position armed rail ankle");
+    ///
+    /// assert_eq!(diff, vec![
+    ///     WordDiff::Typo { position: 2, typed: "persn".to_string(), suggestion: "person" },
+    ///     WordDiff::Swapped { first: 7, second: 8 },
+    ///     WordDiff::Missing { position: 10, expected: "grid" },
+    /// ]);
+    /// ```
+    pub fn diff(&self, candidate: &str) -> Vec<WordDiff> {
+        let wordmap: &WordMap = self.language().wordmap();
+        let wordlist: &WordList = self.language().wordlist();
+        let expected: Vec<&'static str> = self
+            .word_indices()
+            .into_iter()
+            .map(|index: u16| wordlist.get_word(Bits11::from(index)))
+            .collect();
+        let typed: Vec<String> = candidate
+            .split_whitespace()
+            .map(|word: &str| word.to_lowercase())
+            .collect();
+
+        let steps: Vec<Step> = align(&expected, &typed);
+        let mut diffs: Vec<WordDiff> = Vec::new();
+        let mut substitutions: Vec<(usize, usize)> = Vec::new();
+
+        for step in steps {
+            match step {
+                Step::Same => {}
+                Step::Substitute(position, index) => substitutions.push((position, index)),
+                Step::Transpose(position) => diffs.push(WordDiff::Swapped {
+                    first: position,
+                    second: position + 1,
+                }),
+                Step::Missing(position) => diffs.push(WordDiff::Missing {
+                    position,
+                    expected: expected[position],
+                }),
+                Step::Extra(index) => diffs.push(WordDiff::Extra {
+                    position: index,
+                    typed: typed[index].clone(),
+                }),
+            }
+        }
+
+        let mut swapped: Vec<usize> = Vec::new();
+
+        for (i, &(first, first_index)) in substitutions.iter().enumerate() {
+            for &(second, second_index) in &substitutions[i + 1..] {
+                if swapped.contains(&first) || swapped.contains(&second) {
+                    continue;
+                }
+
+                if typed[first_index] == expected[second] && typed[second_index] == expected[first]
+                {
+                    diffs.push(WordDiff::Swapped { first, second });
+                    swapped.push(first);
+                    swapped.push(second);
+                }
+            }
+        }
+
+        for (position, index) in substitutions {
+            if swapped.contains(&position) {
+                continue;
+            }
+
+            let typed: String = typed[index].clone();
+
+            if wordmap.get_bits(&typed).is_ok() {
+                diffs.push(WordDiff::Wrong {
+                    position,
+                    typed,
+                    expected: expected[position],
+                });
+            } else {
+                diffs.push(WordDiff::Typo {
+                    position,
+                    typed,
+                    suggestion: expected[position],
+                });
+            }
+        }
+
+        diffs.sort_by_key(|diff: &WordDiff| match *diff {
+            WordDiff::Typo { position, .. }
+            | WordDiff::Wrong { position, .. }
+            | WordDiff::Missing { position, .. } => position,
+            WordDiff::Swapped { first, .. } => first,
+            WordDiff::Extra { position, .. } => position,
+        });
+
+        diffs
+    }
+}
+
+/// Align the words with the smallest number of substitutions, swaps of neighbours, missing and
+/// extra words
+fn align(expected: &[&str], typed: &[String]) -> Vec<Step> {
+    let (n, m): (usize, usize) = (expected.len(), typed.len());
+
+    // costs[i][j] is the cost of aligning the first i expected words with the first j typed ones
+    let mut costs: Vec<Vec<usize>> = vec![vec![0; m + 1]; n + 1];
+
+    for (i, row) in costs.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, cost) in costs[0].iter_mut().enumerate() {
+        *cost = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution: usize = if expected[i - 1] == typed[j - 1] {
+                0
+            } else {
+                1
+            };
+
+            costs[i][j] = (costs[i - 1][j - 1] + substitution)
+                .min(costs[i - 1][j] + 1)
+                .min(costs[i][j - 1] + 1);
+
+            if transposed(expected, typed, i, j) {
+                costs[i][j] = costs[i][j].min(costs[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    let mut steps: Vec<Step> = Vec::new();
+    let (mut i, mut j): (usize, usize) = (n, m);
+
+    while i > 0 || j > 0 {
+        if transposed(expected, typed, i, j) && costs[i][j] == costs[i - 2][j - 2] + 1 {
+            steps.push(Step::Transpose(i - 2));
+            i -= 2;
+            j -= 2;
+
+            continue;
+        }
+
+        if i > 0 && j > 0 {
+            let same: bool = expected[i - 1] == typed[j - 1];
+            let substitution: usize = if same { 0 } else { 1 };
+
+            if costs[i][j] == costs[i - 1][j - 1] + substitution {
+                steps.push(if same {
+                    Step::Same
+                } else {
+                    Step::Substitute(i - 1, j - 1)
+                });
+                i -= 1;
+                j -= 1;
+
+                continue;
+            }
+        }
+
+        if i > 0 && costs[i][j] == costs[i - 1][j] + 1 {
+            steps.push(Step::Missing(i - 1));
+            i -= 1;
+        } else {
+            steps.push(Step::Extra(j - 1));
+            j -= 1;
+        }
+    }
+
+    steps.reverse();
+    steps
+}
+
+/// Whether the last two of the first `i` expected words were typed the other way around as the
+/// last two of the first `j` typed words
+fn transposed(expected: &[&str], typed: &[String], i: usize, j: usize) -> bool {
+    i > 1
+        && j > 1
+        && expected[i - 1] != expected[i - 2]
+        && expected[i - 1] == typed[j - 2]
+        && expected[i - 2] == typed[j - 1]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::language::Language;
+
+    fn keyphrase() -> KeyPhrase {
+        KeyPhrase::from_phrase(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn diff_matching() {
+        assert!(keyphrase()
+            .diff(" Park remain person kitchen mule spell knee armed position rail grid  ankle")
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_mismatches() {
+        assert_eq!(
+            keyphrase().diff(
+                "park remain person kitchen mule spell knee armed position rail grid apple ankle"
+            ),
+            vec![WordDiff::Extra {
+                position: 11,
+                typed: "apple".to_string()
+            }]
+        );
+
+        assert_eq!(
+            keyphrase()
+                .diff("park remain person kitchen mule spell knee armed position rail grid apple"),
+            vec![WordDiff::Wrong {
+                position: 11,
+                typed: "apple".to_string(),
+                expected: "ankle"
+            }]
+        );
+
+        assert_eq!(
+            keyphrase().diff("remain park person kitchen mule spell knee armed position rail grid"),
+            vec![
+                WordDiff::Swapped {
+                    first: 0,
+                    second: 1
+                },
+                WordDiff::Missing {
+                    position: 11,
+                    expected: "ankle"
+                }
+            ]
+        );
+    }
+}
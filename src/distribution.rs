@@ -0,0 +1,87 @@
+//! Sampling random [`KeyPhrase`][KeyPhrase]s with `rand`, for property tests and simulations.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// Distribution of [`KeyPhrase`][KeyPhrase]s of a given length and language
+///
+/// `Standard` samples 12 word English keyphrases, use this for other lengths and languages.
+///
+/// Unlike [`KeyPhrase::new_with_rng()`][KeyPhrase::new_with_rng()], sampling accepts any RNG,
+/// including non-cryptographic ones, so the keyphrases are only as unpredictable as the RNG.
+///
+/// # Example
+///
+/// ```
+/// extern crate rand;
+///
+/// use keyphrase::{KeyPhrase, KeyPhraseDistribution, KeyPhraseType, Language};
+/// use rand::Rng;
+///
+/// let mut rng = rand::thread_rng();
+///
+/// let keyphrase: KeyPhrase = rng.gen();
+/// assert_eq!(keyphrase.phrase().split(" ").count(), 12);
+///
+/// let keyphrase = rng.sample(KeyPhraseDistribution::new(KeyPhraseType::Words24, Language::English));
+/// assert_eq!(keyphrase.phrase().split(" ").count(), 24);
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::new_with_rng()]: ../keyphrase/struct.KeyPhrase.html#method.new_with_rng
+#[derive(Debug, Clone, Copy)]
+pub struct KeyPhraseDistribution {
+    keyphrase_type: KeyPhraseType,
+    lang: Language,
+}
+
+impl KeyPhraseDistribution {
+    pub fn new(keyphrase_type: KeyPhraseType, lang: Language) -> KeyPhraseDistribution {
+        KeyPhraseDistribution {
+            keyphrase_type,
+            lang,
+        }
+    }
+}
+
+impl Distribution<KeyPhrase> for KeyPhraseDistribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> KeyPhrase {
+        let mut entropy: Vec<u8> = vec![0; self.keyphrase_type.entropy_bits() / 8];
+
+        rng.fill_bytes(&mut entropy);
+
+        KeyPhrase::from_entropy_unchecked(entropy, self.lang)
+    }
+}
+
+impl Distribution<KeyPhrase> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> KeyPhrase {
+        KeyPhraseDistribution::new(KeyPhraseType::Words12, Language::English).sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sample_deterministic() {
+        let distribution: KeyPhraseDistribution =
+            KeyPhraseDistribution::new(KeyPhraseType::Words18, Language::English);
+
+        let m1: KeyPhrase = StdRng::seed_from_u64(7).sample(distribution);
+        let m2: KeyPhrase = StdRng::seed_from_u64(7).sample(distribution);
+        let m3: KeyPhrase = StdRng::seed_from_u64(7).gen();
+
+        assert_eq!(m1.phrase(), m2.phrase());
+        assert_eq!(m1.phrase().split(' ').count(), 18);
+        assert_eq!(m3.phrase().split(' ').count(), 12);
+    }
+}
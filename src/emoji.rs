@@ -0,0 +1,207 @@
+//! Emoji rendering of the words of a [`KeyPhrase`][KeyPhrase], enabled by the `emoji` feature.
+//!
+//! Every index of the wordlist maps to a fixed emoji, the same in every language, so two screens
+//! can be compared at a glance. This is a display aid, not a backup format: emoji look different
+//! on every platform, newer ones may not render at all, and nobody can write them down reliably.
+//! Always back up the words themselves.
+//!
+//! The 2048 emoji are, in this order, the single emoji with emoji presentation of Unicode 14,
+//! the ones with text presentation followed by the variation selector 16, the keycaps, a few
+//! ZWJ sequences, and the 5 skin tones of the emoji that have them. Flags are left out, as some
+//! platforms show them as letters.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+
+/// The emoji of every index of the wordlist
+static EMOJI: Lazy<Vec<&'static str>> =
+    sync_lazy! { include_str!("langs/emoji.txt").lines().collect() };
+
+/// The indices of the emoji, without variation selectors
+static EMOJI_INDICES: Lazy<HashMap<String, u16>> = sync_lazy! {
+    EMOJI
+        .iter()
+        .enumerate()
+        .map(|(index, emoji): (usize, &&str)| (strip_selectors(emoji), index as u16))
+        .collect()
+};
+
+/// Most characters of an emoji, without variation selectors
+const MAX_EMOJI_CHARS: usize = 3;
+
+/// The variation selector 16, asking for emoji presentation
+const VS16: char = '\u{fe0f}';
+
+impl KeyPhrase {
+    /// Get the emoji of the words of the keyphrase, separated by spaces
+    ///
+    /// Every word is one of 2048 fixed emoji, the same in every language. This is only a
+    /// display aid to compare keyphrases at a glance, not a backup format: emoji look different
+    /// on every platform and can't be written down reliably. It can be parsed back with
+    /// [`KeyPhrase::from_emoji()`][KeyPhrase::from_emoji()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let emoji = keyphrase.to_emoji();
+    ///
+    /// assert_eq!(emoji.split(' ').count(), 12);
+    /// assert_eq!(
+    ///     KeyPhrase::from_emoji(&emoji, Language::English).unwrap().phrase(),
+    ///     keyphrase.phrase()
+    /// );
+    /// ```
+    ///
+    /// [KeyPhrase::from_emoji()]: ../keyphrase/struct.KeyPhrase.html#method.from_emoji
+    pub fn to_emoji(&self) -> String {
+        self.word_indices()
+            .iter()
+            .map(|&index: &u16| EMOJI[index as usize])
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Parse the emoji of a keyphrase in `lang`, as created by
+    /// [`KeyPhrase::to_emoji()`][KeyPhrase::to_emoji()]
+    ///
+    /// Whitespace and variation selectors are ignored, as they are often lost when emoji are
+    /// copied. Returns an `Error` of kind `ErrorKind::InvalidWord` if an emoji isn't one of the
+    /// 2048, or the errors of a phrase with the same words.
+    ///
+    /// [KeyPhrase::to_emoji()]: ../keyphrase/struct.KeyPhrase.html#method.to_emoji
+    pub fn from_emoji(emoji: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_word_indices(&parse_emoji(emoji)?, lang)
+    }
+}
+
+/// Get the indices of a string of emoji, ignoring whitespace and variation selectors
+fn parse_emoji(emoji: &str) -> Result<Vec<u16>, Error> {
+    let chars: Vec<char> = emoji
+        .chars()
+        .filter(|&c: &char| !c.is_whitespace() && c != VS16)
+        .collect();
+    let mut indices: Vec<u16> = Vec::new();
+    let mut offset: usize = 0;
+
+    while offset < chars.len() {
+        // The longest emoji first, so that skin tones and ZWJ sequences aren't split
+        let found: Option<(usize, u16)> = (1..=MAX_EMOJI_CHARS.min(chars.len() - offset))
+            .rev()
+            .find_map(|len: usize| {
+                let candidate: String = chars[offset..offset + len].iter().collect();
+
+                EMOJI_INDICES
+                    .get(&candidate)
+                    .map(|&index: &u16| (len, index))
+            });
+
+        match found {
+            Some((len, index)) => {
+                indices.push(index);
+                offset += len;
+            }
+            None => Err(ErrorKind::InvalidWord {
+                word: chars[offset].to_string(),
+                index: indices.len(),
+                lang: None,
+            })?,
+        }
+    }
+
+    Ok(indices)
+}
+
+/// Remove the variation selectors of an emoji
+fn strip_selectors(emoji: &str) -> String {
+    emoji.chars().filter(|&c: &char| c != VS16).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+    use hashbrown::HashSet;
+
+    #[test]
+    fn emoji_table() {
+        assert_eq!(EMOJI.len(), 2048);
+        assert_eq!(EMOJI_INDICES.len(), 2048);
+        assert_eq!(EMOJI.iter().collect::<HashSet<&&str>>().len(), 2048);
+        assert!(EMOJI
+            .iter()
+            .all(|emoji: &&str| strip_selectors(emoji).chars().count() <= MAX_EMOJI_CHARS));
+    }
+
+    #[test]
+    fn keyphrase_to_emoji() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+        let emoji: String = keyphrase.to_emoji();
+
+        assert_eq!(emoji, "⌚ ".repeat(11) + "⏪");
+        assert_eq!(
+            KeyPhrase::from_emoji(&emoji, Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+
+        // the same words in another language
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy(keyphrase.entropy(), Language::French).unwrap();
+
+        assert_eq!(keyphrase.to_emoji(), emoji);
+    }
+
+    #[test]
+    fn keyphrase_from_emoji_lenient() {
+        [KeyPhraseType::Words12, KeyPhraseType::Words24]
+            .iter()
+            .for_each(|&keyphrase_type: &KeyPhraseType| {
+                let keyphrase: KeyPhrase = KeyPhrase::new(keyphrase_type, Language::English);
+                let emoji: String = keyphrase.to_emoji();
+
+                // without spaces or variation selectors
+                let compact: String = emoji
+                    .chars()
+                    .filter(|&c: &char| c != ' ' && c != VS16)
+                    .collect();
+
+                assert_eq!(
+                    KeyPhrase::from_emoji(&compact, Language::English)
+                        .unwrap()
+                        .phrase(),
+                    keyphrase.phrase()
+                );
+            });
+
+        // every emoji, even next to one it could be mistaken with
+        let all: String = EMOJI.concat();
+
+        assert_eq!(parse_emoji(&all).unwrap(), (0..2048).collect::<Vec<u16>>());
+        assert_eq!(
+            parse_emoji(&strip_selectors(&all)).unwrap(),
+            (0..2048).collect::<Vec<u16>>()
+        );
+    }
+
+    #[test]
+    fn keyphrase_from_emoji_invalid() {
+        // wrong checksum, not an emoji, 11 emoji
+        assert!(KeyPhrase::from_emoji(&"⌚ ".repeat(12), Language::English).is_err());
+        assert!(KeyPhrase::from_emoji(&("⌚ ".repeat(11) + "a"), Language::English).is_err());
+        assert!(KeyPhrase::from_emoji(&"⌚ ".repeat(11), Language::English).is_err());
+        assert!(KeyPhrase::from_emoji("", Language::English).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Base58, base64 and bech32 encodings of a [`Seed`][Seed] and of the entropy of a
+//! [`KeyPhrase`][KeyPhrase], enabled by the `encodings` feature.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::seed::Seed;
+use bech32::{FromBase32, ToBase32, Variant};
+use std::convert::TryFrom;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+impl Seed {
+    /// Get the seed value as base58, with the Bitcoin alphabet and without a checksum
+    ///
+    /// The result can be parsed back with [`Seed::from_base58()`][Seed::from_base58()].
+    ///
+    /// [Seed::from_base58()]: ../seed/struct.Seed.html#method.from_base58
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.as_bytes()).into_string()
+    }
+
+    /// Parse a seed previously written as base58
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not base58, or
+    /// `ErrorKind::InvalidSeedLength` if it isn't 64 bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    /// use std::convert::TryFrom;
+    ///
+    /// let seed = Seed::try_from(&[0x5e; 64][..]).unwrap();
+    ///
+    /// assert_eq!(Seed::from_base58(&seed.to_base58()).unwrap(), seed);
+    /// ```
+    pub fn from_base58(encoded: &str) -> Result<Seed, Error> {
+        let bytes: Vec<u8> = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|_| ErrorKind::InvalidEncoding("invalid base58"))?;
+
+        from_decoded(bytes)
+    }
+
+    /// Get the seed value as base64, with the standard alphabet and padding
+    ///
+    /// The result can be parsed back with [`Seed::from_base64()`][Seed::from_base64()].
+    ///
+    /// [Seed::from_base64()]: ../seed/struct.Seed.html#method.from_base64
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    /// Parse a seed previously written as base64
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not padded
+    /// base64, or `ErrorKind::InvalidSeedLength` if it isn't 64 bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    /// use std::convert::TryFrom;
+    ///
+    /// let seed = Seed::try_from(&[0x5e; 64][..]).unwrap();
+    ///
+    /// assert_eq!(Seed::from_base64(&seed.to_base64()).unwrap(), seed);
+    /// ```
+    pub fn from_base64(encoded: &str) -> Result<Seed, Error> {
+        let bytes: Vec<u8> =
+            base64::decode(encoded).map_err(|_| ErrorKind::InvalidEncoding("invalid base64"))?;
+
+        from_decoded(bytes)
+    }
+
+    /// Get the seed value as bech32 with the human readable prefix `hrp`
+    ///
+    /// The prefix tells what the string is, and the checksum catches typos. Returns an `Error`
+    /// of kind `ErrorKind::InvalidEncoding` if `hrp` is not a valid bech32 prefix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    /// use std::convert::TryFrom;
+    ///
+    /// let seed = Seed::try_from(&[0x5e; 64][..]).unwrap();
+    /// let encoded = seed.to_bech32("seed").unwrap();
+    ///
+    /// assert!(encoded.starts_with("seed1"));
+    /// assert_eq!(Seed::from_bech32(&encoded, "seed").unwrap(), seed);
+    /// ```
+    pub fn to_bech32(&self, hrp: &str) -> Result<String, Error> {
+        encode_bech32(hrp, self.as_bytes())
+    }
+
+    /// Parse a seed previously written as bech32 with the human readable prefix `hrp`
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not bech32 or
+    /// has another prefix, or `ErrorKind::InvalidSeedLength` if it isn't 64 bytes long.
+    pub fn from_bech32(encoded: &str, hrp: &str) -> Result<Seed, Error> {
+        from_decoded(decode_bech32(encoded, hrp)?)
+    }
+}
+
+impl KeyPhrase {
+    /// Get the entropy of the keyphrase as base58, with the Bitcoin alphabet and without a
+    /// checksum
+    ///
+    /// The result can be parsed back with
+    /// [`KeyPhrase::from_entropy_base58()`][KeyPhrase::from_entropy_base58()].
+    ///
+    /// [KeyPhrase::from_entropy_base58()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_base58
+    pub fn entropy_base58(&self) -> String {
+        bs58::encode(self.entropy()).into_string()
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy previously written as base58
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not base58, or
+    /// `ErrorKind::InvalidKeysize` if the entropy isn't of a length defined by BIP39.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let encoded = keyphrase.entropy_base58();
+    ///
+    /// assert_eq!(
+    ///     KeyPhrase::from_entropy_base58(&encoded, Language::English).unwrap().phrase(),
+    ///     keyphrase.phrase()
+    /// );
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_entropy_base58(encoded: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        let bytes: Vec<u8> = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|_| ErrorKind::InvalidEncoding("invalid base58"))?;
+
+        keyphrase_from_decoded(bytes, lang)
+    }
+
+    /// Get the entropy of the keyphrase as base64, with the standard alphabet and padding
+    ///
+    /// The result can be parsed back with
+    /// [`KeyPhrase::from_entropy_base64()`][KeyPhrase::from_entropy_base64()].
+    ///
+    /// [KeyPhrase::from_entropy_base64()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_base64
+    pub fn entropy_base64(&self) -> String {
+        base64::encode(self.entropy())
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy previously written as base64
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not padded
+    /// base64, or `ErrorKind::InvalidKeysize` if the entropy isn't of a length defined by BIP39.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_entropy_base64(encoded: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        let bytes: Vec<u8> =
+            base64::decode(encoded).map_err(|_| ErrorKind::InvalidEncoding("invalid base64"))?;
+
+        keyphrase_from_decoded(bytes, lang)
+    }
+
+    /// Get the entropy of the keyphrase as bech32 with the human readable prefix `hrp`
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if `hrp` is not a valid bech32
+    /// prefix.
+    pub fn entropy_bech32(&self, hrp: &str) -> Result<String, Error> {
+        encode_bech32(hrp, self.entropy())
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy previously written as bech32 with the
+    /// human readable prefix `hrp`
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not bech32 or
+    /// has another prefix, or `ErrorKind::InvalidKeysize` if the entropy isn't of a length
+    /// defined by BIP39.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_entropy_bech32(
+        encoded: &str,
+        hrp: &str,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        keyphrase_from_decoded(decode_bech32(encoded, hrp)?, lang)
+    }
+}
+
+/// Write `bytes` as bech32 with the human readable prefix `hrp`
+fn encode_bech32(hrp: &str, bytes: &[u8]) -> Result<String, Error> {
+    let encoded: String = bech32::encode(hrp, bytes.to_base32(), Variant::Bech32)
+        .map_err(|_| ErrorKind::InvalidEncoding("invalid bech32 prefix"))?;
+
+    Ok(encoded)
+}
+
+/// Read the bytes of a bech32 string with the human readable prefix `hrp`
+fn decode_bech32(encoded: &str, hrp: &str) -> Result<Vec<u8>, Error> {
+    let (found_hrp, data, variant) =
+        bech32::decode(encoded).map_err(|_| ErrorKind::InvalidEncoding("invalid bech32"))?;
+
+    if variant != Variant::Bech32 {
+        Err(ErrorKind::InvalidEncoding("bech32m is not supported"))?;
+    }
+
+    if !found_hrp.eq_ignore_ascii_case(hrp) {
+        Err(ErrorKind::InvalidEncoding("unexpected bech32 prefix"))?;
+    }
+
+    let bytes: Vec<u8> = Vec::<u8>::from_base32(&data)
+        .map_err(|_| ErrorKind::InvalidEncoding("invalid bech32 padding"))?;
+
+    Ok(bytes)
+}
+
+/// Create a seed from decoded bytes, wiping them
+#[allow(unused_mut)]
+fn from_decoded(mut bytes: Vec<u8>) -> Result<Seed, Error> {
+    let seed: Result<Seed, Error> = Seed::try_from(bytes.as_slice());
+
+    #[cfg(feature = "zeroize")]
+    bytes.zeroize();
+
+    seed
+}
+
+/// Create a keyphrase from decoded entropy, wiping it
+#[allow(unused_mut)]
+fn keyphrase_from_decoded(mut bytes: Vec<u8>, lang: Language) -> Result<KeyPhrase, Error> {
+    let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&bytes, lang);
+
+    #[cfg(feature = "zeroize")]
+    bytes.zeroize();
+
+    keyphrase
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEED: &str = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+
+    #[test]
+    fn seed_encodings() {
+        let seed: Seed = Seed::from_hex(SEED).unwrap();
+
+        let base58: String = seed.to_base58();
+        assert_eq!(
+            base58,
+            "4wpHkugSQQas49Mxk6QNsGuzXYkUYf76H19dZWYFHYiv74BqCk3Bwhyeex2i63yR4sQLoCzXYRHVoxoB2qucE5w1"
+        );
+        assert_eq!(Seed::from_base58(&base58).unwrap(), seed);
+
+        let base64: String = seed.to_base64();
+        assert_eq!(
+            base64,
+            "xVJXw2DAfHICmuvBtTwF7QNiraOOrT4+nvo3COU0lVMfCaaYdZnRgmTB4ckvLPFBYwx6PEq3yBsvABaY50Y7BA=="
+        );
+        assert_eq!(Seed::from_base64(&base64).unwrap(), seed);
+
+        let bech32: String = seed.to_bech32("seed").unwrap();
+        assert_eq!(
+            bech32,
+            "seed1c4f90smqcp78yq56a0qm20q9a5pk9tdr36knu057lgms3ef5j4f37zdxnp6en5vzvnq7rjf09nc5zccv0g7y4d7grvhsq95cuarrkpqexac57"
+        );
+        assert_eq!(Seed::from_bech32(&bech32, "seed").unwrap(), seed);
+        assert_eq!(
+            Seed::from_bech32(&bech32.to_uppercase(), "seed").unwrap(),
+            seed
+        );
+    }
+
+    #[test]
+    fn seed_encodings_invalid() {
+        let seed: Seed = Seed::from_hex(SEED).unwrap();
+
+        assert!(Seed::from_base58("0OIl").is_err());
+        assert!(Seed::from_base58(&bs58::encode(&[1u8; 32]).into_string()).is_err());
+        assert!(Seed::from_base64("not base64").is_err());
+        assert!(Seed::from_base64(&base64::encode([1u8; 65])).is_err());
+        assert!(seed.to_bech32("").is_err());
+
+        let bech32: String = seed.to_bech32("seed").unwrap();
+        let mut typo: String = bech32.clone();
+        typo.replace_range(10..11, if &bech32[10..11] == "q" { "p" } else { "q" });
+
+        assert!(Seed::from_bech32(&typo, "seed").is_err());
+        assert!(Seed::from_bech32(&bech32, "xprv").is_err());
+    }
+
+    #[test]
+    fn entropy_encodings() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(&[0x7f; 16], Language::English).unwrap();
+
+        let base58: String = keyphrase.entropy_base58();
+        assert_eq!(base58, "Gk9pcGhrxdtLwJN1qcWgHk");
+        assert_eq!(
+            KeyPhrase::from_entropy_base58(&base58, Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+
+        let base64: String = keyphrase.entropy_base64();
+        assert_eq!(base64, "f39/f39/f39/f39/f39/fw==");
+        assert_eq!(
+            KeyPhrase::from_entropy_base64(&base64, Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+
+        let bech32: String = keyphrase.entropy_bech32("entropy").unwrap();
+        assert!(bech32.starts_with("entropy1"));
+        assert_eq!(
+            KeyPhrase::from_entropy_bech32(&bech32, "entropy", Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+    }
+
+    #[test]
+    fn entropy_encodings_invalid() {
+        assert!(KeyPhrase::from_entropy_base58("0OIl", Language::English).is_err());
+        assert!(
+            KeyPhrase::from_entropy_base64(&base64::encode([1u8; 15]), Language::English).is_err()
+        );
+        assert!(KeyPhrase::from_entropy_bech32(
+            &Seed::from_hex(SEED).unwrap().to_bech32("entropy").unwrap(),
+            "entropy",
+            Language::English
+        )
+        .is_err());
+    }
+}
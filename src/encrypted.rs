@@ -0,0 +1,280 @@
+//! Password encrypted export of a [`KeyPhrase`][KeyPhrase], enabled by the `encrypted-export`
+//! feature.
+//!
+//! The format is:
+//!
+//! - a header made of the magic bytes `KPE`, a version byte, the Argon2id memory in KiB, number
+//!   of iterations and number of lanes as big endian `u32`s, a 16 bytes salt and a 12 bytes nonce
+//! - the language code and the entropy of the keyphrase, sealed with ChaCha20-Poly1305 under the
+//!   Argon2id key of the password, followed by the 16 bytes tag
+//!
+//! The header is authenticated with the entropy, so none of it can be changed without the
+//! password.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::gen_random_bytes;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::passphrase::Passphrase;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::convert::TryInto;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+const MAGIC: &[u8; 3] = b"KPE";
+const VERSION: u8 = 1;
+
+const SALT_BYTES: usize = 16;
+const NONCE_BYTES: usize = 12;
+const TAG_BYTES: usize = 16;
+const HEADER_BYTES: usize = MAGIC.len() + 1 + 3 * 4 + SALT_BYTES + NONCE_BYTES;
+
+/// Argon2id memory, iterations and lanes of new exports
+const ARGON2_PARAMS: (u32, u32, u32) = (64 * 1024, 3, 1);
+
+/// Most memory, iterations and lanes an import may ask for, so that a crafted file can't exhaust
+/// the memory or keep the CPU busy for hours
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_LANES: u32 = 16;
+
+impl KeyPhrase {
+    /// Encrypt the keyphrase with `password`, to store it digitally
+    ///
+    /// The key is derived from the password with Argon2id, using 64MB of memory, and the
+    /// entropy and language are sealed with ChaCha20-Poly1305. The result is 77 to 93 bytes
+    /// long, and can be decrypted with
+    /// [`KeyPhrase::import_encrypted()`][KeyPhrase::import_encrypted()].
+    ///
+    /// The keyphrase is only as safe as the password: anyone with the export can try passwords
+    /// offline, so it must be long and random.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let exported = keyphrase.export_encrypted("correct horse battery staple");
+    ///
+    /// let imported = KeyPhrase::import_encrypted(&exported, "correct horse battery staple").unwrap();
+    ///
+    /// assert_eq!(imported.phrase(), keyphrase.phrase());
+    /// assert!(KeyPhrase::import_encrypted(&exported, "wrong password").is_err());
+    /// ```
+    ///
+    /// [KeyPhrase::import_encrypted()]: ../keyphrase/struct.KeyPhrase.html#method.import_encrypted
+    pub fn export_encrypted<P>(&self, password: P) -> Vec<u8>
+    where
+        P: Into<Passphrase>,
+    {
+        let (memory_kib, iterations, lanes): (u32, u32, u32) = ARGON2_PARAMS;
+
+        export(self, &password.into(), memory_kib, iterations, lanes)
+            .expect("the default Argon2 parameters are valid")
+    }
+
+    /// Decrypt a keyphrase exported with
+    /// [`KeyPhrase::export_encrypted()`][KeyPhrase::export_encrypted()]
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidPassword` if the password is wrong or the
+    /// export was modified, `ErrorKind::InvalidEncoding` if the bytes are not an export, or
+    /// `ErrorKind::InvalidKdfParams` if its Argon2 parameters are invalid or ask for more than
+    /// 1 GiB of memory, 64 iterations or 16 lanes.
+    ///
+    /// [KeyPhrase::export_encrypted()]: ../keyphrase/struct.KeyPhrase.html#method.export_encrypted
+    pub fn import_encrypted<P>(bytes: &[u8], password: P) -> Result<KeyPhrase, Error>
+    where
+        P: Into<Passphrase>,
+    {
+        if bytes.len() < HEADER_BYTES + 1 + TAG_BYTES || &bytes[..MAGIC.len()] != MAGIC {
+            Err(ErrorKind::InvalidEncoding("not an encrypted keyphrase"))?;
+        }
+
+        if bytes[MAGIC.len()] != VERSION {
+            Err(ErrorKind::InvalidEncoding("unsupported version"))?;
+        }
+
+        let (header, sealed): (&[u8], &[u8]) = bytes.split_at(HEADER_BYTES);
+        let param = |i: usize| -> u32 {
+            let offset: usize = MAGIC.len() + 1 + 4 * i;
+
+            u32::from_be_bytes(header[offset..offset + 4].try_into().unwrap())
+        };
+
+        if param(0) > MAX_MEMORY_KIB {
+            Err(ErrorKind::InvalidKdfParams("too much Argon2 memory"))?;
+        }
+
+        if param(1) > MAX_ITERATIONS {
+            Err(ErrorKind::InvalidKdfParams("too many Argon2 iterations"))?;
+        }
+
+        if param(2) > MAX_LANES {
+            Err(ErrorKind::InvalidKdfParams("too many Argon2 lanes"))?;
+        }
+
+        let salt: &[u8] =
+            &header[HEADER_BYTES - NONCE_BYTES - SALT_BYTES..HEADER_BYTES - NONCE_BYTES];
+        let nonce: &[u8] = &header[HEADER_BYTES - NONCE_BYTES..];
+
+        #[allow(unused_mut)]
+        let mut key: [u8; 32] = derive_key(&password.into(), salt, param(0), param(1), param(2))?;
+        let plaintext: Result<Vec<u8>, _> = ChaCha20Poly1305::new(Key::from_slice(&key)).decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: sealed,
+                aad: header,
+            },
+        );
+
+        #[cfg(feature = "zeroize")]
+        key.zeroize();
+
+        #[allow(unused_mut)]
+        let mut plaintext: Vec<u8> = plaintext.map_err(|_| ErrorKind::InvalidPassword)?;
+
+        let keyphrase: Result<KeyPhrase, Error> = match Language::from_code(plaintext[0]) {
+            Some(lang) => KeyPhrase::from_entropy(&plaintext[1..], lang),
+            None => Err(ErrorKind::InvalidEncoding("unknown language").into()),
+        };
+
+        #[cfg(feature = "zeroize")]
+        plaintext.zeroize();
+
+        keyphrase
+    }
+}
+
+/// Encrypt a keyphrase with the given Argon2id parameters
+fn export(
+    keyphrase: &KeyPhrase,
+    password: &Passphrase,
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+) -> Result<Vec<u8>, Error> {
+    let mut out: Vec<u8> =
+        Vec::with_capacity(HEADER_BYTES + 1 + keyphrase.entropy().len() + TAG_BYTES);
+
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&memory_kib.to_be_bytes());
+    out.extend_from_slice(&iterations.to_be_bytes());
+    out.extend_from_slice(&lanes.to_be_bytes());
+    out.extend_from_slice(&gen_random_bytes(SALT_BYTES));
+    out.extend_from_slice(&gen_random_bytes(NONCE_BYTES));
+
+    let salt: &[u8] = &out[HEADER_BYTES - NONCE_BYTES - SALT_BYTES..HEADER_BYTES - NONCE_BYTES];
+
+    #[allow(unused_mut)]
+    let mut key: [u8; 32] = derive_key(password, salt, memory_kib, iterations, lanes)?;
+    let mut plaintext: Vec<u8> = Vec::with_capacity(1 + keyphrase.entropy().len());
+
+    plaintext.push(keyphrase.language().code());
+    plaintext.extend_from_slice(keyphrase.entropy());
+
+    let sealed: Vec<u8> = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(
+            Nonce::from_slice(&out[HEADER_BYTES - NONCE_BYTES..]),
+            Payload {
+                msg: &plaintext,
+                aad: &out,
+            },
+        )
+        .expect("the plaintext is short enough for ChaCha20");
+
+    #[cfg(feature = "zeroize")]
+    {
+        key.zeroize();
+        plaintext.zeroize();
+    }
+
+    out.extend_from_slice(&sealed);
+
+    Ok(out)
+}
+
+/// Derive the encryption key from the password with Argon2id
+fn derive_key(
+    password: &Passphrase,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+) -> Result<[u8; 32], Error> {
+    let mut key: [u8; 32] = [0; 32];
+    let params: Params = Params::new(memory_kib, iterations, lanes, Some(key.len()))
+        .map_err(|_| ErrorKind::InvalidKdfParams("invalid Argon2 parameters"))?;
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.expose_secret().as_bytes(), salt, &mut key)
+        .map_err(|_| ErrorKind::InvalidKdfParams("invalid Argon2 parameters"))?;
+
+    Ok(key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+
+    #[test]
+    fn keyphrase_export_encrypted() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+        let password: Passphrase = Passphrase::new("password");
+        let exported: Vec<u8> = export(&keyphrase, &password, 64, 1, 1).unwrap();
+
+        assert_eq!(exported.len(), HEADER_BYTES + 1 + 32 + TAG_BYTES);
+        assert_eq!(
+            KeyPhrase::import_encrypted(&exported, "password")
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+        assert!(KeyPhrase::import_encrypted(&exported, "Password").is_err());
+
+        // the salt and nonce are random
+        assert_ne!(export(&keyphrase, &password, 64, 1, 1).unwrap(), exported);
+    }
+
+    #[test]
+    fn keyphrase_import_encrypted_invalid() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+        let exported: Vec<u8> = export(&keyphrase, &Passphrase::new("password"), 64, 1, 1).unwrap();
+
+        // every byte is authenticated, the Argon2 parameters are left alone to keep this fast
+        (0..MAGIC.len() + 1)
+            .chain(MAGIC.len() + 1 + 3 * 4..exported.len())
+            .for_each(|i: usize| {
+                let mut modified: Vec<u8> = exported.clone();
+
+                modified[i] ^= 1;
+
+                assert!(KeyPhrase::import_encrypted(&modified, "password").is_err());
+            });
+
+        // memory, iterations and lanes above the limits are rejected before deriving the key
+        (0..3).for_each(|param: usize| {
+            let mut greedy: Vec<u8> = exported.clone();
+
+            greedy[MAGIC.len() + 1 + 4 * param] = 0xff;
+
+            match KeyPhrase::import_encrypted(&greedy, "password")
+                .unwrap_err()
+                .kind()
+            {
+                ErrorKind::InvalidKdfParams(_) => {}
+                kind => panic!("unexpected error: {:?}", kind),
+            }
+        });
+
+        assert!(KeyPhrase::import_encrypted(&exported[..exported.len() - 1], "password").is_err());
+        assert!(KeyPhrase::import_encrypted(&exported[..HEADER_BYTES], "password").is_err());
+        assert!(KeyPhrase::import_encrypted(b"", "password").is_err());
+    }
+}
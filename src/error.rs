@@ -1,18 +1,251 @@
+use crate::analog::Card;
 use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use std::error;
+use std::fmt;
 
-#[derive(Debug, Fail)]
+/// The error returned by every fallible function of the crate
+///
+/// It implements `std::error::Error`, so it works with `?` in functions returning
+/// `Box<dyn std::error::Error>` or the error types of `anyhow` and `thiserror`. Match on
+/// [`Error::kind()`][Error::kind()] to handle specific errors.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{ErrorKind, KeyPhrase, Language};
+///
+/// let error = KeyPhrase::from_phrase("park remain", Language::English).unwrap_err();
+///
+/// match error.kind() {
+///     ErrorKind::InvalidWordLength { words, .. } => assert_eq!(*words, 2),
+///     other => panic!("unexpected error: {}", other),
+/// }
+/// ```
+///
+/// [Error::kind()]: ./struct.Error.html#method.kind
+#[derive(Debug)]
+pub struct Error {
+	kind: ErrorKind,
+}
+
+impl Error {
+	/// Get the kind of the error
+	pub fn kind(&self) -> &ErrorKind {
+		&self.kind
+	}
+
+	/// Get the kind of the error, taking ownership of its data
+	pub fn into_kind(self) -> ErrorKind {
+		self.kind
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&self.kind, f)
+	}
+}
+
+impl error::Error for Error {}
+
+impl From<ErrorKind> for Error {
+	fn from(kind: ErrorKind) -> Error {
+		Error { kind }
+	}
+}
+
+/// Every kind of error of the crate, with the input that caused it
+///
+/// New kinds are added as features grow, so matches on `ErrorKind` need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
-	#[fail(display = "invalid checksum")]
+	/// The checksum of the phrase doesn't match its words
 	InvalidChecksum,
-	#[fail(display = "invalid word in phrase")]
-	InvalidWord,
-	#[fail(display = "invalid keysize: {}", _0)]
+	/// The word at `index`, starting at 0, isn't in the wordlist of `lang`, or of another
+	/// standard such as SLIP-0039 or RFC 1751 when `lang` is `None`
+	InvalidWord {
+		word: String,
+		index: usize,
+		lang: Option<Language>,
+	},
+	/// The word at `index` is in the wordlist once normalized to NFKD or NFC, which the phrase
+	/// must be written in
+	InvalidNormalization { word: String, index: usize },
+	/// The entropy isn't of a length defined by BIP39, in bits
 	InvalidKeysize(usize),
-	#[fail(display = "invalid number of words in phrase: {}", _0)]
-	InvalidWordLength(usize),
-	#[fail(
-		display = "invalid entropy length {}bits for keyphrase type {:?}",
-		_0, _1
-	)]
+	/// The number of words isn't one of those `expected`
+	InvalidWordLength { words: usize, expected: &'static str },
+	/// The entropy, of a length in bits, doesn't match the number of words
 	InvalidEntropyLength(usize, KeyPhraseType),
+	/// Two phrases combined together have different numbers of words
+	WordCountMismatch(usize, usize),
+	/// Two phrases combined together are in different languages
+	LanguageMismatch(Language, Language),
+	/// The language isn't enabled by the features of the crate, or isn't supported by the format
+	UnsupportedLanguage,
+	/// The phrase is valid, checksum included, in each of these languages, so its language can't
+	/// be detected
+	AmbiguousLanguage(Vec<Language>),
+	/// The entropy source of the operating system failed
+	EntropyUnavailable,
+	/// An entropy source given by the caller failed, with its message
+	EntropySourceFailed(String),
+	/// A dice roll isn't between 1 and 6
+	InvalidDiceRoll(u8),
+	/// The dice rolls or cards don't give the number of bits of entropy needed
+	NotEnoughEntropy(usize),
+	/// A card isn't one of the 52 of a deck, or was drawn twice
+	InvalidCard(Card),
+	/// The number of cards drawn isn't one that is accepted
+	InvalidDeckSize(usize),
+	/// An encoded keyphrase or seed is malformed
+	InvalidEncoding(&'static str),
+	/// The parameters of a key derivation function are out of range
+	InvalidKdfParams(&'static str),
+	/// A seed isn't 64 bytes long
+	InvalidSeedLength(usize),
+	/// The key derived at an index is invalid, which happens with a probability lower than 1 in
+	/// 2^127
+	InvalidDerivedKey,
+	/// A child number is out of range
+	InvalidChildNumber(u32),
+	/// A hardened child was derived from an extended public key
+	HardenedFromPublic,
+	/// A derivation path is malformed
+	InvalidDerivationPath(String),
+	/// A normal child was derived from an ed25519 key
+	HardenedOnly,
+	/// The threshold or number of shares of a secret sharing is out of range
+	InvalidSharing(&'static str),
+	/// A share is malformed, or doesn't belong with the others
+	InvalidShare(&'static str),
+	/// A keystore is malformed or uses unsupported parameters
+	InvalidKeystore(&'static str),
+	/// Encrypted data can't be decrypted with the password
+	InvalidPassword,
+}
+
+impl ErrorKind {
+	/// Get the machine-readable code of the kind of error, such as `"invalid_checksum"`
+	///
+	/// Codes are snake case and never change, so that clients of services built on the crate
+	/// can rely on them, while the messages of `Display` may be reworded.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use keyphrase::{KeyPhrase, Language};
+	///
+	/// let error = KeyPhrase::from_phrase("park remain", Language::English).unwrap_err();
+	///
+	/// assert_eq!(error.kind().code(), "invalid_word_length");
+	/// ```
+	pub fn code(&self) -> &'static str {
+		match self {
+			ErrorKind::InvalidChecksum => "invalid_checksum",
+			ErrorKind::InvalidWord { .. } => "invalid_word",
+			ErrorKind::InvalidNormalization { .. } => "invalid_normalization",
+			ErrorKind::InvalidKeysize(_) => "invalid_keysize",
+			ErrorKind::InvalidWordLength { .. } => "invalid_word_length",
+			ErrorKind::InvalidEntropyLength(..) => "invalid_entropy_length",
+			ErrorKind::WordCountMismatch(..) => "word_count_mismatch",
+			ErrorKind::LanguageMismatch(..) => "language_mismatch",
+			ErrorKind::UnsupportedLanguage => "unsupported_language",
+			ErrorKind::AmbiguousLanguage(_) => "ambiguous_language",
+			ErrorKind::EntropyUnavailable => "entropy_unavailable",
+			ErrorKind::EntropySourceFailed(_) => "entropy_source_failed",
+			ErrorKind::InvalidDiceRoll(_) => "invalid_dice_roll",
+			ErrorKind::NotEnoughEntropy(_) => "not_enough_entropy",
+			ErrorKind::InvalidCard(_) => "invalid_card",
+			ErrorKind::InvalidDeckSize(_) => "invalid_deck_size",
+			ErrorKind::InvalidEncoding(_) => "invalid_encoding",
+			ErrorKind::InvalidKdfParams(_) => "invalid_kdf_params",
+			ErrorKind::InvalidSeedLength(_) => "invalid_seed_length",
+			ErrorKind::InvalidDerivedKey => "invalid_derived_key",
+			ErrorKind::InvalidChildNumber(_) => "invalid_child_number",
+			ErrorKind::HardenedFromPublic => "hardened_from_public",
+			ErrorKind::InvalidDerivationPath(_) => "invalid_derivation_path",
+			ErrorKind::HardenedOnly => "hardened_only",
+			ErrorKind::InvalidSharing(_) => "invalid_sharing",
+			ErrorKind::InvalidShare(_) => "invalid_share",
+			ErrorKind::InvalidKeystore(_) => "invalid_keystore",
+			ErrorKind::InvalidPassword => "invalid_password",
+		}
+	}
+}
+
+impl fmt::Display for ErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ErrorKind::InvalidChecksum => write!(f, "invalid checksum"),
+			ErrorKind::InvalidWord { index, lang, .. } => match lang {
+				Some(lang) => write!(
+					f,
+					"invalid word in phrase at position {}, not in the {:?} wordlist",
+					index, lang
+				),
+				None => write!(f, "invalid word in phrase at position {}", index),
+			},
+			ErrorKind::InvalidNormalization { index, .. } => write!(
+				f,
+				"word at position {} is not normalized like the wordlist",
+				index
+			),
+			ErrorKind::InvalidKeysize(size) => write!(f, "invalid keysize: {}", size),
+			ErrorKind::InvalidWordLength { words, expected } => write!(
+				f,
+				"invalid number of words in phrase: {}, expected {}",
+				words, expected
+			),
+			ErrorKind::InvalidEntropyLength(bits, keyphrase_type) => write!(
+				f,
+				"invalid entropy length {}bits for keyphrase type {:?}",
+				bits, keyphrase_type
+			),
+			ErrorKind::WordCountMismatch(first, second) => {
+				write!(f, "mismatched number of words: {} and {}", first, second)
+			}
+			ErrorKind::LanguageMismatch(first, second) => {
+				write!(f, "mismatched languages: {:?} and {:?}", first, second)
+			}
+			ErrorKind::UnsupportedLanguage => write!(f, "unsupported language"),
+			ErrorKind::AmbiguousLanguage(langs) => {
+				write!(f, "phrase is valid in several languages: {:?}", langs)
+			}
+			ErrorKind::EntropyUnavailable => write!(f, "entropy source unavailable"),
+			ErrorKind::EntropySourceFailed(message) => {
+				write!(f, "entropy source failed: {}", message)
+			}
+			ErrorKind::InvalidDiceRoll(roll) => write!(f, "invalid dice roll: {}", roll),
+			ErrorKind::NotEnoughEntropy(bits) => {
+				write!(f, "not enough input for {}bits of entropy", bits)
+			}
+			ErrorKind::InvalidCard(card) => write!(f, "invalid or duplicate card: {:?}", card),
+			ErrorKind::InvalidDeckSize(cards) => write!(f, "invalid number of cards: {}", cards),
+			ErrorKind::InvalidEncoding(reason) => write!(f, "invalid encoding: {}", reason),
+			ErrorKind::InvalidKdfParams(reason) => {
+				write!(f, "invalid key derivation parameters: {}", reason)
+			}
+			ErrorKind::InvalidSeedLength(bytes) => write!(f, "invalid seed length: {} bytes", bytes),
+			ErrorKind::InvalidDerivedKey => write!(f, "derived key is invalid, use another index"),
+			ErrorKind::InvalidChildNumber(index) => write!(f, "invalid child number: {}", index),
+			ErrorKind::HardenedFromPublic => {
+				write!(f, "hardened children can't be derived from a public key")
+			}
+			ErrorKind::InvalidDerivationPath(path) => {
+				write!(f, "invalid derivation path: {}", path)
+			}
+			ErrorKind::HardenedOnly => write!(f, "ed25519 keys only have hardened children"),
+			ErrorKind::InvalidSharing(reason) => {
+				write!(f, "invalid sharing parameters: {}", reason)
+			}
+			ErrorKind::InvalidShare(reason) => write!(f, "invalid share: {}", reason),
+			ErrorKind::InvalidKeystore(reason) => write!(f, "invalid keystore: {}", reason),
+			ErrorKind::InvalidPassword => {
+				write!(f, "wrong password, or the encrypted data was modified")
+			}
+		}
+	}
 }
@@ -0,0 +1,482 @@
+//! C bindings, enabled by the `ffi` feature.
+//!
+//! The functions are declared in `include/keyphrase.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/keyphrase.h`. The crate only builds an `rlib`
+//! by default, build the library to link with
+//! `cargo rustc --lib --release --features ffi --crate-type staticlib`, or `cdylib`.
+//!
+//! Every function returns a [`KeyPhraseStatus`][KeyPhraseStatus], `KEY_PHRASE_STATUS_OK` on
+//! success, and writes its results through out pointers.
+//!
+//! Languages are given by their stable codes, 0 for English to 7 for Spanish, in the order of
+//! [`Language`][Language]. Phrases returned by the library must be released with
+//! `keyphrase_string_free()`, which wipes them first. Seeds and entropy are written into buffers
+//! of the caller, which should wipe them with `keyphrase_zeroize()` once done.
+//!
+//! ```c
+//! char *phrase = NULL;
+//! uint8_t seed[KEYPHRASE_SEED_BYTES];
+//!
+//! if (keyphrase_generate(12, 0, &phrase) == KEY_PHRASE_STATUS_OK) {
+//!     keyphrase_seed_bip39(phrase, 0, "", seed);
+//!     keyphrase_string_free(phrase);
+//!     keyphrase_zeroize(seed, sizeof(seed));
+//! }
+//! ```
+//!
+//! [KeyPhraseStatus]: ./enum.KeyPhraseStatus.html
+//! [Language]: ../language/enum.Language.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Length of the seeds written by `keyphrase_seed_bip39()` and `keyphrase_seed()`
+pub const KEYPHRASE_SEED_BYTES: usize = 64;
+
+/// Longest entropy written by `keyphrase_to_entropy()`, for 24 words
+pub const KEYPHRASE_MAX_ENTROPY_BYTES: usize = 32;
+
+/// The result of every function of the C bindings
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyPhraseStatus {
+    /// Success
+    Ok = 0,
+    /// A pointer is null, a string isn't UTF-8, or a buffer is too small
+    InvalidArgument = 1,
+    /// The language code is unknown, or its feature isn't enabled
+    UnsupportedLanguage = 2,
+    /// A word isn't in the wordlist
+    InvalidWord = 3,
+    /// The phrase doesn't match its checksum
+    InvalidChecksum = 4,
+    /// The number of words isn't 12, 15, 18, 21 or 24
+    InvalidWordLength = 5,
+    /// The entropy isn't 16, 20, 24, 28 or 32 bytes
+    InvalidKeysize = 6,
+    /// The operating system gave no randomness
+    EntropyUnavailable = 7,
+    /// Any other error
+    Other = 8,
+}
+
+impl From<Error> for KeyPhraseStatus {
+    fn from(error: Error) -> KeyPhraseStatus {
+        match error.kind() {
+            ErrorKind::UnsupportedLanguage => KeyPhraseStatus::UnsupportedLanguage,
+            ErrorKind::InvalidWord { .. } | ErrorKind::InvalidNormalization { .. } => {
+                KeyPhraseStatus::InvalidWord
+            }
+            ErrorKind::InvalidChecksum => KeyPhraseStatus::InvalidChecksum,
+            ErrorKind::InvalidWordLength { .. } => KeyPhraseStatus::InvalidWordLength,
+            ErrorKind::InvalidKeysize(_) | ErrorKind::InvalidEntropyLength(..) => {
+                KeyPhraseStatus::InvalidKeysize
+            }
+            ErrorKind::EntropyUnavailable | ErrorKind::EntropySourceFailed(_) => {
+                KeyPhraseStatus::EntropyUnavailable
+            }
+            _ => KeyPhraseStatus::Other,
+        }
+    }
+}
+
+/// Generate a new keyphrase of `word_count` words in the language `language`
+///
+/// On success, `*out_phrase` points to the phrase, to release with `keyphrase_string_free()`.
+///
+/// # Safety
+///
+/// `out_phrase` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_generate(
+    word_count: usize,
+    language: u8,
+    out_phrase: *mut *mut c_char,
+) -> KeyPhraseStatus {
+    if out_phrase.is_null() {
+        return KeyPhraseStatus::InvalidArgument;
+    }
+
+    let lang: Language = match Language::from_code(language) {
+        Some(lang) => lang,
+        None => return KeyPhraseStatus::UnsupportedLanguage,
+    };
+
+    match KeyPhraseType::for_word_count(word_count)
+        .and_then(|keyphrase_type: KeyPhraseType| KeyPhrase::try_new(keyphrase_type, lang))
+    {
+        Ok(keyphrase) => write_phrase(keyphrase, out_phrase),
+        Err(error) => error.into(),
+    }
+}
+
+/// Check that `phrase` is a valid keyphrase in the language `language`
+///
+/// # Safety
+///
+/// `phrase` must be null or a valid NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_validate(
+    phrase: *const c_char,
+    language: u8,
+) -> KeyPhraseStatus {
+    match read_keyphrase(phrase, language) {
+        Ok(_) => KeyPhraseStatus::Ok,
+        Err(status) => status,
+    }
+}
+
+/// Write the entropy of `phrase` into `out_entropy`, and its length into `*out_len`
+///
+/// `out_capacity` is the size of `out_entropy`, `KEYPHRASE_MAX_ENTROPY_BYTES` is always enough.
+///
+/// # Safety
+///
+/// `phrase` must be null or a valid NUL terminated string, `out_entropy` null or valid for
+/// `out_capacity` bytes of writes, and `out_len` null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_to_entropy(
+    phrase: *const c_char,
+    language: u8,
+    out_entropy: *mut u8,
+    out_capacity: usize,
+    out_len: *mut usize,
+) -> KeyPhraseStatus {
+    if out_entropy.is_null() || out_len.is_null() {
+        return KeyPhraseStatus::InvalidArgument;
+    }
+
+    let keyphrase: KeyPhrase = match read_keyphrase(phrase, language) {
+        Ok(keyphrase) => keyphrase,
+        Err(status) => return status,
+    };
+    let entropy: &[u8] = keyphrase.entropy();
+
+    if entropy.len() > out_capacity {
+        return KeyPhraseStatus::InvalidArgument;
+    }
+
+    ptr::copy_nonoverlapping(entropy.as_ptr(), out_entropy, entropy.len());
+    *out_len = entropy.len();
+
+    KeyPhraseStatus::Ok
+}
+
+/// Create the keyphrase of `entropy_len` bytes of entropy, in the language `language`
+///
+/// On success, `*out_phrase` points to the phrase, to release with `keyphrase_string_free()`.
+///
+/// # Safety
+///
+/// `entropy` must be null or valid for `entropy_len` bytes of reads, and `out_phrase` null or
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_from_entropy(
+    entropy: *const u8,
+    entropy_len: usize,
+    language: u8,
+    out_phrase: *mut *mut c_char,
+) -> KeyPhraseStatus {
+    if entropy.is_null() || out_phrase.is_null() {
+        return KeyPhraseStatus::InvalidArgument;
+    }
+
+    let lang: Language = match Language::from_code(language) {
+        Some(lang) => lang,
+        None => return KeyPhraseStatus::UnsupportedLanguage,
+    };
+
+    match KeyPhrase::from_entropy(slice::from_raw_parts(entropy, entropy_len), lang) {
+        Ok(keyphrase) => write_phrase(keyphrase, out_phrase),
+        Err(error) => error.into(),
+    }
+}
+
+/// Derive the BIP39 seed of `phrase` and `password` into `out_seed`
+///
+/// The seed is the one of any BIP39 wallet for the same phrase and password.
+///
+/// # Safety
+///
+/// `phrase` and `password` must be null or valid NUL terminated strings, and `out_seed` null or
+/// valid for `KEYPHRASE_SEED_BYTES` bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_seed_bip39(
+    phrase: *const c_char,
+    language: u8,
+    password: *const c_char,
+    out_seed: *mut u8,
+) -> KeyPhraseStatus {
+    write_seed(
+        phrase,
+        language,
+        password,
+        out_seed,
+        |keyphrase: &KeyPhrase, password: &str| Seed::new_bip39(keyphrase, password),
+    )
+}
+
+/// Derive the legacy seed of `phrase` and `password`, salted with `"keyphrase"`, into
+/// `out_seed`
+///
+/// Only built without the `strict-bip39-only` feature, like `Seed::new()`.
+///
+/// # Safety
+///
+/// `phrase` and `password` must be null or valid NUL terminated strings, and `out_seed` null or
+/// valid for `KEYPHRASE_SEED_BYTES` bytes of writes.
+#[cfg(not(feature = "strict-bip39-only"))]
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_seed(
+    phrase: *const c_char,
+    language: u8,
+    password: *const c_char,
+    out_seed: *mut u8,
+) -> KeyPhraseStatus {
+    write_seed(
+        phrase,
+        language,
+        password,
+        out_seed,
+        |keyphrase: &KeyPhrase, password: &str| Seed::new(keyphrase, password),
+    )
+}
+
+/// Wipe and release a string returned by the library
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by the library, not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_string_free(string: *mut c_char) {
+    if string.is_null() {
+        return;
+    }
+
+    let string: CString = CString::from_raw(string);
+    let mut bytes: Vec<u8> = string.into_bytes_with_nul();
+
+    keyphrase_zeroize(bytes.as_mut_ptr(), bytes.len());
+}
+
+/// Wipe `len` bytes at `buffer`, in a way the compiler can't optimize out
+///
+/// # Safety
+///
+/// `buffer` must be null or valid for `len` bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn keyphrase_zeroize(buffer: *mut u8, len: usize) {
+    if buffer.is_null() {
+        return;
+    }
+
+    (0..len).for_each(|i: usize| ptr::write_volatile(buffer.add(i), 0));
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Get a static description of a status, never to be released
+///
+/// The status is taken as an integer, as C can pass any value for an enum: values that aren't a
+/// `KeyPhraseStatus` get `"unknown status"`.
+#[no_mangle]
+pub extern "C" fn keyphrase_status_message(status: u32) -> *const c_char {
+    let message: &'static [u8] = match status {
+        0 => b"ok\0",
+        1 => b"invalid argument\0",
+        2 => b"unsupported language\0",
+        3 => b"invalid word in phrase\0",
+        4 => b"invalid checksum\0",
+        5 => b"invalid number of words in phrase\0",
+        6 => b"invalid entropy length\0",
+        7 => b"entropy source unavailable\0",
+        8 => b"error\0",
+        _ => b"unknown status\0",
+    };
+
+    message.as_ptr() as *const c_char
+}
+
+/// Parse a phrase given as a C string
+unsafe fn read_keyphrase(
+    phrase: *const c_char,
+    language: u8,
+) -> Result<KeyPhrase, KeyPhraseStatus> {
+    let lang: Language =
+        Language::from_code(language).ok_or(KeyPhraseStatus::UnsupportedLanguage)?;
+
+    KeyPhrase::from_phrase(read_str(phrase)?, lang).map_err(KeyPhraseStatus::from)
+}
+
+/// Borrow a C string as UTF-8
+unsafe fn read_str<'a>(string: *const c_char) -> Result<&'a str, KeyPhraseStatus> {
+    if string.is_null() {
+        return Err(KeyPhraseStatus::InvalidArgument);
+    }
+
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| KeyPhraseStatus::InvalidArgument)
+}
+
+/// Hand the phrase of a keyphrase over to C
+unsafe fn write_phrase(keyphrase: KeyPhrase, out_phrase: *mut *mut c_char) -> KeyPhraseStatus {
+    // Words never contain NUL
+    *out_phrase = CString::new(keyphrase.into_phrase())
+        .expect("phrases have no NUL")
+        .into_raw();
+
+    KeyPhraseStatus::Ok
+}
+
+/// Derive a seed with `derive` into a buffer of C
+unsafe fn write_seed<F>(
+    phrase: *const c_char,
+    language: u8,
+    password: *const c_char,
+    out_seed: *mut u8,
+    derive: F,
+) -> KeyPhraseStatus
+where
+    F: Fn(&KeyPhrase, &str) -> Seed,
+{
+    if out_seed.is_null() {
+        return KeyPhraseStatus::InvalidArgument;
+    }
+
+    let keyphrase: KeyPhrase = match read_keyphrase(phrase, language) {
+        Ok(keyphrase) => keyphrase,
+        Err(status) => return status,
+    };
+    let password: &str = match read_str(password) {
+        Ok(password) => password,
+        Err(status) => return status,
+    };
+    let seed: Seed = derive(&keyphrase, password);
+
+    ptr::copy_nonoverlapping(seed.as_bytes().as_ptr(), out_seed, KEYPHRASE_SEED_BYTES);
+
+    KeyPhraseStatus::Ok
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ffi_roundtrip() {
+        unsafe {
+            let mut phrase: *mut c_char = ptr::null_mut();
+
+            assert_eq!(keyphrase_generate(24, 0, &mut phrase), KeyPhraseStatus::Ok);
+            assert_eq!(keyphrase_validate(phrase, 0), KeyPhraseStatus::Ok);
+
+            let mut entropy: [u8; KEYPHRASE_MAX_ENTROPY_BYTES] = [0; KEYPHRASE_MAX_ENTROPY_BYTES];
+            let mut len: usize = 0;
+
+            assert_eq!(
+                keyphrase_to_entropy(phrase, 0, entropy.as_mut_ptr(), entropy.len(), &mut len),
+                KeyPhraseStatus::Ok
+            );
+            assert_eq!(len, 32);
+
+            let mut restored: *mut c_char = ptr::null_mut();
+
+            assert_eq!(
+                keyphrase_from_entropy(entropy.as_ptr(), len, 0, &mut restored),
+                KeyPhraseStatus::Ok
+            );
+            assert_eq!(CStr::from_ptr(restored), CStr::from_ptr(phrase));
+
+            keyphrase_string_free(phrase);
+            keyphrase_string_free(restored);
+            keyphrase_zeroize(entropy.as_mut_ptr(), entropy.len());
+
+            assert_eq!(entropy, [0; KEYPHRASE_MAX_ENTROPY_BYTES]);
+        }
+    }
+
+    #[test]
+    fn ffi_seed() {
+        let phrase: &[u8] = b"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about\0";
+        let mut seed: [u8; KEYPHRASE_SEED_BYTES] = [0; KEYPHRASE_SEED_BYTES];
+
+        unsafe {
+            assert_eq!(
+                keyphrase_seed_bip39(
+                    phrase.as_ptr() as *const c_char,
+                    0,
+                    b"TREZOR\0".as_ptr() as *const c_char,
+                    seed.as_mut_ptr()
+                ),
+                KeyPhraseStatus::Ok
+            );
+        }
+
+        assert_eq!(&seed[..4], &[0xc5, 0x52, 0x57, 0xc3]);
+    }
+
+    #[test]
+    fn ffi_errors() {
+        let mut phrase: *mut c_char = ptr::null_mut();
+        let mut entropy: [u8; 16] = [0; 16];
+        let mut len: usize = 0;
+        let invalid: &[u8] = b"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon\0";
+
+        unsafe {
+            assert_eq!(
+                keyphrase_generate(13, 0, &mut phrase),
+                KeyPhraseStatus::InvalidWordLength
+            );
+            assert_eq!(
+                keyphrase_generate(12, 8, &mut phrase),
+                KeyPhraseStatus::UnsupportedLanguage
+            );
+            assert_eq!(
+                keyphrase_generate(12, 0, ptr::null_mut()),
+                KeyPhraseStatus::InvalidArgument
+            );
+            assert_eq!(
+                keyphrase_validate(invalid.as_ptr() as *const c_char, 0),
+                KeyPhraseStatus::InvalidChecksum
+            );
+            assert_eq!(
+                keyphrase_validate(ptr::null(), 0),
+                KeyPhraseStatus::InvalidArgument
+            );
+            assert_eq!(
+                keyphrase_from_entropy(entropy.as_ptr(), 15, 0, &mut phrase),
+                KeyPhraseStatus::InvalidKeysize
+            );
+            // a 12 words phrase doesn't fit in 15 bytes
+            assert_eq!(
+                keyphrase_to_entropy(
+                    b"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about\0".as_ptr() as *const c_char,
+                    0,
+                    entropy.as_mut_ptr(),
+                    15,
+                    &mut len
+                ),
+                KeyPhraseStatus::InvalidArgument
+            );
+            assert!(phrase.is_null());
+            assert_eq!(
+                CStr::from_ptr(keyphrase_status_message(
+                    KeyPhraseStatus::InvalidChecksum as u32
+                )),
+                CStr::from_bytes_with_nul(b"invalid checksum\0").unwrap()
+            );
+            assert_eq!(
+                CStr::from_ptr(keyphrase_status_message(42)),
+                CStr::from_bytes_with_nul(b"unknown status\0").unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Entry points into the internals of the crate for its fuzz targets.
+//!
+//! Only compiled with `--cfg fuzzing`, which `cargo fuzz` sets, so nothing here is part of the
+//! public API.
+
+use crate::util::{BitWriter, Bits11, IterExt};
+
+/// Split bytes into 11 bit words with `BitIter`, dropping the trailing bits that don't fill a
+/// word, then pack the words back into bytes with `BitWriter`
+///
+/// Returns the words and the packed bytes, whose last byte is padded with zero bits.
+pub fn bits_round_trip(data: &[u8]) -> (Vec<u16>, Vec<u8>) {
+    let words: Vec<u16> = data.iter().bits::<Bits11>().map(u16::from).collect();
+    let mut writer: BitWriter = BitWriter::with_capacity(words.len() * 11);
+
+    for &word in &words {
+        writer.push(Bits11::from(word));
+    }
+
+    assert_eq!(writer.len(), words.len() * 11);
+
+    (words, writer.into_bytes())
+}
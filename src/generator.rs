@@ -0,0 +1,154 @@
+//! Generating an endless stream of [`KeyPhrase`][KeyPhrase]s.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::DefaultRng;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use rand_core::RngCore;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Infinite iterator of random [`KeyPhrase`][KeyPhrase]s, returned by
+/// [`KeyPhrase::generator()`][KeyPhrase::generator()]
+///
+/// The RNG handle and the entropy buffer are set up once and reused for every keyphrase.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::generator()]: ../keyphrase/struct.KeyPhrase.html#method.generator
+pub struct KeyPhraseGenerator {
+    rng: DefaultRng,
+    entropy: Vec<u8>,
+    lang: Language,
+}
+
+impl Iterator for KeyPhraseGenerator {
+    type Item = KeyPhrase;
+
+    fn next(&mut self) -> Option<KeyPhrase> {
+        self.rng.fill_bytes(&mut self.entropy);
+
+        Some(KeyPhrase::from_entropy_unchecked(
+            self.entropy.as_slice(),
+            self.lang,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl fmt::Debug for KeyPhraseGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyPhraseGenerator")
+            .field("entropy_len", &self.entropy.len())
+            .field("lang", &self.lang)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPhraseGenerator {
+    fn drop(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+impl KeyPhrase {
+    /// Create an infinite iterator of random [`KeyPhrase`][KeyPhrase]s
+    ///
+    /// Meant for batch tooling generating many keyphrases in a row, without paying the setup
+    /// cost of [`KeyPhrase::new()`][KeyPhrase::new()] for every one of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrases: Vec<KeyPhrase> = KeyPhrase::generator(KeyPhraseType::Words12, Language::English)
+    ///     .take(10)
+    ///     .collect();
+    ///
+    /// assert_eq!(keyphrases.len(), 10);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::new()]: ../keyphrase/struct.KeyPhrase.html#method.new
+    pub fn generator(keyphrase_type: KeyPhraseType, lang: Language) -> KeyPhraseGenerator {
+        KeyPhraseGenerator {
+            rng: DefaultRng,
+            entropy: vec![0; keyphrase_type.entropy_bits() / 8],
+            lang,
+        }
+    }
+
+    /// Generate `n` random [`KeyPhrase`][KeyPhrase]s using all the cores
+    ///
+    /// Every worker thread draws from its own
+    /// [`KeyPhrase::generator()`][KeyPhrase::generator()], so the threads don't contend on a
+    /// shared RNG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrases = KeyPhrase::generate_batch_parallel(1000, KeyPhraseType::Words12, Language::English);
+    ///
+    /// assert_eq!(keyphrases.len(), 1000);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::generator()]: ../keyphrase/struct.KeyPhrase.html#method.generator
+    #[cfg(feature = "parallel")]
+    pub fn generate_batch_parallel(
+        n: usize,
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Vec<KeyPhrase> {
+        (0..n)
+            .into_par_iter()
+            .map_init(
+                || KeyPhrase::generator(keyphrase_type, lang),
+                |generator: &mut KeyPhraseGenerator, _| generator.next().unwrap(),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generator_distinct() {
+        let keyphrases: Vec<KeyPhrase> =
+            KeyPhrase::generator(KeyPhraseType::Words15, Language::English)
+                .take(3)
+                .collect();
+
+        assert_eq!(keyphrases[0].phrase().split(' ').count(), 15);
+        assert_ne!(keyphrases[0].phrase(), keyphrases[1].phrase());
+        assert_ne!(keyphrases[1].phrase(), keyphrases[2].phrase());
+        assert!(KeyPhrase::validate(keyphrases[2].phrase(), Language::English).is_ok());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn generate_batch_parallel_len() {
+        let keyphrases: Vec<KeyPhrase> =
+            KeyPhrase::generate_batch_parallel(100, KeyPhraseType::Words18, Language::English);
+
+        assert_eq!(keyphrases.len(), 100);
+        assert!(keyphrases.iter().all(|keyphrase: &KeyPhrase| keyphrase
+            .phrase()
+            .split(' ')
+            .count()
+            == 18));
+    }
+}
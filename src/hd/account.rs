@@ -0,0 +1,211 @@
+//! Watch-only export of BIP32 accounts as xpubs and output descriptors.
+
+use super::bip32::{ExtendedPrivKey, ExtendedPubKey};
+use super::path::{ChildNumber, DerivationPath};
+use crate::error::Error;
+use crate::seed::Seed;
+use std::fmt::Write;
+
+/// Characters allowed in a descriptor, in the order used by its checksum
+const DESCRIPTOR_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Characters of a descriptor checksum
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Generator of the BCH code of descriptor checksums
+const CHECKSUM_GENERATOR: [u64; 5] = [
+    0xf5_dee5_1989,
+    0xa9_fdca_3312,
+    0x1b_ab10_e32d,
+    0x37_06b1_677a,
+    0x64_4d62_6ffd,
+];
+
+/// The public side of a BIP32 account, with where it comes from
+///
+/// It holds no private key, so it can be given to a coordinator or a watch-only wallet to
+/// receive funds and track the balance, while the [`Seed`][Seed] stays offline.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::hd::Account;
+/// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+/// let seed = Seed::new_bip39(&keyphrase, "");
+///
+/// let account = Account::bip84(&seed, 0).unwrap();
+///
+/// assert!(account.xpub().to_xpub().starts_with("xpub"));
+/// assert!(account.descriptor(false).starts_with("wpkh(["));
+/// ```
+///
+/// [Seed]: ../seed/struct.Seed.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    master_fingerprint: [u8; 4],
+    path: DerivationPath,
+    xpub: ExtendedPubKey,
+}
+
+impl Account {
+    /// Derive the account at `path` from the master key of a [`Seed`][Seed]
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn from_seed(seed: &Seed, path: &DerivationPath) -> Result<Account, Error> {
+        let master: ExtendedPrivKey = ExtendedPrivKey::from_seed(seed)?;
+
+        Ok(Account {
+            master_fingerprint: master.fingerprint(),
+            path: path.clone(),
+            xpub: master.derive_path(path)?.extended_pub_key(),
+        })
+    }
+
+    /// Derive the BIP84 native segwit account `m/84'/0'/index'` of a [`Seed`][Seed]
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildNumber` if `index` is 2^31 or more.
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn bip84(seed: &Seed, index: u32) -> Result<Account, Error> {
+        let path: DerivationPath = DerivationPath::from(vec![
+            ChildNumber::Hardened(84),
+            ChildNumber::Hardened(0),
+            ChildNumber::Hardened(index),
+        ]);
+
+        Account::from_seed(seed, &path)
+    }
+
+    /// Get the extended public key of the account
+    pub fn xpub(&self) -> &ExtendedPubKey {
+        &self.xpub
+    }
+
+    /// Get the fingerprint of the master key the account was derived from
+    pub fn master_fingerprint(&self) -> [u8; 4] {
+        self.master_fingerprint
+    }
+
+    /// Get the derivation path of the account
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Get the `wpkh()` output descriptor of the receive addresses, or of the change addresses
+    /// if `change` is true
+    ///
+    /// The descriptor includes the key origin and ends with its checksum, like
+    /// `wpkh([73c5da0a/84'/0'/0']xpub…/0/*)#wc3n3van`, which Bitcoin Core's `importdescriptors`
+    /// and most coordinators accept as is.
+    pub fn descriptor(&self, change: bool) -> String {
+        let mut descriptor: String = String::from("wpkh([");
+
+        self.master_fingerprint
+            .iter()
+            .for_each(|byte: &u8| write!(descriptor, "{:02x}", byte).unwrap());
+        self.path
+            .children()
+            .iter()
+            .for_each(|child_number: &ChildNumber| {
+                write!(descriptor, "/{}", child_number).unwrap()
+            });
+        write!(
+            descriptor,
+            "]{}/{}/*)",
+            self.xpub.to_xpub(),
+            if change { 1 } else { 0 }
+        )
+        .unwrap();
+
+        let checksum: String = descriptor_checksum(&descriptor);
+
+        descriptor + "#" + &checksum
+    }
+}
+
+/// Compute the BIP380 checksum of a descriptor made of characters of `DESCRIPTOR_CHARSET`
+fn descriptor_checksum(descriptor: &str) -> String {
+    let mut symbols: Vec<u64> = Vec::with_capacity(descriptor.len() * 4 / 3 + 9);
+    let mut groups: Vec<u64> = Vec::with_capacity(3);
+
+    for character in descriptor.chars() {
+        let position: u64 = DESCRIPTOR_CHARSET
+            .find(character)
+            .expect("descriptors are built from valid characters")
+            as u64;
+
+        symbols.push(position & 31);
+        groups.push(position >> 5);
+
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    }
+
+    symbols.extend_from_slice(&[0; 8]);
+
+    let checksum: u64 = symbols.iter().fold(1, |checksum: u64, &symbol: &u64| {
+        let top: u64 = checksum >> 35;
+
+        CHECKSUM_GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|&(i, _): &(usize, &u64)| (top >> i) & 1 == 1)
+            .fold(
+                (checksum & 0x7_ffff_ffff) << 5 ^ symbol,
+                |checksum: u64, (_, generator): (usize, &u64)| checksum ^ generator,
+            )
+    }) ^ 1;
+
+    (0..8)
+        .map(|i: u64| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase::KeyPhrase;
+    use crate::language::Language;
+
+    #[test]
+    fn account_bip84_descriptors() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+        let seed: Seed = Seed::new_bip39(&keyphrase, "");
+        let account: Account = Account::bip84(&seed, 0).unwrap();
+
+        assert_eq!(account.master_fingerprint(), [0x73, 0xc5, 0xda, 0x0a]);
+        assert_eq!(account.path().to_string(), "m/84'/0'/0'");
+        assert_eq!(
+            account.xpub().to_xpub(),
+            "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V"
+        );
+        assert_eq!(
+            account.descriptor(false),
+            "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van"
+        );
+        assert_eq!(
+            account.descriptor(true),
+            "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/1/*)#lv5jvedt"
+        );
+        assert!(Account::bip84(&seed, 1 << 31).is_err());
+    }
+
+    #[test]
+    fn descriptor_checksum_bip380() {
+        assert_eq!(descriptor_checksum("raw(deadbeef)"), "89f8spxm");
+    }
+}
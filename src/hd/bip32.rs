@@ -0,0 +1,616 @@
+//! BIP32 secp256k1 extended keys, enabled by the `bip32` feature.
+
+use super::path::{ChildNumber, DerivationPath};
+use crate::crypto::{hash160, hmac_sha512, sha256};
+use crate::error::{Error, ErrorKind};
+use crate::seed::Seed;
+use secp256k1::Secp256k1;
+pub use secp256k1::{PublicKey, SecretKey};
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Version bytes of a mainnet extended private key, serialized as `xprv…`
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// Version bytes of a mainnet extended public key, serialized as `xpub…`
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// Length of a serialized extended key, without the checksum
+const EXTENDED_KEY_BYTES: usize = 78;
+
+/// BIP32 extended private key: a secp256k1 private key and the chain code to derive its
+/// children
+///
+/// The master key is created from a [`Seed`][Seed] with
+/// [`ExtendedPrivKey::from_seed()`][ExtendedPrivKey::from_seed()].
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::hd::{ChildNumber, ExtendedPrivKey};
+/// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+/// let seed = Seed::new_bip39(&keyphrase, "");
+///
+/// let master = ExtendedPrivKey::from_seed(&seed).unwrap();
+/// let account = master.derive_child(ChildNumber::Hardened(0)).unwrap();
+///
+/// assert!(account.to_xprv().starts_with("xprv"));
+/// assert!(account.extended_pub_key().to_xpub().starts_with("xpub"));
+/// ```
+///
+/// [Seed]: ../seed/struct.Seed.html
+/// [ExtendedPrivKey::from_seed()]: ./struct.ExtendedPrivKey.html#method.from_seed
+#[derive(Clone)]
+pub struct ExtendedPrivKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    secret_key: SecretKey,
+}
+
+/// BIP32 extended public key: a secp256k1 public key and the chain code to derive its normal
+/// children
+///
+/// It can't sign anything, share it with watch-only wallets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPubKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPrivKey {
+    /// Create the master key of a [`Seed`][Seed]
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidDerivedKey` for the seeds, with a
+    /// probability lower than 1 in 2^127, that have no valid master key.
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn from_seed(seed: &Seed) -> Result<ExtendedPrivKey, Error> {
+        #[allow(unused_mut)]
+        let mut output: [u8; 64] = hmac_sha512(b"Bitcoin seed", seed.as_bytes());
+
+        let key: Result<ExtendedPrivKey, Error> = split(&output).map(
+            |(secret_key, chain_code): (SecretKey, [u8; 32])| ExtendedPrivKey {
+                depth: 0,
+                parent_fingerprint: [0; 4],
+                child_number: ChildNumber::Normal(0),
+                chain_code,
+                secret_key,
+            },
+        );
+
+        #[cfg(feature = "zeroize")]
+        output.zeroize();
+
+        key
+    }
+
+    /// Derive a child key, hardened or normal
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildNumber` if the index is 2^31 or more,
+    /// or `ErrorKind::InvalidDerivedKey` for the indexes, with a probability lower than 1 in
+    /// 2^127, that give no valid key.
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<ExtendedPrivKey, Error> {
+        let index: u32 = child_number.to_u32()?;
+
+        #[allow(unused_mut)]
+        let mut data: Vec<u8> = Vec::with_capacity(37);
+
+        if child_number.is_hardened() {
+            data.push(0);
+            data.extend_from_slice(&self.secret_key[..]);
+        } else {
+            data.extend_from_slice(&self.public_key().serialize());
+        }
+
+        data.extend_from_slice(&index.to_be_bytes());
+
+        #[allow(unused_mut)]
+        let mut output: [u8; 64] = hmac_sha512(&self.chain_code, &data);
+
+        let mut secret_key: SecretKey = self.secret_key;
+        let tweaked: Result<(), Error> = secret_key
+            .add_assign(&output[..32])
+            .map_err(|_| ErrorKind::InvalidDerivedKey.into());
+
+        let mut chain_code: [u8; 32] = [0; 32];
+        chain_code.copy_from_slice(&output[32..]);
+
+        #[cfg(feature = "zeroize")]
+        {
+            data.zeroize();
+            output.zeroize();
+        }
+
+        tweaked?;
+
+        Ok(ExtendedPrivKey {
+            depth: self
+                .depth
+                .checked_add(1)
+                .ok_or(ErrorKind::InvalidDerivedKey)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    /// Derive the descendant at `path`, one child at a time
+    ///
+    /// The path is relative to this key, which is usually the master key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivKey, Error> {
+        path.children().iter().try_fold(
+            self.clone(),
+            |key: ExtendedPrivKey, &child_number: &ChildNumber| key.derive_child(child_number),
+        )
+    }
+
+    /// Get the extended public key, which can derive the public keys of the normal children
+    pub fn extended_pub_key(&self) -> ExtendedPubKey {
+        ExtendedPubKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.public_key(),
+        }
+    }
+
+    /// Get the private key
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    /// Get the public key
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_secret_key(&Secp256k1::new(), &self.secret_key)
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Get the fingerprint of the key, the first 4 bytes of the hash160 of its public key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.extended_pub_key().fingerprint()
+    }
+
+    /// Get the depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the fingerprint of the parent key, all zero for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Get the index of the key in its parent
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Serialize the key as an `xprv…` string
+    ///
+    /// The string gives access to all the funds of the key and its children, never log it.
+    pub fn to_xprv(&self) -> String {
+        let mut key: [u8; 33] = [0; 33];
+        key[1..].copy_from_slice(&self.secret_key[..]);
+
+        let encoded: String = encode(
+            XPRV_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+
+        #[cfg(feature = "zeroize")]
+        key.zeroize();
+
+        encoded
+    }
+
+    /// Parse a key previously serialized as an `xprv…` string
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not a valid
+    /// mainnet extended private key.
+    pub fn from_xprv(xprv: &str) -> Result<ExtendedPrivKey, Error> {
+        #[allow(unused_mut)]
+        let mut decoded: Decoded = decode(xprv, XPRV_VERSION)?;
+
+        let secret_key: Result<SecretKey, Error> = if decoded.key[0] == 0 {
+            SecretKey::from_slice(&decoded.key[1..])
+                .map_err(|_| ErrorKind::InvalidEncoding("invalid private key").into())
+        } else {
+            Err(ErrorKind::InvalidEncoding("invalid private key").into())
+        };
+
+        #[cfg(feature = "zeroize")]
+        decoded.key.zeroize();
+
+        Ok(ExtendedPrivKey {
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            secret_key: secret_key?,
+        })
+    }
+}
+
+/// Only the position of the key is printed, not the key itself
+impl fmt::Debug for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivKey")
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+impl ExtendedPubKey {
+    /// Derive a normal child public key
+    ///
+    /// Returns an `Error` of kind `ErrorKind::HardenedFromPublic` for hardened children,
+    /// `ErrorKind::InvalidChildNumber` if the index is 2^31 or more, or
+    /// `ErrorKind::InvalidDerivedKey` for the indexes, with a probability lower than 1 in
+    /// 2^127, that give no valid key.
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<ExtendedPubKey, Error> {
+        if child_number.is_hardened() {
+            Err(ErrorKind::HardenedFromPublic)?;
+        }
+
+        let index: u32 = child_number.to_u32()?;
+
+        let mut data: Vec<u8> = Vec::with_capacity(37);
+        data.extend_from_slice(&self.public_key.serialize());
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let output: [u8; 64] = hmac_sha512(&self.chain_code, &data);
+
+        let mut public_key: PublicKey = self.public_key;
+        public_key
+            .add_exp_assign(&Secp256k1::new(), &output[..32])
+            .map_err(|_| ErrorKind::InvalidDerivedKey)?;
+
+        let mut chain_code: [u8; 32] = [0; 32];
+        chain_code.copy_from_slice(&output[32..]);
+
+        Ok(ExtendedPubKey {
+            depth: self
+                .depth
+                .checked_add(1)
+                .ok_or(ErrorKind::InvalidDerivedKey)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+            chain_code,
+            public_key,
+        })
+    }
+
+    /// Derive the descendant at `path`, which must only contain normal children
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPubKey, Error> {
+        path.children().iter().try_fold(
+            self.clone(),
+            |key: ExtendedPubKey, &child_number: &ChildNumber| key.derive_child(child_number),
+        )
+    }
+
+    /// Get the public key
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Get the fingerprint of the key, the first 4 bytes of the hash160 of its public key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint: [u8; 4] = [0; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key.serialize())[..4]);
+
+        fingerprint
+    }
+
+    /// Get the depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the fingerprint of the parent key, all zero for the master key
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Get the index of the key in its parent
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Serialize the key as an `xpub…` string
+    pub fn to_xpub(&self) -> String {
+        encode(
+            XPUB_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key.serialize(),
+        )
+    }
+
+    /// Parse a key previously serialized as an `xpub…` string
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not a valid
+    /// mainnet extended public key.
+    pub fn from_xpub(xpub: &str) -> Result<ExtendedPubKey, Error> {
+        let decoded: Decoded = decode(xpub, XPUB_VERSION)?;
+
+        let public_key: PublicKey = PublicKey::from_slice(&decoded.key)
+            .map_err(|_| ErrorKind::InvalidEncoding("invalid public key"))?;
+
+        Ok(ExtendedPubKey {
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            public_key,
+        })
+    }
+}
+
+impl fmt::Display for ExtendedPubKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_xpub())
+    }
+}
+
+/// Split the output of HMAC-SHA512 into a private key and a chain code
+fn split(output: &[u8; 64]) -> Result<(SecretKey, [u8; 32]), Error> {
+    let secret_key: SecretKey =
+        SecretKey::from_slice(&output[..32]).map_err(|_| ErrorKind::InvalidDerivedKey)?;
+
+    let mut chain_code: [u8; 32] = [0; 32];
+    chain_code.copy_from_slice(&output[32..]);
+
+    Ok((secret_key, chain_code))
+}
+
+/// Fields of a serialized extended key
+struct Decoded {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+/// Serialize an extended key as base58 with a double SHA256 checksum
+fn encode(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key: &[u8; 33],
+) -> String {
+    // A key can only have been derived with a valid index
+    let index: u32 = child_number.to_u32().unwrap_or_default();
+
+    let mut data: Vec<u8> = Vec::with_capacity(EXTENDED_KEY_BYTES + 4);
+    data.extend_from_slice(&version);
+    data.push(depth);
+    data.extend_from_slice(&parent_fingerprint);
+    data.extend_from_slice(&index.to_be_bytes());
+    data.extend_from_slice(chain_code);
+    data.extend_from_slice(key);
+
+    let checksum: Vec<u8> = sha256(&sha256(&data));
+    data.extend_from_slice(&checksum[..4]);
+
+    let encoded: String = bs58::encode(&data).into_string();
+
+    #[cfg(feature = "zeroize")]
+    data.zeroize();
+
+    encoded
+}
+
+/// Parse an extended key serialized with the given version bytes
+fn decode(encoded: &str, version: [u8; 4]) -> Result<Decoded, Error> {
+    #[allow(unused_mut)]
+    let mut data: Vec<u8> = bs58::decode(encoded)
+        .into_vec()
+        .map_err(|_| ErrorKind::InvalidEncoding("invalid base58"))?;
+
+    let decoded: Result<Decoded, Error> = decode_bytes(&data, version);
+
+    #[cfg(feature = "zeroize")]
+    data.zeroize();
+
+    decoded
+}
+
+/// Parse the bytes of an extended key, including its checksum
+fn decode_bytes(data: &[u8], version: [u8; 4]) -> Result<Decoded, Error> {
+    if data.len() != EXTENDED_KEY_BYTES + 4 {
+        Err(ErrorKind::InvalidEncoding("invalid extended key length"))?;
+    }
+
+    let (payload, checksum): (&[u8], &[u8]) = data.split_at(EXTENDED_KEY_BYTES);
+
+    if sha256(&sha256(payload))[..4] != *checksum {
+        Err(ErrorKind::InvalidEncoding("invalid extended key checksum"))?;
+    }
+
+    if payload[..4] != version {
+        Err(ErrorKind::InvalidEncoding(
+            "unexpected extended key version",
+        ))?;
+    }
+
+    let mut decoded: Decoded = Decoded {
+        depth: payload[4],
+        parent_fingerprint: [0; 4],
+        child_number: ChildNumber::Normal(0),
+        chain_code: [0; 32],
+        key: [0; 33],
+    };
+
+    decoded.parent_fingerprint.copy_from_slice(&payload[5..9]);
+    decoded.chain_code.copy_from_slice(&payload[13..45]);
+    decoded.key.copy_from_slice(&payload[45..]);
+
+    let mut index: [u8; 4] = [0; 4];
+    index.copy_from_slice(&payload[9..13]);
+    decoded.child_number = ChildNumber::from(u32::from_be_bytes(index));
+
+    if decoded.depth == 0
+        && (decoded.parent_fingerprint != [0; 4] || decoded.child_number != ChildNumber::Normal(0))
+    {
+        Err(ErrorKind::InvalidEncoding("master key with a parent"))?;
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// BIP32 test vector 2
+    const SEED: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+
+    #[test]
+    fn bip32_test_vector_2() {
+        let master: ExtendedPrivKey =
+            ExtendedPrivKey::from_seed(&Seed::from_hex(SEED).unwrap()).unwrap();
+
+        assert_eq!(master.to_xprv(), "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U");
+        assert_eq!(master.extended_pub_key().to_xpub(), "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB");
+
+        let child: ExtendedPrivKey = master.derive_child(ChildNumber::Normal(0)).unwrap();
+
+        assert_eq!(child.to_xprv(), "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt");
+        assert_eq!(
+            master
+                .extended_pub_key()
+                .derive_child(ChildNumber::Normal(0))
+                .unwrap(),
+            child.extended_pub_key()
+        );
+
+        let child: ExtendedPrivKey = child
+            .derive_child(ChildNumber::Hardened(2147483647))
+            .unwrap();
+
+        assert_eq!(child.to_xprv(), "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9");
+        assert_eq!(child.extended_pub_key().to_xpub(), "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a");
+
+        let child: ExtendedPrivKey = child
+            .derive_child(ChildNumber::Normal(1))
+            .unwrap()
+            .derive_child(ChildNumber::Hardened(2147483646))
+            .unwrap()
+            .derive_child(ChildNumber::Normal(2))
+            .unwrap();
+
+        assert_eq!(child.depth(), 5);
+        assert_eq!(child.to_xprv(), "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j");
+        assert_eq!(child.extended_pub_key().to_xpub(), "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt");
+    }
+
+    #[test]
+    fn extended_key_round_trip() {
+        let master: ExtendedPrivKey =
+            ExtendedPrivKey::from_seed(&Seed::from_hex(SEED).unwrap()).unwrap();
+        let child: ExtendedPrivKey = master.derive_child(ChildNumber::Hardened(7)).unwrap();
+
+        let parsed: ExtendedPrivKey = ExtendedPrivKey::from_xprv(&child.to_xprv()).unwrap();
+        assert_eq!(parsed.to_xprv(), child.to_xprv());
+        assert_eq!(parsed.child_number(), ChildNumber::Hardened(7));
+        assert_eq!(parsed.parent_fingerprint(), master.fingerprint());
+
+        let xpub: String = child.extended_pub_key().to_xpub();
+        assert_eq!(
+            ExtendedPubKey::from_xpub(&xpub).unwrap(),
+            child.extended_pub_key()
+        );
+
+        assert!(ExtendedPrivKey::from_xprv(&xpub).is_err());
+        assert!(ExtendedPubKey::from_xpub(&child.to_xprv()).is_err());
+        assert!(ExtendedPubKey::from_xpub(&xpub[..xpub.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn bip44_test_vectors() {
+        let seed: Seed = Seed::from_hex("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let master: ExtendedPrivKey = ExtendedPrivKey::from_seed(&seed).unwrap();
+
+        let account: ExtendedPrivKey = master.derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+
+        assert_eq!(account.to_xprv(), "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb");
+        assert_eq!(account.extended_pub_key().to_xpub(), "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj");
+
+        let path: DerivationPath = "m/0/0".parse().unwrap();
+        let address: ExtendedPubKey = account.extended_pub_key().derive_path(&path).unwrap();
+
+        assert_eq!(
+            address,
+            account.derive_path(&path).unwrap().extended_pub_key()
+        );
+        assert_eq!(address.depth(), 5);
+
+        // Public key of 1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA
+        let public_key: String = address
+            .public_key()
+            .serialize()
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect();
+
+        assert_eq!(
+            public_key,
+            "03aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e"
+        );
+
+        // Ethereum's first account
+        let ethereum: ExtendedPrivKey = master
+            .derive_path(&"m/44'/60'/0'/0/0".parse().unwrap())
+            .unwrap();
+        let secret_key: String = ethereum.secret_key()[..]
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect();
+
+        assert_eq!(
+            secret_key,
+            "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"
+        );
+    }
+
+    #[test]
+    fn child_number_limits() {
+        let master: ExtendedPrivKey =
+            ExtendedPrivKey::from_seed(&Seed::from_hex(SEED).unwrap()).unwrap();
+
+        assert!(master.derive_child(ChildNumber::Normal(1 << 31)).is_err());
+        assert!(master
+            .extended_pub_key()
+            .derive_child(ChildNumber::Hardened(0))
+            .is_err());
+    }
+}
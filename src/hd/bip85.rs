@@ -0,0 +1,180 @@
+//! BIP85 child keyphrases, derived from a BIP32 master key.
+
+use super::bip32::ExtendedPrivKey;
+use super::path::{ChildNumber, DerivationPath};
+use crate::crypto::hmac_sha512;
+use crate::error::Error;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Purpose of BIP85 derivation paths, "DRNG" on a phone keypad
+const BIP85_PURPOSE: u32 = 83_696_968;
+
+/// Application number of BIP39 keyphrases in BIP85
+const BIP39_APPLICATION: u32 = 39;
+
+impl ExtendedPrivKey {
+    /// Derive the BIP85 child [`KeyPhrase`][KeyPhrase] number `index` of this master key
+    ///
+    /// The child is derived at `m/83696968'/39'/{language}'/{words}'/{index}'`. It is
+    /// independent from the master: it can be handed out or lost without exposing the master,
+    /// and the master can recreate it at any time.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildNumber` if `index` is 2^31 or more.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn derive_bip85_phrase(
+        &self,
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+        index: u32,
+    ) -> Result<KeyPhrase, Error> {
+        let path: DerivationPath = DerivationPath::from(vec![
+            ChildNumber::Hardened(BIP85_PURPOSE),
+            ChildNumber::Hardened(BIP39_APPLICATION),
+            ChildNumber::Hardened(language_code(lang)),
+            ChildNumber::Hardened(keyphrase_type.word_count() as u32),
+            ChildNumber::Hardened(index),
+        ]);
+        let child: ExtendedPrivKey = self.derive_path(&path)?;
+
+        #[allow(unused_mut)]
+        let mut entropy: [u8; 64] = hmac_sha512(b"bip-entropy-from-k", &child.secret_key()[..]);
+        let keyphrase: Result<KeyPhrase, Error> =
+            KeyPhrase::from_entropy(&entropy[..keyphrase_type.entropy_bits() / 8], lang);
+
+        #[cfg(feature = "zeroize")]
+        entropy.zeroize();
+
+        keyphrase
+    }
+}
+
+impl KeyPhrase {
+    /// Derive the BIP85 child keyphrase number `index`, in the same language
+    ///
+    /// The master key is the one of the seed of this keyphrase without a password, so any BIP85
+    /// wallet restored from this keyphrase derives the same children. Use
+    /// [`ExtendedPrivKey::derive_bip85_phrase()`][ExtendedPrivKey::derive_bip85_phrase()] for
+    /// a seed protected by a password.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildNumber` if `index` is 2^31 or more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    /// let child = keyphrase.derive_child_phrase(0, KeyPhraseType::Words12).unwrap();
+    ///
+    /// let again = keyphrase.derive_child_phrase(0, KeyPhraseType::Words12).unwrap();
+    ///
+    /// assert_eq!(child.phrase().split(' ').count(), 12);
+    /// assert_eq!(child.phrase(), again.phrase());
+    /// ```
+    ///
+    /// [ExtendedPrivKey::derive_bip85_phrase()]: ../hd/struct.ExtendedPrivKey.html#method.derive_bip85_phrase
+    pub fn derive_child_phrase(
+        &self,
+        index: u32,
+        keyphrase_type: KeyPhraseType,
+    ) -> Result<KeyPhrase, Error> {
+        ExtendedPrivKey::from_seed(&Seed::new_bip39(self, ""))?.derive_bip85_phrase(
+            keyphrase_type,
+            self.language(),
+            index,
+        )
+    }
+}
+
+/// Get the BIP85 number of a language, the order of the BIP39 wordlists
+fn language_code(lang: Language) -> u32 {
+    match lang {
+        Language::English => 0,
+        #[cfg(feature = "japanese")]
+        Language::Japanese => 1,
+        #[cfg(feature = "korean")]
+        Language::Korean => 2,
+        #[cfg(feature = "spanish")]
+        Language::Spanish => 3,
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified => 4,
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional => 5,
+        #[cfg(feature = "french")]
+        Language::French => 6,
+        #[cfg(feature = "italian")]
+        Language::Italian => 7,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bip85_test_vectors() {
+        let master: ExtendedPrivKey = ExtendedPrivKey::from_xprv("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap();
+
+        let vectors: [(KeyPhraseType, &str, &str); 3] = [
+            (
+                KeyPhraseType::Words12,
+                "6250b68daf746d12a24d58b4787a714b",
+                "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+            ),
+            (
+                KeyPhraseType::Words18,
+                "938033ed8b12698449d4bbca3c853c66b293ea1b1ce9d9dc",
+                "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token",
+            ),
+            (
+                KeyPhraseType::Words24,
+                "ae131e2312cdc61331542efe0d1077bac5ea803adf24b313a4f0e48e9c51f37f",
+                "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+            ),
+        ];
+
+        vectors.iter().for_each(
+            |&(keyphrase_type, entropy, phrase): &(KeyPhraseType, &str, &str)| {
+                let child: KeyPhrase = master
+                    .derive_bip85_phrase(keyphrase_type, Language::English, 0)
+                    .unwrap();
+
+                assert_eq!(child.entropy_hex(), entropy);
+                assert_eq!(child.phrase(), phrase);
+            },
+        );
+    }
+
+    #[test]
+    fn keyphrase_derive_child_phrase() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(
+            keyphrase
+                .derive_child_phrase(0, KeyPhraseType::Words12)
+                .unwrap()
+                .phrase(),
+            "prosper short ramp prepare exchange stove life snack client enough purpose fold"
+        );
+        assert_eq!(
+            keyphrase
+                .derive_child_phrase(1, KeyPhraseType::Words12)
+                .unwrap()
+                .phrase(),
+            "sing slogan bar group gauge sphere rescue fossil loyal vital model desert"
+        );
+        assert!(keyphrase
+            .derive_child_phrase(1 << 31, KeyPhraseType::Words12)
+            .is_err());
+    }
+}
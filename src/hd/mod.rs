@@ -0,0 +1,22 @@
+//! Hierarchical deterministic keys derived from a [`Seed`][Seed]: BIP32 secp256k1 keys with the
+//! `bip32` feature, and SLIP-0010 ed25519 keys with the `ed25519` feature.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+
+#[cfg(feature = "bip32")]
+mod account;
+#[cfg(feature = "bip32")]
+mod bip32;
+#[cfg(feature = "bip32")]
+mod bip85;
+mod path;
+#[cfg(feature = "ed25519")]
+mod slip10;
+
+#[cfg(feature = "bip32")]
+pub use self::account::Account;
+#[cfg(feature = "bip32")]
+pub use self::bip32::{ExtendedPrivKey, ExtendedPubKey, PublicKey, SecretKey};
+pub use self::path::{ChildNumber, DerivationPath};
+#[cfg(feature = "ed25519")]
+pub use self::slip10::Ed25519Key;
@@ -0,0 +1,220 @@
+//! BIP32 derivation paths, shared by the secp256k1 and ed25519 derivations.
+
+use crate::error::{Error, ErrorKind};
+use std::fmt;
+use std::str::FromStr;
+
+/// Bit set in the index of hardened children
+const HARDENED_BIT: u32 = 1 << 31;
+
+/// Index of a child key
+///
+/// Hardened children can only be derived from a private key, and their public key doesn't
+/// reveal anything about their siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChildNumber {
+    /// Normal child, the index must be lower than 2^31
+    Normal(u32),
+    /// Hardened child, the index must be lower than 2^31
+    Hardened(u32),
+}
+
+impl ChildNumber {
+    /// Whether this is a hardened child
+    pub fn is_hardened(self) -> bool {
+        match self {
+            ChildNumber::Normal(_) => false,
+            ChildNumber::Hardened(_) => true,
+        }
+    }
+
+    /// Get the index as serialized by BIP32, with the high bit set for hardened children
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidChildNumber` if the index is 2^31 or more.
+    pub fn to_u32(self) -> Result<u32, Error> {
+        let (index, hardened): (u32, bool) = match self {
+            ChildNumber::Normal(index) => (index, false),
+            ChildNumber::Hardened(index) => (index, true),
+        };
+
+        if index >= HARDENED_BIT {
+            Err(ErrorKind::InvalidChildNumber(index))?;
+        }
+
+        Ok(if hardened {
+            index | HARDENED_BIT
+        } else {
+            index
+        })
+    }
+}
+
+/// Parse an index as serialized by BIP32
+impl From<u32> for ChildNumber {
+    fn from(index: u32) -> ChildNumber {
+        if index & HARDENED_BIT == 0 {
+            ChildNumber::Normal(index)
+        } else {
+            ChildNumber::Hardened(index & !HARDENED_BIT)
+        }
+    }
+}
+
+/// Hardened children are written with a `'` suffix, like in derivation paths
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChildNumber::Normal(index) => write!(f, "{}", index),
+            ChildNumber::Hardened(index) => write!(f, "{}'", index),
+        }
+    }
+}
+
+/// Path from a master key to one of its descendants, like `m/44'/0'/0'/0/0`
+///
+/// Hardened children are written with a `'`, `h` or `H` suffix.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::hd::{ChildNumber, DerivationPath};
+///
+/// let path: DerivationPath = "m/44'/60'/0'/0/0".parse().unwrap();
+///
+/// assert_eq!(path.children()[0], ChildNumber::Hardened(44));
+/// assert_eq!(path.to_string(), "m/44'/60'/0'/0/0");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath {
+    children: Vec<ChildNumber>,
+}
+
+impl DerivationPath {
+    /// Get the child numbers of the path, from the master key down
+    pub fn children(&self) -> &[ChildNumber] {
+        &self.children
+    }
+
+    /// Get the path to a child of the key at this path
+    pub fn child(&self, child_number: ChildNumber) -> DerivationPath {
+        let mut children: Vec<ChildNumber> = self.children.clone();
+        children.push(child_number);
+
+        DerivationPath { children }
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> DerivationPath {
+        DerivationPath { children }
+    }
+}
+
+impl AsRef<[ChildNumber]> for DerivationPath {
+    fn as_ref(&self) -> &[ChildNumber] {
+        self.children()
+    }
+}
+
+/// Parse a path starting with `m`
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidDerivationPath` if the path is malformed or an
+/// index is 2^31 or more.
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<DerivationPath, Error> {
+        let mut parts = path.split('/');
+
+        if parts.next() != Some("m") {
+            Err(ErrorKind::InvalidDerivationPath(path.to_string()))?;
+        }
+
+        let children: Vec<ChildNumber> = parts
+            .map(|part: &str| {
+                let (digits, hardened): (&str, bool) =
+                    match part.strip_suffix(&['\'', 'h', 'H'][..]) {
+                        Some(digits) => (digits, true),
+                        None => (part, false),
+                    };
+
+                let index: Option<u32> =
+                    if !digits.is_empty() && digits.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+                        digits
+                            .parse()
+                            .ok()
+                            .filter(|&index: &u32| index < HARDENED_BIT)
+                    } else {
+                        None
+                    };
+
+                match (index, hardened) {
+                    (Some(index), false) => Ok(ChildNumber::Normal(index)),
+                    (Some(index), true) => Ok(ChildNumber::Hardened(index)),
+                    (None, _) => Err(ErrorKind::InvalidDerivationPath(path.to_string())),
+                }
+            })
+            .collect::<Result<Vec<ChildNumber>, ErrorKind>>()?;
+
+        Ok(DerivationPath { children })
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+
+        for child_number in &self.children {
+            write!(f, "/{}", child_number)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn derivation_path_parse() {
+        let path: DerivationPath = "m/44h/0H/1'/2/3".parse().unwrap();
+
+        assert_eq!(
+            path.children(),
+            &[
+                ChildNumber::Hardened(44),
+                ChildNumber::Hardened(0),
+                ChildNumber::Hardened(1),
+                ChildNumber::Normal(2),
+                ChildNumber::Normal(3),
+            ][..]
+        );
+        assert_eq!(path.to_string(), "m/44'/0'/1'/2/3");
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::default()
+        );
+
+        for invalid in &[
+            "",
+            "44'/0'",
+            "m/",
+            "m//0",
+            "m/+1",
+            "m/1''",
+            "m/2147483648",
+            "m/x",
+        ] {
+            assert!(invalid.parse::<DerivationPath>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn child_number_u32() {
+        assert_eq!(ChildNumber::from(0x8000_0002), ChildNumber::Hardened(2));
+        assert_eq!(ChildNumber::Hardened(2).to_u32().unwrap(), 0x8000_0002);
+        assert!(ChildNumber::Normal(1 << 31).to_u32().is_err());
+        assert_eq!(ChildNumber::Hardened(44).to_string(), "44'");
+    }
+}
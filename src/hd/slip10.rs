@@ -0,0 +1,210 @@
+//! SLIP-0010 ed25519 keys, enabled by the `ed25519` feature.
+
+use super::path::{ChildNumber, DerivationPath};
+use crate::crypto::hmac_sha512;
+use crate::error::{Error, ErrorKind};
+use crate::seed::Seed;
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// SLIP-0010 ed25519 private key and the chain code to derive its children, as used by Solana
+/// and Stellar wallets
+///
+/// ed25519 keys only have hardened children. The private key is the 32 byte secret of an
+/// ed25519 signing key, give it to an ed25519 library to sign or to get the public key.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::hd::DerivationPath;
+/// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+/// let seed = Seed::new_bip39(&keyphrase, "");
+///
+/// let path: DerivationPath = "m/44'/501'/0'/0'".parse().unwrap();
+/// let key = seed.derive_ed25519(&path).unwrap();
+///
+/// assert_eq!(key.secret_key().len(), 32);
+/// ```
+#[derive(Clone)]
+pub struct Ed25519Key {
+    depth: u8,
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    secret_key: [u8; 32],
+}
+
+impl Ed25519Key {
+    /// Create the master key of a [`Seed`][Seed]
+    ///
+    /// [Seed]: ../seed/struct.Seed.html
+    pub fn from_seed(seed: &Seed) -> Ed25519Key {
+        Ed25519Key::from_output(
+            &hmac_sha512(b"ed25519 seed", seed.as_bytes()),
+            0,
+            ChildNumber::Hardened(0),
+        )
+    }
+
+    /// Derive a hardened child key
+    ///
+    /// Returns an `Error` of kind `ErrorKind::HardenedOnly` for normal children, or
+    /// `ErrorKind::InvalidChildNumber` if the index is 2^31 or more.
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<Ed25519Key, Error> {
+        if !child_number.is_hardened() {
+            Err(ErrorKind::HardenedOnly)?;
+        }
+
+        let index: u32 = child_number.to_u32()?;
+
+        #[allow(unused_mut)]
+        let mut data: Vec<u8> = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&self.secret_key);
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let depth: u8 = self
+            .depth
+            .checked_add(1)
+            .ok_or(ErrorKind::InvalidDerivedKey)?;
+        let key: Ed25519Key =
+            Ed25519Key::from_output(&hmac_sha512(&self.chain_code, &data), depth, child_number);
+
+        #[cfg(feature = "zeroize")]
+        data.zeroize();
+
+        Ok(key)
+    }
+
+    /// Derive the descendant at `path`, which must only contain hardened children
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Ed25519Key, Error> {
+        path.children().iter().try_fold(
+            self.clone(),
+            |key: Ed25519Key, &child_number: &ChildNumber| key.derive_child(child_number),
+        )
+    }
+
+    /// Get the private key
+    pub fn secret_key(&self) -> &[u8; 32] {
+        &self.secret_key
+    }
+
+    /// Get the chain code
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Get the depth of the key, 0 for the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Get the index of the key in its parent, meaningless for the master key
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Split the output of HMAC-SHA512 into a private key and a chain code, wiping it
+    #[allow(unused_mut)]
+    fn from_output(output: &[u8; 64], depth: u8, child_number: ChildNumber) -> Ed25519Key {
+        let mut output: [u8; 64] = *output;
+        let mut key: Ed25519Key = Ed25519Key {
+            depth,
+            child_number,
+            chain_code: [0; 32],
+            secret_key: [0; 32],
+        };
+
+        key.secret_key.copy_from_slice(&output[..32]);
+        key.chain_code.copy_from_slice(&output[32..]);
+
+        #[cfg(feature = "zeroize")]
+        output.zeroize();
+
+        key
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Ed25519Key {
+    fn drop(&mut self) {
+        self.secret_key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+/// Only the position of the key is printed, not the key itself
+impl fmt::Debug for Ed25519Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ed25519Key")
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect()
+    }
+
+    #[test]
+    fn slip10_test_vector_2() {
+        let seed: Seed = Seed::from_hex("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap();
+        let master: Ed25519Key = Ed25519Key::from_seed(&seed);
+
+        assert_eq!(
+            hex(&master.chain_code()),
+            "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b"
+        );
+        assert_eq!(
+            hex(master.secret_key()),
+            "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012"
+        );
+
+        let child: Ed25519Key = master.derive_child(ChildNumber::Hardened(0)).unwrap();
+
+        assert_eq!(
+            hex(&child.chain_code()),
+            "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d"
+        );
+        assert_eq!(
+            hex(child.secret_key()),
+            "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635"
+        );
+
+        let child: Ed25519Key = seed
+            .derive_ed25519(&"m/0'/2147483647'/1'/2147483646'/2'".parse().unwrap())
+            .unwrap();
+
+        assert_eq!(child.depth(), 5);
+        assert_eq!(
+            hex(&child.chain_code()),
+            "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4"
+        );
+        assert_eq!(
+            hex(child.secret_key()),
+            "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d"
+        );
+    }
+
+    #[test]
+    fn slip10_hardened_only() {
+        let seed: Seed = Seed::from_hex(&"00".repeat(64)).unwrap();
+
+        assert!(seed
+            .derive_ed25519(&"m/44'/501'/0'/0".parse().unwrap())
+            .is_err());
+        assert!(Ed25519Key::from_seed(&seed)
+            .derive_child(ChildNumber::Hardened(1 << 31))
+            .is_err());
+    }
+}
@@ -1,14 +1,24 @@
-use crate::crypto::{gen_random_bytes, sha256_first_byte};
-use crate::error::ErrorKind;
+use crate::checksum::{ChecksumScheme, Sha256Checksum};
+use crate::crypto::{constant_time_eq, gen_random_bytes_from, sha256, sha256_tagged};
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+use crate::crypto::{gen_random_bytes, mix_entropy, try_gen_random_bytes};
+use crate::error::{Error, ErrorKind};
 use crate::keyphrase_type::KeyPhraseType;
 use crate::language::{Language, WordList, WordMap};
-use crate::util::{checksum, BitWriter, Bits11, IterExt};
-use failure::Error;
+#[cfg(feature = "mlock")]
+use crate::locked::Locked;
+use crate::util::{checksum, decode_hex, Bit, BitWriter, Bits11, IterExt};
+use rand_core::{CryptoRng, RngCore};
+use std::convert::TryFrom;
 use std::fmt;
+use std::mem;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// Human readable backup phrases which contain most of the information needed to recreate your [EARTH](https://www.earth.engineering) addresses.
 ///
 /// To create a *new* [`KeyPhrase`][KeyPhrase] from a randomly generated key, call [`KeyPhrase::new()`][KeyPhrase::new()].
+/// For more control over the generation, use a [`KeyPhraseBuilder`][KeyPhraseBuilder].
 ///
 /// To get a [`KeyPhrase`][KeyPhrase] instance for an existing keyphrase, including
 /// those generated by other software or hardware wallets, use [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()].
@@ -23,24 +33,43 @@ use std::fmt;
 ///
 /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
 /// [KeyPhrase::new()]: ./keyphrase/struct.KeyPhrase.html#method.new
+/// [KeyPhraseBuilder]: ./keyphrase_builder/struct.KeyPhraseBuilder.html
 /// [KeyPhrase::from_phrase()]: ./keyphrase/struct.KeyPhrase.html#method.from_phrase
 /// [KeyPhrase::entropy()]: ./keyphrase/struct.KeyPhrase.html#method.entropy
 /// [Seed]: ./seed/struct.Seed.html
 /// [Seed::new()]: ./seed/struct.Seed.html#method.new
 /// [Seed::as_bytes()]: ./seed/struct.Seed.html#method.as_bytes
 ///
+/// With the `zeroize` feature enabled, both the phrase and the entropy are wiped from memory when
+/// the [`KeyPhrase`][KeyPhrase] is dropped. With the `mlock` feature enabled, the entropy is kept
+/// in memory locked out of swap.
+///
 #[derive(Clone)]
 pub struct KeyPhrase {
     phrase: String,
     lang: Language,
-    entropy: Vec<u8>,
+    entropy: Entropy,
 }
 
+/// The buffer of the entropy of a [`KeyPhrase`][KeyPhrase], locked in RAM with the `mlock` feature
+///
+/// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+#[cfg(feature = "mlock")]
+type Entropy = Locked<[u8]>;
+#[cfg(not(feature = "mlock"))]
+type Entropy = Vec<u8>;
+
+// The entropy is converted into an `Entropy` when stored, which is a no-op without `mlock`
+#[cfg_attr(not(feature = "mlock"), allow(clippy::useless_conversion))]
 impl KeyPhrase {
     /// Generates a new [`KeyPhrase`][KeyPhrase]
     ///
     /// Use [`KeyPhrase::phrase()`][KeyPhrase::phrase()] to get an `str` slice of the generated phrase.
     ///
+    /// The entropy is drawn from the [`DefaultRng`][DefaultRng], this panics if it fails. Only
+    /// compiled in with the `rand` or `getrandom` feature, as are the other functions drawing from
+    /// it.
+    ///
     /// # Example
     ///
     /// ```
@@ -56,12 +85,110 @@ impl KeyPhrase {
     ///
     /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
     /// [KeyPhrase::phrase()]: ./keyphrase/struct.KeyPhrase.html#method.phrase
+    /// [DefaultRng]: ./struct.DefaultRng.html
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
     pub fn new(keyphrase_type: KeyPhraseType, lang: Language) -> KeyPhrase {
         let entropy: Vec<u8> = gen_random_bytes(keyphrase_type.entropy_bits() / 8);
 
         KeyPhrase::from_entropy_unchecked(entropy, lang)
     }
 
+    /// Generates a new [`KeyPhrase`][KeyPhrase], returning an error if no entropy is available
+    ///
+    /// Unlike [`KeyPhrase::new()`][KeyPhrase::new()], which panics if the entropy source fails,
+    /// this returns an `Error` of kind `ErrorKind::EntropyUnavailable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::try_new(KeyPhraseType::Words12, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 12);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::new()]: ./keyphrase/struct.KeyPhrase.html#method.new
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    pub fn try_new(keyphrase_type: KeyPhraseType, lang: Language) -> Result<KeyPhrase, Error> {
+        let entropy: Vec<u8> = try_gen_random_bytes(keyphrase_type.entropy_bits() / 8)?;
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+
+    /// Generates a new [`KeyPhrase`][KeyPhrase] using the given RNG
+    ///
+    /// Use this instead of [`KeyPhrase::new()`][KeyPhrase::new()] to draw the entropy from a
+    /// specific source, such as a DRBG seeded from a hardware RNG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate rand;
+    ///
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    /// use rand::rngs::OsRng;
+    ///
+    /// let mut rng = OsRng::new().unwrap();
+    /// let keyphrase = KeyPhrase::new_with_rng(KeyPhraseType::Words12, Language::English, &mut rng);
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 12);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::new()]: ./keyphrase/struct.KeyPhrase.html#method.new
+    pub fn new_with_rng<R>(keyphrase_type: KeyPhraseType, lang: Language, rng: &mut R) -> KeyPhrase
+    where
+        R: RngCore + CryptoRng,
+    {
+        let entropy: Vec<u8> = gen_random_bytes_from(rng, keyphrase_type.entropy_bits() / 8);
+
+        KeyPhrase::from_entropy_unchecked(entropy, lang)
+    }
+
+    /// Generates a new [`KeyPhrase`][KeyPhrase] mixing user provided entropy with OS entropy
+    ///
+    /// The two sources are hashed together with SHA-256, so the keyphrase stays unpredictable as
+    /// long as either of them is. `user_entropy` can be of any length, such as keyboard input or
+    /// the bytes of dice rolls.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::EntropyUnavailable` if the OS entropy source fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let rolls = b"3264151523613645461325642352311244365615213653421516263425463154231662";
+    /// let keyphrase = KeyPhrase::new_mixed(rolls, KeyPhraseType::Words24, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 24);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    pub fn new_mixed(
+        user_entropy: &[u8],
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        let entropy_bytes: usize = keyphrase_type.entropy_bits() / 8;
+        #[allow(unused_mut)]
+        let mut os_entropy: Vec<u8> = try_gen_random_bytes(entropy_bytes)?;
+        let mut entropy: Vec<u8> = mix_entropy(&os_entropy, user_entropy);
+
+        #[cfg(feature = "zeroize")]
+        {
+            os_entropy.zeroize();
+            entropy[entropy_bytes..].zeroize();
+        }
+
+        entropy.truncate(entropy_bytes);
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+
     /// Create a [`KeyPhrase`][KeyPhrase] from pre-generated entropy
     ///
     /// # Example
@@ -84,14 +211,59 @@ impl KeyPhrase {
         Ok(Self::from_entropy_unchecked(entropy, lang))
     }
 
-    fn from_entropy_unchecked<E>(entropy: E, lang: Language) -> KeyPhrase
+    /// Create a [`KeyPhrase`][KeyPhrase] from pre-generated entropy, using a custom checksum
+    ///
+    /// This is meant for research and for chains that historically diverged from the standard
+    /// checksum, the resulting phrase is **not** compatible with BIP39 wallets unless the
+    /// [`Sha256Checksum`][Sha256Checksum] scheme is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Sha256Checksum};
+    ///
+    /// let entropy = &[0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84, 0x6A, 0x79];
+    /// let keyphrase = KeyPhrase::from_entropy_with_checksum(entropy, Language::English, &Sha256Checksum).unwrap();
+    ///
+    /// assert_eq!("crop cash unable insane eight faith inflict route frame loud box vibrant", keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [Sha256Checksum]: ../checksum/struct.Sha256Checksum.html
+    pub fn from_entropy_with_checksum<C>(
+        entropy: &[u8],
+        lang: Language,
+        scheme: &C,
+    ) -> Result<KeyPhrase, Error>
+    where
+        C: ChecksumScheme + ?Sized,
+    {
+        // Validate entropy size
+        KeyPhraseType::for_key_size(entropy.len() * 8)?;
+
+        Ok(Self::from_entropy_unchecked_with(entropy, lang, scheme))
+    }
+
+    pub(crate) fn from_entropy_unchecked<E>(entropy: E, lang: Language) -> KeyPhrase
+    where
+        E: Into<Vec<u8>>,
+    {
+        KeyPhrase::from_entropy_unchecked_with(entropy, lang, &Sha256Checksum)
+    }
+
+    pub(crate) fn from_entropy_unchecked_with<E, C>(
+        entropy: E,
+        lang: Language,
+        scheme: &C,
+    ) -> KeyPhrase
     where
         E: Into<Vec<u8>>,
+        C: ChecksumScheme + ?Sized,
     {
         let entropy: Vec<u8> = entropy.into();
         let wordlist: &WordList = lang.wordlist();
 
-        let checksum_byte: u8 = sha256_first_byte(&entropy);
+        let checksum_byte: u8 = scheme.checksum_byte(&entropy);
 
         // First, create a byte iterator for the given entropy and the first byte of the
         // hash of the entropy that will serve as the checksum (up to 8 bits for biggest
@@ -112,7 +284,18 @@ impl KeyPhrase {
         KeyPhrase {
             phrase,
             lang,
-            entropy,
+            entropy: entropy.into(),
+        }
+    }
+
+    /// Assemble a [`KeyPhrase`][KeyPhrase] from a phrase and entropy known to match
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub(crate) fn from_parts(phrase: String, lang: Language, entropy: Vec<u8>) -> KeyPhrase {
+        KeyPhrase {
+            phrase,
+            lang,
+            entropy: entropy.into(),
         }
     }
 
@@ -142,17 +325,43 @@ impl KeyPhrase {
         // this also validates the checksum and phrase length before returning the entropy so we
         // can store it. We don't use the validate function here to avoid having a public API that
         // takes a phrase string and returns the entropy directly.
-        let entropy: Vec<u8> = KeyPhrase::phrase_to_entropy(&phrase, lang)?;
+        let entropy: Vec<u8> = KeyPhrase::phrase_to_entropy(&phrase, lang, &Sha256Checksum)?;
 
         let keyphrase: KeyPhrase = KeyPhrase {
             phrase,
             lang,
-            entropy,
+            entropy: entropy.into(),
         };
 
         Ok(keyphrase)
     }
 
+    /// Create a [`KeyPhrase`][KeyPhrase] from an existing keyphrase, using a custom checksum
+    ///
+    /// The phrase supplied will be checked for word length and validated according to the given
+    /// checksum scheme. See [`KeyPhrase::from_entropy_with_checksum()`][KeyPhrase::from_entropy_with_checksum()].
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_entropy_with_checksum()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_with_checksum
+    pub fn from_phrase_with_checksum<S, C>(
+        phrase: S,
+        lang: Language,
+        scheme: &C,
+    ) -> Result<KeyPhrase, Error>
+    where
+        S: Into<String>,
+        C: ChecksumScheme + ?Sized,
+    {
+        let phrase: String = phrase.into();
+        let entropy: Vec<u8> = KeyPhrase::phrase_to_entropy(&phrase, lang, scheme)?;
+
+        Ok(KeyPhrase {
+            phrase,
+            lang,
+            entropy: entropy.into(),
+        })
+    }
+
     /// Validate a keyphrase
     ///
     /// The phrase supplied will be checked for word length and validated according to the checksum
@@ -168,26 +377,116 @@ impl KeyPhrase {
     /// assert!(KeyPhrase::validate(test_keyphrase, Language::English).is_ok());
     /// ```
     pub fn validate(phrase: &str, lang: Language) -> Result<(), Error> {
-        KeyPhrase::phrase_to_entropy(phrase, lang)?;
+        KeyPhrase::phrase_to_entropy(phrase, lang, &Sha256Checksum)?;
 
         Ok(())
     }
 
+    /// List every word that completes a partial keyphrase into a valid one
+    ///
+    /// The partial phrase must be one word short of a valid length, for example 11 or 23 words
+    /// chosen manually. The last word of a keyphrase carries both entropy and checksum bits, so
+    /// only some of the 2048 words produce a valid checksum: 128 words for a 12 word phrase, down
+    /// to 8 words for a 24 word phrase. They are returned in wordlist order.
+    ///
+    /// A partial phrase of any other length returns an `Error` of kind
+    /// `ErrorKind::InvalidWordLength`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let partial = "park remain person kitchen mule spell knee armed position rail grid";
+    ///
+    /// let words = KeyPhrase::valid_last_words(partial, Language::English).unwrap();
+    ///
+    /// assert_eq!(words.len(), 128);
+    /// assert!(words.contains(&"ankle"));
+    /// ```
+    pub fn valid_last_words(
+        partial_phrase: &str,
+        lang: Language,
+    ) -> Result<Vec<&'static str>, Error> {
+        let wordmap: &WordMap = lang.wordmap();
+        let wordlist: &WordList = lang.wordlist();
+
+        let word_count: usize = partial_phrase.split(' ').count();
+        let mtype: KeyPhraseType = KeyPhraseType::for_word_count(word_count + 1)
+            .map_err(|_| ErrorKind::InvalidWordLength {
+                words: word_count,
+                expected: "11, 14, 17, 20 or 23",
+            })?;
+
+        let mut bits = BitWriter::with_capacity(mtype.entropy_bits());
+
+        for (index, word) in partial_phrase.split(' ').enumerate() {
+            bits.push(wordmap.get_bits_at(word, index)?);
+        }
+
+        // The last word starts with the entropy bits left, followed by the checksum
+        let checksum_bits: u8 = mtype.checksum_bits();
+        let free_bits: usize = 11 - checksum_bits as usize;
+
+        let words: Vec<&'static str> = (0..1u16 << free_bits)
+            .map(|value: u16| {
+                let mut candidate: BitWriter = bits.clone();
+
+                for shift in (0..free_bits).rev() {
+                    candidate.push(Bit(value >> shift & 1 == 1));
+                }
+
+                let checksum_byte: u8 = Sha256Checksum.checksum_byte(&candidate.into_bytes());
+                let index: u16 =
+                    value << checksum_bits | u16::from(checksum(checksum_byte, checksum_bits));
+
+                wordlist.get_word(Bits11::from(index))
+            })
+            .collect();
+
+        Ok(words)
+    }
+
     /// Calculate the checksum, verify it and return the entropy
     ///
     /// Only intended for internal use, as returning a `Vec<u8>` that looks a bit like it could be
     /// used as the seed is likely to cause problems for someone eventually. All the other functions
     /// that return something like that are explicit about what it is and what to use it for.
-    fn phrase_to_entropy(phrase: &str, lang: Language) -> Result<Vec<u8>, Error> {
-        let wordmap: &WordMap = lang.wordmap();
+    pub(crate) fn phrase_to_entropy<C>(
+        phrase: &str,
+        lang: Language,
+        scheme: &C,
+    ) -> Result<Vec<u8>, Error>
+    where
+        C: ChecksumScheme + ?Sized,
+    {
+        KeyPhrase::words_to_entropy(phrase, lang.wordmap(), scheme)
+    }
 
+    /// Same as `phrase_to_entropy`, with the wordmap already looked up
+    pub(crate) fn words_to_entropy<C>(
+        phrase: &str,
+        wordmap: &WordMap,
+        scheme: &C,
+    ) -> Result<Vec<u8>, Error>
+    where
+        C: ChecksumScheme + ?Sized,
+    {
         // Preallocate enough space for the longest possible word list
         let mut bits = BitWriter::with_capacity(264);
 
-        for word in phrase.split(" ") {
-            bits.push(wordmap.get_bits(&word)?);
+        for (index, word) in phrase.split(" ").enumerate() {
+            bits.push(wordmap.get_bits_at(word, index)?);
         }
 
+        KeyPhrase::bits_to_entropy(bits, scheme)
+    }
+
+    /// Verify the checksum of the words packed into `bits` and return the entropy
+    pub(crate) fn bits_to_entropy<C>(bits: BitWriter, scheme: &C) -> Result<Vec<u8>, Error>
+    where
+        C: ChecksumScheme + ?Sized,
+    {
         let mtype: KeyPhraseType = KeyPhraseType::for_word_count(bits.len() / 11)?;
 
         debug_assert!(
@@ -200,19 +499,191 @@ impl KeyPhrase {
 
         let actual_checksum: u8 = checksum(entropy[entropy_bytes], mtype.checksum_bits());
 
+        // The checksum byte also carries the trailing entropy bits, wipe it before truncating
+        #[cfg(feature = "zeroize")]
+        entropy[entropy_bytes..].zeroize();
+
         // Truncate to get rid of the byte containing the checksum
         entropy.truncate(entropy_bytes);
 
-        let checksum_byte: u8 = sha256_first_byte(&entropy);
+        let checksum_byte: u8 = scheme.checksum_byte(&entropy);
         let expected_checksum: u8 = checksum(checksum_byte, mtype.checksum_bits());
 
         if actual_checksum != expected_checksum {
+            #[cfg(feature = "zeroize")]
+            entropy.zeroize();
+
             Err(ErrorKind::InvalidChecksum)?;
         }
 
         Ok(entropy)
     }
 
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy of a length not defined by BIP39
+    ///
+    /// **This is not part of the BIP39 standard.** It exists for research and for interop with
+    /// legacy wallets, use [`KeyPhrase::from_entropy()`][KeyPhrase::from_entropy()] otherwise.
+    ///
+    /// Any entropy length that is a multiple of 32 bits is accepted, up to 8192 bits. The
+    /// checksum is computed the same way as for the standard lengths: one bit per 32 bits of
+    /// entropy, taken from the start of the SHA256 hash of the entropy. A phrase therefore has
+    /// 3 words for every 32 bits of entropy, and the standard lengths produce standard phrases.
+    ///
+    /// Other lengths return an `Error` of kind `ErrorKind::InvalidKeysize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// // 96 bits of entropy
+    /// let entropy = &[0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C];
+    /// let keyphrase = KeyPhrase::from_entropy_nonstandard(entropy, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase().split(" ").count(), 9);
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_entropy()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy
+    pub fn from_entropy_nonstandard(entropy: &[u8], lang: Language) -> Result<KeyPhrase, Error> {
+        let entropy_bits: usize = entropy.len() * 8;
+
+        if entropy_bits == 0 || entropy_bits % 32 != 0 || entropy_bits > 8192 {
+            Err(ErrorKind::InvalidKeysize(entropy_bits))?;
+        }
+
+        let wordlist: &WordList = lang.wordlist();
+        let checksum_bits: usize = entropy_bits / 32;
+        let hash: Vec<u8> = sha256(entropy);
+
+        // Same as for the standard lengths, except that the checksum can span several bytes
+        let phrase: String = entropy
+            .iter()
+            .chain(&hash[..checksum_bits.div_ceil(8)])
+            .bits()
+            .take((entropy_bits + checksum_bits) / 11)
+            .map(|bits: Bits11| wordlist.get_word(bits))
+            .join(" ");
+
+        Ok(KeyPhrase {
+            phrase,
+            lang,
+            entropy: entropy.to_vec().into(),
+        })
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from an existing phrase of a length not defined by BIP39
+    ///
+    /// **This is not part of the BIP39 standard.** See
+    /// [`KeyPhrase::from_entropy_nonstandard()`][KeyPhrase::from_entropy_nonstandard()] for
+    /// the accepted lengths, the word count must be a multiple of 3.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "crop cash unable insane eight faith inflict route foster";
+    ///
+    /// assert!(KeyPhrase::from_phrase_nonstandard(phrase, Language::English).is_ok());
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_entropy_nonstandard()]: ../keyphrase/struct.KeyPhrase.html#method.from_entropy_nonstandard
+    pub fn from_phrase_nonstandard<S>(phrase: S, lang: Language) -> Result<KeyPhrase, Error>
+    where
+        S: Into<String>,
+    {
+        let phrase: String = phrase.into();
+        let wordmap: &WordMap = lang.wordmap();
+
+        let word_count: usize = phrase.split(' ').count();
+
+        if word_count % 3 != 0 || word_count > 768 {
+            Err(ErrorKind::InvalidWordLength {
+                words: word_count,
+                expected: "a multiple of 3 up to 768",
+            })?;
+        }
+
+        let mut bits = BitWriter::with_capacity(word_count * 11);
+
+        for (index, word) in phrase.split(' ').enumerate() {
+            bits.push(wordmap.get_bits_at(word, index)?);
+        }
+
+        let entropy_bytes: usize = word_count / 3 * 4;
+        let checksum_bits: usize = word_count / 3;
+
+        let mut entropy: Vec<u8> = bits.into_bytes();
+        let hash: Vec<u8> = sha256(&entropy[..entropy_bytes]);
+
+        let valid: bool = entropy[entropy_bytes..]
+            .iter()
+            .zip(hash.iter())
+            .enumerate()
+            .all(|(i, (actual, expected)): (usize, (&u8, &u8))| {
+                // Only compare the checksum bits of the last byte
+                let bits: usize = (checksum_bits - i * 8).min(8);
+
+                checksum(*actual, bits as u8) == checksum(*expected, bits as u8)
+            });
+
+        if !valid {
+            #[cfg(feature = "zeroize")]
+            entropy.zeroize();
+
+            Err(ErrorKind::InvalidChecksum)?;
+        }
+
+        // The checksum bytes are derived from the entropy, wipe them before truncating
+        #[cfg(feature = "zeroize")]
+        entropy[entropy_bytes..].zeroize();
+
+        entropy.truncate(entropy_bytes);
+
+        Ok(KeyPhrase {
+            phrase,
+            lang,
+            entropy: entropy.into(),
+        })
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from the wordlist indices of its words
+    ///
+    /// Indices outside of the wordlist return an `Error` of kind `ErrorKind::InvalidEncoding`,
+    /// the checksum is validated the same way as for a phrase.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub(crate) fn from_word_indices(indices: &[u16], lang: Language) -> Result<KeyPhrase, Error> {
+        let mut bits = BitWriter::with_capacity(264);
+
+        for &index in indices {
+            if index >= 2048 {
+                Err(ErrorKind::InvalidEncoding("word index out of range"))?;
+            }
+
+            bits.push(Bits11::from(index));
+        }
+
+        let entropy: Vec<u8> = KeyPhrase::bits_to_entropy(bits, &Sha256Checksum)?;
+
+        Ok(KeyPhrase::from_entropy_unchecked(entropy, lang))
+    }
+
+    /// Get the wordlist indices of the words of the keyphrase
+    pub(crate) fn word_indices(&self) -> Vec<u16> {
+        let wordmap: &WordMap = self.lang.wordmap();
+
+        // The phrase is known to be valid, and unlike the entropy it already carries the checksum
+        // bits, whichever scheme was used to compute them
+        self.phrase
+            .split(' ')
+            .filter_map(|word: &str| wordmap.get_bits(word).ok())
+            .map(|bits: Bits11| bits.into())
+            .collect()
+    }
+
     /// Get the keyphrase as a string reference.
     ///
     /// # Example
@@ -228,6 +699,33 @@ impl KeyPhrase {
         &self.phrase
     }
 
+    /// Get a `Debug` view of the keyphrase that prints the full phrase
+    ///
+    /// The `Debug` output of a keyphrase is redacted, this is the explicit way to see the phrase
+    /// while debugging locally. Never log it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "crop cash unable insane eight faith inflict route frame loud box vibrant";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(format!("{:?}", keyphrase.reveal()), format!("{:?}", phrase));
+    /// ```
+    pub fn reveal(&self) -> impl fmt::Debug + '_ {
+        struct Reveal<'a>(&'a KeyPhrase);
+
+        impl<'a> fmt::Debug for Reveal<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(self.0.phrase(), f)
+            }
+        }
+
+        Reveal(self)
+    }
+
     /// Consume the `KeyPhrase` and return the phrase as a `String`.
     ///
     /// This operation doesn't perform any allocations.
@@ -241,11 +739,18 @@ impl KeyPhrase {
     ///
     /// let phrase = keyphrase.into_phrase();
     /// ```
-    pub fn into_phrase(self) -> String {
-        self.phrase
+    pub fn into_phrase(mut self) -> String {
+        mem::take(&mut self.phrase)
     }
 
-    /// Get the original entropy value of the keyphrase as a slice.
+    /// Get the keyphrase with every word abbreviated to its first four letters
+    ///
+    /// This is the form steel backup plates usually store. Words shorter than four letters are
+    /// kept whole. In the English, Italian and Chinese wordlists the first four letters identify
+    /// a single word, which lets
+    /// [`KeyPhrase::from_abbreviated_phrase()`][KeyPhrase::from_abbreviated_phrase()] restore
+    /// the full phrase. Some words of the other wordlists share their first four letters, so
+    /// their abbreviations can't always be restored.
     ///
     /// # Example
     ///
@@ -253,53 +758,484 @@ impl KeyPhrase {
     /// use keyphrase::{KeyPhrase, Language};
     ///
     /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
-    ///
     /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
     ///
-    /// let entropy: &[u8] = keyphrase.entropy();
+    /// assert_eq!(keyphrase.abbreviated_phrase(), "park rema pers kitc mule spel knee arme posi rail grid ankl");
     /// ```
     ///
-    /// **Note:** You shouldn't use the generated entropy as secrets, for that generate a new
-    /// `Seed` from the `KeyPhrase`.
-    pub fn entropy(&self) -> &[u8] {
-        &self.entropy
+    /// [KeyPhrase::from_abbreviated_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_abbreviated_phrase
+    pub fn abbreviated_phrase(&self) -> String {
+        self.phrase.split(' ').map(abbreviate).join(" ")
     }
 
-    /// Get the [`Language`][Language]
+    /// Create a [`KeyPhrase`][KeyPhrase] from a phrase abbreviated to four letters per word
     ///
-    /// [Language]: ../language/struct.Language.html
+    /// Every abbreviation must match exactly one word of the wordlist, as produced by
+    /// [`KeyPhrase::abbreviated_phrase()`][KeyPhrase::abbreviated_phrase()], otherwise an
+    /// `Error` of kind `ErrorKind::InvalidWord` is returned. Full words are accepted too, as
+    /// long as they don't match several words. The checksum is validated the same way as for
+    /// [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()].
     ///
     /// # Example
     ///
     /// ```
     /// use keyphrase::{KeyPhrase, Language};
     ///
-    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
-    ///
-    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    /// let abbreviated = "park rema pers kitc mule spel knee arme posi rail grid ankl";
+    /// let keyphrase = KeyPhrase::from_abbreviated_phrase(abbreviated, Language::English).unwrap();
     ///
-    /// let lang = keyphrase.language();
+    /// assert_eq!(keyphrase.phrase(), "park remain person kitchen mule spell knee armed position rail grid ankle");
     /// ```
-    pub fn language(&self) -> Language {
-        self.lang
-    }
-}
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::abbreviated_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.abbreviated_phrase
+    /// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+    pub fn from_abbreviated_phrase(phrase: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        let words: &[&'static str] = lang.wordlist().words();
+        let mut indices: Vec<u16> = Vec::with_capacity(24);
 
-impl AsRef<str> for KeyPhrase {
+        for (index, abbreviation) in phrase.split(' ').enumerate() {
+            let abbreviation: &str = abbreviate(abbreviation);
+
+            let mut matches = words
+                .iter()
+                .enumerate()
+                .filter(|&(_, word): &(usize, &&str)| abbreviate(word) == abbreviation);
+
+            match (matches.next(), matches.next()) {
+                (Some((i, _)), None) => indices.push(i as u16),
+                _ => Err(ErrorKind::InvalidWord {
+                    word: abbreviation.to_string(),
+                    index,
+                    lang: Some(lang),
+                })?,
+            }
+        }
+
+        KeyPhrase::from_word_indices(&indices, lang)
+    }
+
+    /// Get the original entropy value of the keyphrase as a slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    ///
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let entropy: &[u8] = keyphrase.entropy();
+    /// ```
+    ///
+    /// **Note:** You shouldn't use the generated entropy as secrets, for that generate a new
+    /// `Seed` from the `KeyPhrase`.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Get the original entropy value of the keyphrase as a lowercase hex string, two digits
+    /// per byte
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    ///
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.entropy_hex(), "00000000000000000000000000000000");
+    /// ```
+    pub fn entropy_hex(&self) -> String {
+        format!("{:x}", self)
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from entropy previously written as hex
+    ///
+    /// Both cases and an optional `0x` prefix are accepted, every byte must be written with two
+    /// digits. Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not hex,
+    /// or `ErrorKind::InvalidKeysize` if the entropy isn't of a length defined by BIP39.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let keyphrase =
+    ///     KeyPhrase::from_entropy_hex("00000000000000000000000000000000", Language::English)
+    ///         .unwrap();
+    ///
+    /// assert!(keyphrase.phrase().ends_with("abandon about"));
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn from_entropy_hex(hex: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        #[allow(unused_mut)]
+        let mut entropy: Vec<u8> = decode_hex(hex)?;
+        let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&entropy, lang);
+
+        #[cfg(feature = "zeroize")]
+        entropy.zeroize();
+
+        keyphrase
+    }
+
+    /// Consume the `KeyPhrase` and return the original entropy value as a `Vec<u8>`.
+    ///
+    /// This operation doesn't perform any allocations. With the `zeroize` feature enabled, the
+    /// phrase is wiped from memory in the process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    ///
+    /// let entropy: Vec<u8> = keyphrase.into_entropy();
+    /// ```
+    ///
+    /// **Note:** You shouldn't use the generated entropy as secrets, for that generate a new
+    /// `Seed` from the `KeyPhrase`.
+    pub fn into_entropy(mut self) -> Vec<u8> {
+        mem::take(&mut self.entropy).into()
+    }
+
+    /// Get the [`Language`][Language]
+    ///
+    /// [Language]: ../language/struct.Language.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    ///
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let lang = keyphrase.language();
+    /// ```
+    pub fn language(&self) -> Language {
+        self.lang
+    }
+
+    /// Check that a phrase typed by the user matches a [`KeyPhrase`][KeyPhrase]
+    ///
+    /// Intended for confirm-your-backup screens. The candidate is normalized first: surrounding
+    /// and repeated whitespace is ignored, and the words are lowercased. It is then parsed in the
+    /// language of `expected`, and the entropy of both is compared in constant time, so that the
+    /// time taken doesn't reveal how much of the candidate was right. An invalid candidate
+    /// returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert!(KeyPhrase::verify_phrase(&keyphrase, " Park remain person kitchen mule spell knee armed position rail grid  ankle\n"));
+    /// assert!(!KeyPhrase::verify_phrase(&keyphrase, "park remain"));
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn verify_phrase(expected: &KeyPhrase, candidate: &str) -> bool {
+        let normalized: String = candidate
+            .split_whitespace()
+            .map(|word: &str| word.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let candidate: Result<KeyPhrase, Error> = KeyPhrase::from_phrase(normalized, expected.lang);
+
+        match candidate {
+            Ok(candidate) => constant_time_eq(&candidate.entropy, &expected.entropy),
+            Err(_) => false,
+        }
+    }
+
+    /// Generate a new [`KeyPhrase`][KeyPhrase] with the same words, except for the last one
+    ///
+    /// The last word holds the end of the entropy followed by the checksum. Only the entropy bits
+    /// it holds (7 bits for 12 words, down to 3 bits for 24 words, and up to 10 bits for the
+    /// shortest non-standard keyphrases) are drawn from `rng`, and the checksum is recomputed. The
+    /// new last word can be the same as the current one.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidKeysize` for non-standard keyphrases of 352
+    /// bits of entropy or more, whose last word only holds checksum bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate rand;
+    ///
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let rerolled = keyphrase.reroll_last_word(&mut rand::thread_rng()).unwrap();
+    ///
+    /// assert!(rerolled.phrase().starts_with("park remain person kitchen mule spell knee armed position rail grid "));
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn reroll_last_word<R>(&self, rng: &mut R) -> Result<KeyPhrase, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let entropy_bits: usize = self.entropy.len() * 8;
+        let checksum_bits: usize = entropy_bits / 32;
+
+        if checksum_bits >= 11 {
+            Err(ErrorKind::InvalidKeysize(entropy_bits))?;
+        }
+
+        // At most 10 bits, which always fit in the last two bytes of the entropy (4 at least)
+        let free_bits: usize = 11 - checksum_bits;
+        let mask: u16 = (1 << free_bits) - 1;
+
+        let mut entropy: Vec<u8> = self.entropy.to_vec();
+        let tail: usize = entropy.len() - 2;
+        let bits: u16 = u16::from_be_bytes([entropy[tail], entropy[tail + 1]]);
+        let rerolled: u16 = (bits & !mask) | (rng.next_u32() as u16 & mask);
+
+        entropy[tail..].copy_from_slice(&rerolled.to_be_bytes());
+
+        // Also covers the standard lengths, producing the same phrase as `from_entropy`
+        let keyphrase: Result<KeyPhrase, Error> =
+            KeyPhrase::from_entropy_nonstandard(&entropy, self.lang);
+
+        #[cfg(feature = "zeroize")]
+        entropy.zeroize();
+
+        keyphrase
+    }
+
+    /// Get a fingerprint identifying the keyphrase without revealing it
+    ///
+    /// The fingerprint is the first 4 bytes of a domain-separated SHA256 hash of the entropy,
+    /// as lowercase hex. It only depends on the entropy, so it is the same for a keyphrase in
+    /// every language, and it will not change between versions of this crate. It is meant to let
+    /// backends reference a backup, not to tell keyphrases apart securely: with 32 bits, two
+    /// different keyphrases can have the same fingerprint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.fingerprint(), "1e94dd22");
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        sha256_tagged("keyphrase-fingerprint", &self.entropy)[..4]
+            .iter()
+            .map(|byte: &u8| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// XOR the entropy of two keyphrases into a new [`KeyPhrase`][KeyPhrase]
+    ///
+    /// Useful for dual-custody setups: neither keyphrase alone reveals anything about the
+    /// result, and XOR-ing the result with one of them gives back the other. The checksum of the
+    /// new keyphrase is recomputed.
+    ///
+    /// Both keyphrases must have the same number of words, otherwise an `Error` of kind
+    /// `ErrorKind::WordCountMismatch` is returned, and the same language, otherwise an `Error`
+    /// of kind `ErrorKind::LanguageMismatch` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let first = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    /// let second = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    ///
+    /// let combined = first.xor(&second).unwrap();
+    ///
+    /// assert_eq!(combined.xor(&second).unwrap().phrase(), first.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn xor(&self, other: &KeyPhrase) -> Result<KeyPhrase, Error> {
+        if self.entropy.len() != other.entropy.len() {
+            Err(ErrorKind::WordCountMismatch(
+                self.phrase.split(' ').count(),
+                other.phrase.split(' ').count(),
+            ))?;
+        }
+
+        if self.lang != other.lang {
+            Err(ErrorKind::LanguageMismatch(self.lang, other.lang))?;
+        }
+
+        let entropy: Vec<u8> = self
+            .entropy
+            .iter()
+            .zip(other.entropy.iter())
+            .map(|(a, b): (&u8, &u8)| a ^ b)
+            .collect();
+
+        // Also covers the standard lengths, producing the same phrase as `from_entropy`
+        KeyPhrase::from_entropy_nonstandard(&entropy, self.lang)
+    }
+
+    /// Split the keyphrase into `n` shares, all of them are needed to get it back
+    ///
+    /// Every share is a valid keyphrase with the same number of words and language, so it can be
+    /// written down and checked like any other. Fewer than `n` shares tell nothing about the
+    /// keyphrase. Use [`KeyPhrase::combine_xor()`][KeyPhrase::combine_xor()] to get it back.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidSharing` if `n` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate rand;
+    ///
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    /// let shares = keyphrase.split_xor(3, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert_eq!(KeyPhrase::combine_xor(&shares).unwrap().phrase(), keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase::combine_xor()]: ../keyphrase/struct.KeyPhrase.html#method.combine_xor
+    pub fn split_xor<R>(&self, n: usize, rng: &mut R) -> Result<Vec<KeyPhrase>, Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        if n == 0 {
+            Err(ErrorKind::InvalidSharing("at least one share is needed"))?;
+        }
+
+        let mut shares: Vec<KeyPhrase> = (1..n)
+            .map(|_| {
+                #[allow(unused_mut)]
+                let mut entropy: Vec<u8> = gen_random_bytes_from(rng, self.entropy.len());
+                let share: Result<KeyPhrase, Error> =
+                    KeyPhrase::from_entropy_nonstandard(&entropy, self.lang);
+
+                #[cfg(feature = "zeroize")]
+                entropy.zeroize();
+
+                share
+            })
+            .collect::<Result<Vec<KeyPhrase>, Error>>()?;
+
+        let last: KeyPhrase = shares
+            .iter()
+            .try_fold(self.clone(), |last: KeyPhrase, share: &KeyPhrase| {
+                last.xor(share)
+            })?;
+        shares.push(last);
+
+        Ok(shares)
+    }
+
+    /// Get back a keyphrase from all the shares made by
+    /// [`KeyPhrase::split_xor()`][KeyPhrase::split_xor()], in any order
+    ///
+    /// Missing shares can't be detected: the result is then another valid keyphrase. Returns an
+    /// `Error` of kind `ErrorKind::InvalidShare` if `shares` is empty, or the errors of
+    /// [`KeyPhrase::xor()`][KeyPhrase::xor()] if the shares have different lengths or languages.
+    ///
+    /// [KeyPhrase::split_xor()]: ../keyphrase/struct.KeyPhrase.html#method.split_xor
+    /// [KeyPhrase::xor()]: ../keyphrase/struct.KeyPhrase.html#method.xor
+    pub fn combine_xor(shares: &[KeyPhrase]) -> Result<KeyPhrase, Error> {
+        let (first, rest): (&KeyPhrase, &[KeyPhrase]) = shares
+            .split_first()
+            .ok_or(ErrorKind::InvalidShare("no shares given"))?;
+
+        rest.iter()
+            .try_fold(first.clone(), |combined: KeyPhrase, share: &KeyPhrase| {
+                combined.xor(share)
+            })
+    }
+}
+
+/// The first four characters of a word, or the whole word if it's shorter
+pub(crate) fn abbreviate(word: &str) -> &str {
+    match word.char_indices().nth(4) {
+        Some((end, _)) => &word[..end],
+        None => word,
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPhrase {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+        self.entropy.zeroize();
+    }
+}
+
+impl AsRef<str> for KeyPhrase {
     fn as_ref(&self) -> &str {
         self.phrase()
     }
 }
 
+/// Displays the phrase, or with the alternate flag (`{:#}`) one numbered word per line, as on a
+/// recovery sheet:
+///
+/// ```text
+///  1. park
+///  2. remain
+/// ...
+/// 12. ankle
+/// ```
 impl fmt::Display for KeyPhrase {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self.phrase(), f)
+        if !f.alternate() {
+            return fmt::Display::fmt(self.phrase(), f);
+        }
+
+        let word_count: usize = self.phrase.split(' ').count();
+        let width: usize = word_count.to_string().len();
+
+        for (i, word) in self.phrase.split(' ').enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+
+            write!(f, "{:>width$}. {}", i + 1, word, width = width)?;
+        }
+
+        Ok(())
     }
 }
 
+/// The phrase is redacted from the `Debug` output, so that it doesn't end up in logs or panic
+/// messages. Only the word count, the language and a short non-secret hash of the entropy are
+/// printed, use [`KeyPhrase::reveal()`][KeyPhrase::reveal()] to print the phrase itself.
+///
+/// [KeyPhrase::reveal()]: ./keyphrase/struct.KeyPhrase.html#method.reveal
 impl fmt::Debug for KeyPhrase {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self.phrase(), f)
+        let hash: Vec<u8> = sha256_tagged("keyphrase-debug", self.entropy());
+
+        f.debug_struct("KeyPhrase")
+            .field("words", &self.phrase.split(' ').count())
+            .field("language", &self.lang)
+            .field(
+                "hash",
+                &format_args!(
+                    "{:02x}{:02x}{:02x}{:02x}",
+                    hash[0], hash[1], hash[2], hash[3]
+                ),
+            )
+            .finish()
     }
 }
 
@@ -310,7 +1246,7 @@ impl fmt::LowerHex for KeyPhrase {
         }
 
         for byte in self.entropy() {
-            write!(f, "{:x}", byte)?;
+            write!(f, "{:02x}", byte)?;
         }
 
         Ok(())
@@ -324,7 +1260,7 @@ impl fmt::UpperHex for KeyPhrase {
         }
 
         for byte in self.entropy() {
-            write!(f, "{:X}", byte)?;
+            write!(f, "{:02X}", byte)?;
         }
 
         Ok(())
@@ -337,11 +1273,89 @@ impl From<KeyPhrase> for String {
     }
 }
 
+/// Create a [`KeyPhrase`][KeyPhrase] from entropy, in the default [`Language`][Language]
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Language]: ../language/enum.Language.html
+impl<'a> TryFrom<&'a [u8]> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from(entropy: &'a [u8]) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(entropy, Language::default())
+    }
+}
+
+/// Create a [`KeyPhrase`][KeyPhrase] from entropy, in the given [`Language`][Language]
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Language]: ../language/enum.Language.html
+impl<'a> TryFrom<(&'a [u8], Language)> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from((entropy, lang): (&'a [u8], Language)) -> Result<KeyPhrase, Error> {
+        KeyPhrase::from_entropy(entropy, lang)
+    }
+}
+
+/// Create a [`KeyPhrase`][KeyPhrase] from entropy, in the default [`Language`][Language]
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Language]: ../language/enum.Language.html
+impl TryFrom<Vec<u8>> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from(entropy: Vec<u8>) -> Result<KeyPhrase, Error> {
+        KeyPhrase::try_from(entropy.as_slice())
+    }
+}
+
+/// Create a [`KeyPhrase`][KeyPhrase] from an existing phrase
+///
+/// The [`Language`][Language] is detected from the words: the phrase is parsed, checksum
+/// included, in every enabled language. If it is valid in several of them, such as a phrase made
+/// of words in both the English and French wordlists, an `Error` of kind
+/// `ErrorKind::AmbiguousLanguage` is returned. If it is valid in none, the error is the one of
+/// the default language.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Language]: ../language/enum.Language.html
+impl<'a> TryFrom<&'a str> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from(phrase: &'a str) -> Result<KeyPhrase, Error> {
+        let mut parsed: Vec<KeyPhrase> = Language::detection_order()
+            .filter_map(|lang: Language| KeyPhrase::from_phrase(phrase, lang).ok())
+            .collect();
+
+        match parsed.len() {
+            0 => KeyPhrase::from_phrase(phrase, Language::default()),
+            1 => Ok(parsed.remove(0)),
+            _ => Err(ErrorKind::AmbiguousLanguage(
+                parsed.iter().map(KeyPhrase::language).collect(),
+            ))?,
+        }
+    }
+}
+
+/// Create a [`KeyPhrase`][KeyPhrase] from an existing phrase, detecting the
+/// [`Language`][Language] the same way as `TryFrom<&str>`
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Language]: ../language/enum.Language.html
+impl TryFrom<String> for KeyPhrase {
+    type Error = Error;
+
+    fn try_from(phrase: String) -> Result<KeyPhrase, Error> {
+        KeyPhrase::try_from(phrase.as_str())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
     fn back_to_back() {
         let m1: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
         let m2: KeyPhrase = KeyPhrase::from_phrase(m1.phrase(), Language::English).unwrap();
@@ -353,6 +1367,283 @@ mod test {
         assert_eq!(m1.phrase(), m3.phrase(), "Phrase must be the same");
     }
 
+    #[test]
+    fn keyphrase_new_with_rng() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let m1: KeyPhrase =
+            KeyPhrase::new_with_rng(KeyPhraseType::Words24, Language::English, &mut rng);
+        let m2: KeyPhrase =
+            KeyPhrase::new_with_rng(KeyPhraseType::Words24, Language::English, &mut rng);
+
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let m3: KeyPhrase =
+            KeyPhrase::new_with_rng(KeyPhraseType::Words24, Language::English, &mut rng);
+
+        assert_eq!(m1.phrase().split(' ').count(), 24);
+        assert_ne!(m1.phrase(), m2.phrase());
+        assert_eq!(m1.phrase(), m3.phrase());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_new_mixed() {
+        let user_entropy: &[u8] = &[0; 16];
+
+        let m1: KeyPhrase =
+            KeyPhrase::new_mixed(user_entropy, KeyPhraseType::Words12, Language::English).unwrap();
+        let m2: KeyPhrase =
+            KeyPhrase::new_mixed(user_entropy, KeyPhraseType::Words12, Language::English).unwrap();
+
+        assert_eq!(m1.entropy().len(), 16);
+        assert_ne!(m1.entropy(), user_entropy);
+        assert_ne!(m1.phrase(), m2.phrase());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_xor() {
+        let first: KeyPhrase = KeyPhrase::from_phrase(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap();
+
+        let zero: KeyPhrase = first.xor(&first).unwrap();
+
+        assert_eq!(zero.entropy(), &[0; 16][..]);
+        assert_eq!(first.xor(&zero).unwrap().phrase(), first.phrase());
+
+        let second: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+        let combined: KeyPhrase = first.xor(&second).unwrap();
+
+        assert!(KeyPhrase::validate(combined.phrase(), Language::English).is_ok());
+        assert_eq!(combined.xor(&second).unwrap().phrase(), first.phrase());
+
+        let longer: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+
+        assert!(first.xor(&longer).is_err());
+    }
+
+    #[test]
+    fn keyphrase_split_xor() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap();
+
+        let mut shares: Vec<KeyPhrase> = keyphrase.split_xor(3, &mut rng).unwrap();
+
+        assert_eq!(shares.len(), 3);
+        shares.iter().for_each(|share: &KeyPhrase| {
+            assert!(KeyPhrase::validate(share.phrase(), Language::English).is_ok());
+            assert_ne!(share.phrase(), keyphrase.phrase());
+        });
+
+        shares.reverse();
+
+        assert_eq!(
+            KeyPhrase::combine_xor(&shares).unwrap().phrase(),
+            keyphrase.phrase()
+        );
+        assert_ne!(
+            KeyPhrase::combine_xor(&shares[1..]).unwrap().phrase(),
+            keyphrase.phrase()
+        );
+        assert_eq!(
+            keyphrase.split_xor(1, &mut rng).unwrap()[0].phrase(),
+            keyphrase.phrase()
+        );
+        assert!(keyphrase.split_xor(0, &mut rng).is_err());
+        assert!(KeyPhrase::combine_xor(&[]).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_valid_last_words() {
+        let partial: &str = "park remain person kitchen mule spell knee armed position rail grid";
+
+        let words: Vec<&str> = KeyPhrase::valid_last_words(partial, Language::English).unwrap();
+
+        assert_eq!(words.len(), 128);
+
+        for word in &words {
+            let phrase: String = format!("{} {}", partial, word);
+
+            assert!(KeyPhrase::validate(&phrase, Language::English).is_ok());
+        }
+
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+        let (partial, last): (&str, &str) = keyphrase.phrase().rsplit_once(' ').unwrap();
+
+        let words: Vec<&str> = KeyPhrase::valid_last_words(partial, Language::English).unwrap();
+
+        assert_eq!(words.len(), 8);
+        assert!(words.contains(&last));
+
+        assert!(KeyPhrase::valid_last_words(keyphrase.phrase(), Language::English).is_err());
+    }
+
+    #[test]
+    fn keyphrase_invalid_word() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed positoin rail grid ankle";
+
+        let error: Error = KeyPhrase::from_phrase(phrase, Language::English).unwrap_err();
+
+        match error.kind() {
+            ErrorKind::InvalidWord { word, index, lang } => {
+                assert_eq!(word, "positoin");
+                assert_eq!(*index, 8);
+                assert_eq!(*lang, Some(Language::English));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "french")]
+    #[test]
+    fn keyphrase_invalid_normalization() {
+        // The French wordlist is NFKD, "acad\u{e9}mie" is written with a precomposed "é"
+        let error: Error = KeyPhrase::from_phrase("acad\u{e9}mie", Language::French).unwrap_err();
+
+        match error.kind() {
+            ErrorKind::InvalidNormalization { index, .. } => assert_eq!(*index, 0),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_abbreviated_phrase() {
+        let languages: Vec<Language> = Language::all()
+            .filter(|lang: &Language| {
+                let words: &[&str] = lang.wordlist().words();
+                let mut abbreviations: Vec<&str> =
+                    words.iter().map(|word| abbreviate(word)).collect();
+
+                abbreviations.sort();
+                abbreviations.dedup();
+                abbreviations.len() == words.len()
+            })
+            .collect();
+
+        assert!(languages.contains(&Language::English));
+
+        for lang in languages {
+            let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, lang);
+            let abbreviated: String = keyphrase.abbreviated_phrase();
+            let restored: KeyPhrase =
+                KeyPhrase::from_abbreviated_phrase(&abbreviated, lang).unwrap();
+
+            assert_eq!(keyphrase.phrase(), restored.phrase());
+        }
+
+        assert!(KeyPhrase::from_abbreviated_phrase(
+            "park rema pers kitc mule spel knee arme posi rail grid park",
+            Language::English
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_reroll_last_word() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+
+        for &ty in &[KeyPhraseType::Words12, KeyPhraseType::Words24] {
+            let keyphrase: KeyPhrase = KeyPhrase::new(ty, Language::English);
+            let (partial, _): (&str, &str) = keyphrase.phrase().rsplit_once(' ').unwrap();
+            let last_words: Vec<&str> =
+                KeyPhrase::valid_last_words(partial, Language::English).unwrap();
+
+            for _ in 0..10 {
+                let rerolled: KeyPhrase = keyphrase.reroll_last_word(&mut rng).unwrap();
+                let (rerolled_partial, last): (&str, &str) =
+                    rerolled.phrase().rsplit_once(' ').unwrap();
+
+                assert_eq!(partial, rerolled_partial);
+                assert!(last_words.contains(&last));
+            }
+        }
+
+        // The 10 entropy bits of the last word of 32 bits of entropy span its last 2 bytes
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy_nonstandard(&[0; 4], Language::English).unwrap();
+        let rerolled: Vec<KeyPhrase> = (0..20)
+            .map(|_| keyphrase.reroll_last_word(&mut rng).unwrap())
+            .collect();
+
+        assert!(rerolled
+            .iter()
+            .all(|rerolled: &KeyPhrase| rerolled.entropy()[..2] == [0, 0]
+                && rerolled.entropy()[2] & 0xfc == 0));
+        assert!(rerolled
+            .iter()
+            .any(|rerolled: &KeyPhrase| rerolled.entropy()[2] != 0));
+
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy_nonstandard(&[0; 44], Language::English).unwrap();
+
+        assert!(keyphrase.reroll_last_word(&mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_verify_phrase() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        assert!(KeyPhrase::verify_phrase(&keyphrase, phrase));
+        assert!(KeyPhrase::verify_phrase(
+            &keyphrase,
+            "PARK remain\tperson kitchen mule spell knee armed position rail grid ankle "
+        ));
+
+        let other: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+
+        assert!(!KeyPhrase::verify_phrase(&keyphrase, other.phrase()));
+        assert!(!KeyPhrase::verify_phrase(
+            &keyphrase,
+            "park remain person kitchen mule spell knee armed position rail grid"
+        ));
+    }
+
+    #[test]
+    fn keyphrase_fingerprint() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        assert_eq!(keyphrase.fingerprint(), "1e94dd22");
+
+        for lang in Language::all() {
+            let other: KeyPhrase = KeyPhrase::from_entropy(keyphrase.entropy(), lang).unwrap();
+
+            assert_eq!(other.fingerprint(), keyphrase.fingerprint());
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn keyphrase_into_entropy() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words18, Language::English);
+        let entropy: Vec<u8> = keyphrase.entropy().to_vec();
+
+        assert_eq!(keyphrase.into_entropy(), entropy);
+    }
+
     #[test]
     fn keyphrase_from_entropy() {
         let entropy: &[u8; 16] = &[
@@ -382,12 +1673,167 @@ mod test {
     }
 
     #[test]
+    fn keyphrase_nonstandard() {
+        for &bytes in &[4, 12, 16, 32, 36, 64, 1024] {
+            let entropy: Vec<u8> = (0..bytes).map(|i| (i * 37) as u8).collect();
+
+            let m1: KeyPhrase =
+                KeyPhrase::from_entropy_nonstandard(&entropy, Language::English).unwrap();
+            let m2: KeyPhrase =
+                KeyPhrase::from_phrase_nonstandard(m1.phrase(), Language::English).unwrap();
+
+            assert_eq!(m1.phrase().split(' ').count(), bytes / 4 * 3);
+            assert_eq!(entropy, m2.entropy());
+
+            if bytes % 4 == 0 && KeyPhraseType::for_key_size(bytes * 8).is_ok() {
+                let m3: KeyPhrase = KeyPhrase::from_entropy(&entropy, Language::English).unwrap();
+
+                assert_eq!(m1.phrase(), m3.phrase());
+            }
+        }
+
+        assert!(KeyPhrase::from_entropy_nonstandard(&[], Language::English).is_err());
+        assert!(KeyPhrase::from_entropy_nonstandard(&[0; 6], Language::English).is_err());
+        assert!(KeyPhrase::from_phrase_nonstandard(
+            "crop cash unable insane eight faith inflict route fresh",
+            Language::English
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn keyphrase_custom_checksum() {
+        struct ZeroChecksum;
+
+        impl ChecksumScheme for ZeroChecksum {
+            fn checksum_byte(&self, _: &[u8]) -> u8 {
+                0
+            }
+        }
+
+        let entropy: &[u8; 16] = &[
+            0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84,
+            0x6A, 0x79,
+        ];
+
+        let m1: KeyPhrase =
+            KeyPhrase::from_entropy_with_checksum(entropy, Language::English, &ZeroChecksum)
+                .unwrap();
+        let m2: KeyPhrase =
+            KeyPhrase::from_phrase_with_checksum(m1.phrase(), Language::English, &ZeroChecksum)
+                .unwrap();
+
+        assert_eq!(
+            "crop cash unable insane eight faith inflict route frame loud box velvet",
+            m1.phrase()
+        );
+        assert_eq!(entropy, m2.entropy());
+        assert!(KeyPhrase::from_phrase(m1.phrase(), Language::English).is_err());
+    }
+
+    #[test]
+    fn keyphrase_try_from() {
+        let entropy: &[u8] = &[
+            0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84,
+            0x6A, 0x79,
+        ];
+        let phrase: &str =
+            "crop cash unable insane eight faith inflict route frame loud box vibrant";
+
+        let m1: KeyPhrase = KeyPhrase::try_from(entropy).unwrap();
+        let m2: KeyPhrase = KeyPhrase::try_from((entropy, Language::English)).unwrap();
+        let m3: KeyPhrase = KeyPhrase::try_from(phrase).unwrap();
+
+        assert_eq!(phrase, m1.phrase());
+        assert_eq!(phrase, m2.phrase());
+        assert_eq!(entropy, m3.entropy());
+
+        assert!(KeyPhrase::try_from(&entropy[1..]).is_err());
+        assert!(KeyPhrase::try_from("crop cash unable").is_err());
+
+        let m4: KeyPhrase = KeyPhrase::try_from(entropy.to_vec()).unwrap();
+        let m5: KeyPhrase = KeyPhrase::try_from(phrase.to_string()).unwrap();
+
+        assert_eq!(phrase, m4.phrase());
+        assert_eq!(entropy, m5.entropy());
+    }
+
+    #[test]
+    #[cfg(feature = "spanish")]
+    fn keyphrase_try_from_detects_language() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::Spanish);
+
+        let detected: KeyPhrase = KeyPhrase::try_from(keyphrase.phrase()).unwrap();
+
+        assert_eq!(detected.language(), Language::Spanish);
+        assert_eq!(detected.entropy(), keyphrase.entropy());
+
+        // every word is in the English and French wordlists, the checksum only matches in French
+        let phrase: &str =
+            "machine intact mobile dragon fortune client figure valve piano essence sentence nation";
+
+        assert!(KeyPhrase::from_phrase(phrase, Language::English).is_err());
+        assert_eq!(
+            KeyPhrase::try_from(phrase.to_string()).unwrap().language(),
+            Language::French
+        );
+
+        // and here it matches in both
+        let phrase: &str =
+            "civil festival festival palace rival concert distance panda junior unique spatial science";
+
+        match KeyPhrase::try_from(phrase).unwrap_err().kind() {
+            ErrorKind::AmbiguousLanguage(langs) => {
+                assert_eq!(langs, &[Language::English, Language::French])
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        // the error of the default language when no language matches
+        match KeyPhrase::try_from("abandon abandon").unwrap_err().kind() {
+            ErrorKind::InvalidWordLength { .. } => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
     fn keyphrase_format() {
         let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words15, Language::English);
 
         assert_eq!(keyphrase.phrase(), format!("{}", keyphrase));
     }
 
+    #[test]
+    fn keyphrase_debug_format() {
+        let phrase: &str =
+            "crop cash unable insane eight faith inflict route frame loud box vibrant";
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+        let debug: String = format!("{:?}", keyphrase);
+
+        assert!(debug.starts_with("KeyPhrase { words: 12, language: English, hash: "));
+        assert!(phrase.split(' ').all(|word: &str| !debug.contains(word)));
+
+        assert_eq!(format!("{:?}", keyphrase.reveal()), format!("{:?}", phrase));
+    }
+
+    #[test]
+    fn keyphrase_numbered_format() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        assert_eq!(format!("{}", keyphrase), phrase);
+
+        let numbered: String = format!("{:#}", keyphrase);
+        let lines: Vec<&str> = numbered.lines().collect();
+
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0], " 1. park");
+        assert_eq!(lines[11], "12. ankle");
+    }
+
     #[test]
     fn keyphrase_hex_format() {
         let entropy: &[u8; 16] = &[
@@ -414,4 +1860,34 @@ mod test {
             "0x33E46BB13A746EA41CDDE45C90846A79"
         );
     }
+
+    #[test]
+    fn keyphrase_hex_low_bytes() {
+        let entropy: &[u8; 16] = &[
+            0x00, 0x01, 0x0A, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84,
+            0x6A, 0x0F,
+        ];
+
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(entropy, Language::English).unwrap();
+
+        assert_eq!(keyphrase.entropy_hex(), "00010ab13a746ea41cdde45c90846a0f");
+        assert_eq!(
+            format!("{:X}", keyphrase),
+            "00010AB13A746EA41CDDE45C90846A0F"
+        );
+        assert_eq!(
+            KeyPhrase::from_entropy_hex(&keyphrase.entropy_hex(), Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+        assert_eq!(
+            KeyPhrase::from_entropy_hex("0X00010AB13A746EA41CDDE45C90846A0F", Language::English)
+                .unwrap()
+                .entropy(),
+            entropy
+        );
+        assert!(KeyPhrase::from_entropy_hex("00010ab", Language::English).is_err());
+        assert!(KeyPhrase::from_entropy_hex("0001", Language::English).is_err());
+    }
 }
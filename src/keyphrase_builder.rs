@@ -0,0 +1,330 @@
+use crate::checksum::{ChecksumScheme, Sha256Checksum};
+use crate::crypto::{try_gen_random_bytes, try_gen_random_bytes_from, DefaultRng};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use rand_core::{CryptoRng, RngCore};
+
+/// Configurable generation of a [`KeyPhrase`][KeyPhrase]
+///
+/// [`KeyPhrase::new()`][KeyPhrase::new()] remains the simple path for generating a random
+/// [`KeyPhrase`][KeyPhrase]. The builder is for when you need to pick the RNG or the checksum
+/// scheme, or supply the entropy yourself.
+///
+/// Unless specified otherwise, a 12 word English keyphrase is generated from entropy read directly
+/// from the OS. A crate built without the `rand` and `getrandom` features has no access to it, so
+/// [`build()`][build()] is only available once the builder is given an RNG.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{KeyPhraseBuilder, Language};
+///
+/// let keyphrase = KeyPhraseBuilder::new()
+///     .word_count(24)
+///     .language(Language::English)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(keyphrase.phrase().split(" ").count(), 24);
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [KeyPhrase::new()]: ../keyphrase/struct.KeyPhrase.html#method.new
+/// [build()]: ./struct.KeyPhraseBuilder.html#method.build
+pub struct KeyPhraseBuilder<R = DefaultRng, C = Sha256Checksum> {
+    word_count: Option<usize>,
+    lang: Language,
+    rng: Option<R>,
+    entropy: Option<Vec<u8>>,
+    checksum: C,
+}
+
+impl KeyPhraseBuilder<DefaultRng, Sha256Checksum> {
+    /// Create a new builder with the default settings
+    pub fn new() -> Self {
+        KeyPhraseBuilder {
+            word_count: None,
+            lang: Language::default(),
+            rng: None,
+            entropy: None,
+            checksum: Sha256Checksum,
+        }
+    }
+}
+
+impl Default for KeyPhraseBuilder<DefaultRng, Sha256Checksum> {
+    fn default() -> Self {
+        KeyPhraseBuilder::new()
+    }
+}
+
+impl<R, C> KeyPhraseBuilder<R, C> {
+    /// Set the number of words in the phrase
+    ///
+    /// A word count not provided for by the BIP39 standard will make [`build()`][build()] return
+    /// an `Error` of kind `ErrorKind::InvalidWordLength`.
+    ///
+    /// [build()]: ./struct.KeyPhraseBuilder.html#method.build
+    pub fn word_count(mut self, word_count: usize) -> Self {
+        self.word_count = Some(word_count);
+        self
+    }
+
+    /// Set the [`Language`][Language] of the phrase
+    ///
+    /// [Language]: ../language/enum.Language.html
+    pub fn language(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Use the given RNG to generate the entropy
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate rand;
+    ///
+    /// use keyphrase::KeyPhraseBuilder;
+    /// use rand::rngs::OsRng;
+    ///
+    /// let keyphrase = KeyPhraseBuilder::new()
+    ///     .rng(OsRng::new().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn rng<T>(self, rng: T) -> KeyPhraseBuilder<T, C>
+    where
+        T: RngCore + CryptoRng,
+    {
+        KeyPhraseBuilder {
+            word_count: self.word_count,
+            lang: self.lang,
+            rng: Some(rng),
+            entropy: self.entropy,
+            checksum: self.checksum,
+        }
+    }
+
+    /// Compute the checksum of the phrase with the given scheme instead of
+    /// [`Sha256Checksum`][Sha256Checksum]
+    ///
+    /// The phrase is **not** compatible with BIP39 wallets unless the scheme is the standard one,
+    /// it has to be read back with
+    /// [`KeyPhrase::from_phrase_with_checksum()`][KeyPhrase::from_phrase_with_checksum()] and the
+    /// same scheme.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{ChecksumScheme, KeyPhrase, KeyPhraseBuilder, Language};
+    ///
+    /// struct XorChecksum;
+    ///
+    /// impl ChecksumScheme for XorChecksum {
+    ///     fn checksum_byte(&self, entropy: &[u8]) -> u8 {
+    ///         entropy.iter().fold(0, |acc, byte| acc ^ byte)
+    ///     }
+    /// }
+    ///
+    /// let keyphrase = KeyPhraseBuilder::new()
+    ///     .checksum(XorChecksum)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(KeyPhrase::from_phrase_with_checksum(keyphrase.phrase(), Language::English, &XorChecksum).is_ok());
+    /// ```
+    ///
+    /// [Sha256Checksum]: ../checksum/struct.Sha256Checksum.html
+    /// [KeyPhrase::from_phrase_with_checksum()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase_with_checksum
+    pub fn checksum<S>(self, scheme: S) -> KeyPhraseBuilder<R, S>
+    where
+        S: ChecksumScheme,
+    {
+        KeyPhraseBuilder {
+            word_count: self.word_count,
+            lang: self.lang,
+            rng: self.rng,
+            entropy: self.entropy,
+            checksum: scheme,
+        }
+    }
+
+    /// Use pre-generated entropy instead of generating it
+    ///
+    /// If a word count is set as well, the entropy length must match it, otherwise
+    /// [`build()`][build()] returns an `Error` of kind `ErrorKind::InvalidEntropyLength`.
+    ///
+    /// [build()]: ./struct.KeyPhraseBuilder.html#method.build
+    pub fn entropy(mut self, entropy: &[u8]) -> Self {
+        self.entropy = Some(entropy.to_vec());
+        self
+    }
+}
+
+impl<R, C> KeyPhraseBuilder<R, C>
+where
+    R: RngCore + CryptoRng,
+    C: ChecksumScheme,
+{
+    /// Generate the [`KeyPhrase`][KeyPhrase]
+    ///
+    /// If the RNG given with [`rng()`][rng()] fails, an `Error` of kind
+    /// `ErrorKind::EntropySourceFailed` is returned with its message, and if the OS entropy source
+    /// fails, an `Error` of kind `ErrorKind::EntropyUnavailable`.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [rng()]: ./struct.KeyPhraseBuilder.html#method.rng
+    pub fn build(self) -> Result<KeyPhrase, Error> {
+        let keyphrase_type: KeyPhraseType = match (self.word_count, &self.entropy) {
+            (Some(word_count), _) => KeyPhraseType::for_word_count(word_count)?,
+            (None, Some(entropy)) => KeyPhraseType::for_key_size(entropy.len() * 8)?,
+            (None, None) => KeyPhraseType::default(),
+        };
+        let entropy_bytes: usize = keyphrase_type.entropy_bits() / 8;
+
+        let entropy: Vec<u8> = match (self.entropy, self.rng) {
+            (Some(entropy), _) => {
+                if entropy.len() != entropy_bytes {
+                    Err(ErrorKind::InvalidEntropyLength(
+                        entropy.len() * 8,
+                        keyphrase_type,
+                    ))?;
+                }
+
+                entropy
+            }
+            (None, Some(mut rng)) => try_gen_random_bytes_from(&mut rng, entropy_bytes)?,
+            (None, None) => try_gen_random_bytes(entropy_bytes)?,
+        };
+
+        Ok(KeyPhrase::from_entropy_unchecked_with(
+            entropy,
+            self.lang,
+            &self.checksum,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rand_core::impls;
+
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            if let Err(error) = self.try_fill_bytes(dest) {
+                panic!("{}", error);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), rand::Error> {
+            Err(rand::Error::new(rand::ErrorKind::Unavailable, "no entropy"))
+        }
+    }
+
+    impl CryptoRng for FailingRng {}
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn build_defaults() {
+        let keyphrase: KeyPhrase = KeyPhraseBuilder::new().build().unwrap();
+
+        assert_eq!(keyphrase.phrase().split(' ').count(), 12);
+    }
+
+    #[test]
+    fn build_from_entropy() {
+        let entropy: &[u8; 16] = &[
+            0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84,
+            0x6A, 0x79,
+        ];
+        let phrase: &str =
+            "crop cash unable insane eight faith inflict route frame loud box vibrant";
+
+        // the RNG isn't used when the entropy is given
+        let keyphrase: KeyPhrase = KeyPhraseBuilder::new()
+            .rng(FailingRng)
+            .entropy(entropy)
+            .build()
+            .unwrap();
+
+        assert_eq!(phrase, keyphrase.phrase());
+        assert!(KeyPhraseBuilder::new()
+            .rng(FailingRng)
+            .word_count(24)
+            .entropy(entropy)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn build_with_rng() {
+        let m1: KeyPhrase = KeyPhraseBuilder::new()
+            .word_count(18)
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+        let m2: KeyPhrase = KeyPhraseBuilder::new()
+            .word_count(18)
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+
+        assert_eq!(m1.phrase().split(' ').count(), 18);
+        assert_eq!(m1.phrase(), m2.phrase());
+    }
+
+    #[test]
+    fn build_with_checksum() {
+        struct ZeroChecksum;
+
+        impl ChecksumScheme for ZeroChecksum {
+            fn checksum_byte(&self, _: &[u8]) -> u8 {
+                0
+            }
+        }
+
+        let entropy: &[u8; 16] = &[
+            0x33, 0xE4, 0x6B, 0xB1, 0x3A, 0x74, 0x6E, 0xA4, 0x1C, 0xDD, 0xE4, 0x5C, 0x90, 0x84,
+            0x6A, 0x79,
+        ];
+
+        let keyphrase: KeyPhrase = KeyPhraseBuilder::new()
+            .rng(FailingRng)
+            .entropy(entropy)
+            .checksum(ZeroChecksum)
+            .build()
+            .unwrap();
+        let expected: KeyPhrase =
+            KeyPhrase::from_entropy_with_checksum(entropy, Language::English, &ZeroChecksum)
+                .unwrap();
+
+        assert_eq!(keyphrase.phrase(), expected.phrase());
+        assert!(KeyPhrase::from_phrase(keyphrase.phrase(), Language::English).is_err());
+    }
+
+    #[test]
+    fn build_with_failing_rng() {
+        let error: Error = KeyPhraseBuilder::new().rng(FailingRng).build().unwrap_err();
+
+        match error.kind() {
+            ErrorKind::EntropySourceFailed(_) => {}
+            _ => panic!("unexpected error: {}", error),
+        }
+    }
+}
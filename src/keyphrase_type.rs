@@ -1,5 +1,4 @@
-use crate::error::ErrorKind;
-use failure::Error;
+use crate::error::{Error, ErrorKind};
 use std::fmt;
 
 const ENTROPY_OFFSET: usize = 8;
@@ -58,7 +57,10 @@ impl KeyPhraseType {
             18 => KeyPhraseType::Words18,
             21 => KeyPhraseType::Words21,
             24 => KeyPhraseType::Words24,
-            _ => Err(ErrorKind::InvalidWordLength(size))?,
+            _ => Err(ErrorKind::InvalidWordLength {
+                words: size,
+                expected: "12, 15, 18, 21 or 24",
+            })?,
         };
 
         Ok(keyphrase_type)
@@ -0,0 +1,508 @@
+//! Web3 Secret Storage of private keys, enabled by the `keystore` feature.
+//!
+//! A keystore is the version 3 JSON file of Ethereum wallets such as geth or MetaMask: the key
+//! is encrypted with AES-128-CTR under a key derived from the password with scrypt or PBKDF2,
+//! and authenticated with a Keccak-256 MAC. Any key can be stored, such as the secret key of an
+//! [`ExtendedPrivKey`][ExtendedPrivKey], but wallets expect 32 bytes secp256k1 keys.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::keystore::{self, KdfParams};
+//!
+//! let key = [0x42; 32];
+//! let json = keystore::encrypt_with_kdf(&key, "correct horse", KdfParams::Pbkdf2 { rounds: 1024 })
+//!     .unwrap();
+//!
+//! assert_eq!(keystore::decrypt(&json, "correct horse").unwrap(), key);
+//! assert!(keystore::decrypt(&json, "battery staple").is_err());
+//! ```
+//!
+//! [ExtendedPrivKey]: ../hd/struct.ExtendedPrivKey.html
+
+use crate::crypto::{constant_time_eq, gen_random_bytes, pbkdf2_sha256};
+use crate::error::{Error, ErrorKind};
+use crate::passphrase::Passphrase;
+use aes::Aes128;
+use ctr::cipher::{NewCipher, StreamCipher};
+use scrypt::ScryptParams;
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::convert::TryFrom;
+use std::fmt::Write;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// AES-128 in counter mode with a 128 bits big endian counter, the only keystore cipher
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+/// Length of the key derived from the password, half for AES and half for the MAC
+const DERIVED_KEY_BYTES: usize = 32;
+
+/// Length of the random KDF salt
+const SALT_BYTES: usize = 32;
+
+/// Length of the random AES counter
+const IV_BYTES: usize = 16;
+
+/// Most memory scrypt may use, `128 * n * r` bytes, and most parallel scrypt runs and PBKDF2
+/// rounds, so that a crafted keystore can't exhaust the memory or keep the CPU busy for hours
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+const MAX_SCRYPT_P: u32 = 16;
+const MAX_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+/// Key derivation function of a keystore, turning the password into the encryption key
+///
+/// The default is scrypt with `n = 2^18`, `r = 8` and `p = 1`, the parameters of geth. scrypt
+/// may use at most 1 GiB of memory (`128 * n * r` bytes) with `p` up to 16, and PBKDF2 at most
+/// 10,000,000 rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfParams {
+    /// Memory-hard scrypt, `n` must be a power of two greater than 1
+    Scrypt { n: u64, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with the given number of rounds
+    Pbkdf2 { rounds: u32 },
+}
+
+impl Default for KdfParams {
+    fn default() -> KdfParams {
+        KdfParams::Scrypt {
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Derive the encryption and MAC key from the password
+    fn derive(&self, password: &[u8], salt: &[u8]) -> Result<[u8; DERIVED_KEY_BYTES], Error> {
+        let mut derived: [u8; DERIVED_KEY_BYTES] = [0; DERIVED_KEY_BYTES];
+
+        match *self {
+            KdfParams::Scrypt { n, r, p } => {
+                if n < 2 || !n.is_power_of_two() {
+                    Err(ErrorKind::InvalidKdfParams(
+                        "scrypt n must be a power of two greater than 1",
+                    ))?;
+                }
+
+                if u128::from(n) * 128 * u128::from(r) > u128::from(MAX_SCRYPT_MEMORY) {
+                    Err(ErrorKind::InvalidKdfParams("too much scrypt memory"))?;
+                }
+
+                if p > MAX_SCRYPT_P {
+                    Err(ErrorKind::InvalidKdfParams("too many parallel scrypt runs"))?;
+                }
+
+                let params: ScryptParams = ScryptParams::new(n.trailing_zeros() as u8, r, p)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt parameters"))?;
+
+                scrypt::scrypt(password, salt, &params, &mut derived)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt output length"))?;
+            }
+            KdfParams::Pbkdf2 { rounds } => {
+                if rounds == 0 {
+                    Err(ErrorKind::InvalidKdfParams(
+                        "PBKDF2 needs at least one round",
+                    ))?;
+                }
+
+                if rounds > MAX_PBKDF2_ROUNDS {
+                    Err(ErrorKind::InvalidKdfParams("too many PBKDF2 rounds"))?;
+                }
+
+                pbkdf2_sha256(password, salt, rounds as usize, &mut derived);
+            }
+        }
+
+        Ok(derived)
+    }
+
+    /// Get the `kdf` name and the `kdfparams` object of the keystore JSON
+    fn to_json(self, salt: &[u8]) -> (&'static str, Value) {
+        match self {
+            KdfParams::Scrypt { n, r, p } => (
+                "scrypt",
+                json!({ "dklen": DERIVED_KEY_BYTES, "n": n, "p": p, "r": r, "salt": to_hex(salt) }),
+            ),
+            KdfParams::Pbkdf2 { rounds } => (
+                "pbkdf2",
+                json!({
+                    "c": rounds,
+                    "dklen": DERIVED_KEY_BYTES,
+                    "prf": "hmac-sha256",
+                    "salt": to_hex(salt),
+                }),
+            ),
+        }
+    }
+
+    /// Parse the `kdf` name and the `kdfparams` object of a keystore JSON
+    fn from_json(kdf: &Value, params: &Value) -> Result<KdfParams, Error> {
+        if params["dklen"].as_u64() != Some(DERIVED_KEY_BYTES as u64) {
+            Err(ErrorKind::InvalidKeystore(
+                "the derived key must be 32 bytes",
+            ))?;
+        }
+
+        match kdf.as_str() {
+            Some("scrypt") => Ok(KdfParams::Scrypt {
+                n: number(&params["n"])?,
+                r: u32::try_from(number(&params["r"])?)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt parameters"))?,
+                p: u32::try_from(number(&params["p"])?)
+                    .map_err(|_| ErrorKind::InvalidKdfParams("invalid scrypt parameters"))?,
+            }),
+            Some("pbkdf2") => {
+                if params["prf"].as_str() != Some("hmac-sha256") {
+                    Err(ErrorKind::InvalidKeystore("unsupported PBKDF2 function"))?;
+                }
+
+                Ok(KdfParams::Pbkdf2 {
+                    rounds: u32::try_from(number(&params["c"])?)
+                        .map_err(|_| ErrorKind::InvalidKdfParams("too many PBKDF2 rounds"))?,
+                })
+            }
+            _ => Err(ErrorKind::InvalidKeystore(
+                "unsupported key derivation function",
+            ))?,
+        }
+    }
+}
+
+/// Encrypt `key` with `password` into a keystore JSON, with the default scrypt parameters
+///
+/// Deriving the encryption key takes 256MB of memory and about a second, which is what makes
+/// guessing the password expensive. Use [`encrypt_with_kdf()`][encrypt_with_kdf()] to tune it.
+///
+/// [encrypt_with_kdf()]: ./fn.encrypt_with_kdf.html
+pub fn encrypt<P>(key: &[u8], password: P) -> Result<String, Error>
+where
+    P: Into<Passphrase>,
+{
+    encrypt_with_kdf(key, password, KdfParams::default())
+}
+
+/// Encrypt `key` with `password` into a keystore JSON, deriving the encryption key with `kdf`
+///
+/// The salt, the AES counter and the `id` of the keystore are random. Returns an `Error` of kind
+/// `ErrorKind::InvalidKdfParams` if the parameters of `kdf` are invalid.
+pub fn encrypt_with_kdf<P>(key: &[u8], password: P, kdf: KdfParams) -> Result<String, Error>
+where
+    P: Into<Passphrase>,
+{
+    let password: Passphrase = password.into();
+    let salt: Vec<u8> = gen_random_bytes(SALT_BYTES);
+    let iv: Vec<u8> = gen_random_bytes(IV_BYTES);
+
+    #[allow(unused_mut)]
+    let mut derived: [u8; DERIVED_KEY_BYTES] =
+        kdf.derive(password.expose_secret().as_bytes(), &salt)?;
+    let mut ciphertext: Vec<u8> = key.to_vec();
+
+    aes_128_ctr(&derived[..16], &iv, &mut ciphertext);
+
+    let mac: Vec<u8> = mac(&derived, &ciphertext);
+    let (kdf_name, kdf_params): (&'static str, Value) = kdf.to_json(&salt);
+
+    #[cfg(feature = "zeroize")]
+    derived.zeroize();
+
+    let keystore: Value = json!({
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": to_hex(&iv) },
+            "ciphertext": to_hex(&ciphertext),
+            "kdf": kdf_name,
+            "kdfparams": kdf_params,
+            "mac": to_hex(&mac),
+        },
+        "id": uuid_v4(),
+        "version": 3,
+    });
+
+    Ok(keystore.to_string())
+}
+
+/// Decrypt the key of a keystore JSON with `password`
+///
+/// Both scrypt and PBKDF2-HMAC-SHA256 keystores are supported. Returns an `Error` of kind
+/// `ErrorKind::InvalidPassword` if the password is wrong, `ErrorKind::InvalidKeystore` if the
+/// JSON is not a version 3 keystore, or `ErrorKind::InvalidKdfParams` if its parameters are
+/// invalid.
+pub fn decrypt<P>(json: &str, password: P) -> Result<Vec<u8>, Error>
+where
+    P: Into<Passphrase>,
+{
+    let password: Passphrase = password.into();
+    let keystore: Value = json
+        .parse()
+        .map_err(|_| ErrorKind::InvalidKeystore("not a JSON document"))?;
+
+    if keystore["version"].as_u64() != Some(3) {
+        Err(ErrorKind::InvalidKeystore("only version 3 is supported"))?;
+    }
+
+    // Some wallets wrote the crypto object with a capital C
+    let crypto: &Value = keystore
+        .get("crypto")
+        .or_else(|| keystore.get("Crypto"))
+        .ok_or(ErrorKind::InvalidKeystore("missing crypto object"))?;
+
+    if crypto["cipher"].as_str() != Some("aes-128-ctr") {
+        Err(ErrorKind::InvalidKeystore("unsupported cipher"))?;
+    }
+
+    let iv: Vec<u8> = from_hex(&crypto["cipherparams"]["iv"])?;
+
+    if iv.len() != IV_BYTES {
+        Err(ErrorKind::InvalidKeystore(
+            "the AES counter must be 16 bytes",
+        ))?;
+    }
+
+    let ciphertext: Vec<u8> = from_hex(&crypto["ciphertext"])?;
+    let expected_mac: Vec<u8> = from_hex(&crypto["mac"])?;
+    let salt: Vec<u8> = from_hex(&crypto["kdfparams"]["salt"])?;
+    let kdf: KdfParams = KdfParams::from_json(&crypto["kdf"], &crypto["kdfparams"])?;
+
+    #[allow(unused_mut)]
+    let mut derived: [u8; DERIVED_KEY_BYTES] =
+        kdf.derive(password.expose_secret().as_bytes(), &salt)?;
+
+    if !constant_time_eq(&mac(&derived, &ciphertext), &expected_mac) {
+        #[cfg(feature = "zeroize")]
+        derived.zeroize();
+
+        Err(ErrorKind::InvalidPassword)?;
+    }
+
+    let mut key: Vec<u8> = ciphertext;
+
+    aes_128_ctr(&derived[..16], &iv, &mut key);
+
+    #[cfg(feature = "zeroize")]
+    derived.zeroize();
+
+    Ok(key)
+}
+
+/// Encrypt or decrypt `data` in place
+fn aes_128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) {
+    Aes128Ctr::new_from_slices(key, iv)
+        .expect("AES-128 keys and counters are 16 bytes")
+        .apply_keystream(data);
+}
+
+/// Compute the MAC of the ciphertext, keyed with the second half of the derived key
+fn mac(derived: &[u8; DERIVED_KEY_BYTES], ciphertext: &[u8]) -> Vec<u8> {
+    Keccak256::new()
+        .chain(&derived[16..])
+        .chain(ciphertext)
+        .finalize()
+        .to_vec()
+}
+
+/// Generate a random version 4 UUID, the `id` of a keystore
+fn uuid_v4() -> String {
+    let mut bytes: Vec<u8> = gen_random_bytes(16);
+
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+
+    [
+        &bytes[..4],
+        &bytes[4..6],
+        &bytes[6..8],
+        &bytes[8..10],
+        &bytes[10..],
+    ]
+    .iter()
+    .map(|group: &&[u8]| to_hex(group))
+    .collect::<Vec<String>>()
+    .join("-")
+}
+
+/// Write bytes as lowercase hex
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex: String = String::with_capacity(bytes.len() * 2);
+
+    bytes
+        .iter()
+        .for_each(|byte: &u8| write!(hex, "{:02x}", byte).unwrap());
+
+    hex
+}
+
+/// Parse a hex string of a keystore JSON
+fn from_hex(value: &Value) -> Result<Vec<u8>, Error> {
+    let hex: &str = value
+        .as_str()
+        .ok_or(ErrorKind::InvalidKeystore("missing hex field"))?;
+
+    if hex.len() % 2 != 0 {
+        Err(ErrorKind::InvalidEncoding("odd number of hex digits"))?;
+    }
+
+    let bytes: Vec<u8> = hex
+        .as_bytes()
+        .chunks(2)
+        .map(|pair: &[u8]| {
+            let high: Option<u32> = char::from(pair[0]).to_digit(16);
+            let low: Option<u32> = char::from(pair[1]).to_digit(16);
+
+            match (high, low) {
+                (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                _ => Err(ErrorKind::InvalidEncoding("invalid hex digit")),
+            }
+        })
+        .collect::<Result<Vec<u8>, ErrorKind>>()?;
+
+    Ok(bytes)
+}
+
+/// Parse a number of a keystore JSON
+fn number(value: &Value) -> Result<u64, Error> {
+    Ok(value
+        .as_u64()
+        .ok_or(ErrorKind::InvalidKeystore("missing KDF parameter"))?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn keystore_pbkdf2_test_vector() {
+        // The PBKDF2 test vector of the Web3 Secret Storage definition
+        let json: &str = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+
+        assert_eq!(to_hex(&decrypt(json, "testpassword").unwrap()), KEY);
+    }
+
+    #[test]
+    fn keystore_scrypt() {
+        let json: &str = r#"{
+            "Crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+                "ciphertext": "01a05c7f05b697274227d8bd0825a6caa89967e24643426c0fcfa2fb663052d7",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 1024,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+                },
+                "mac": "d60a6540bbdeaa746e4c7b4359c74e4bb0b679bedce5b4d129ad96150d200274"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+
+        assert_eq!(to_hex(&decrypt(json, "testpassword").unwrap()), KEY);
+        assert!(decrypt(json, "testpassword!").is_err());
+    }
+
+    #[test]
+    fn keystore_round_trip() {
+        let key: [u8; 32] = [0x42; 32];
+        let kdf: KdfParams = KdfParams::Scrypt { n: 16, r: 8, p: 1 };
+        let json: String = encrypt_with_kdf(&key, "password", kdf).unwrap();
+        let keystore: Value = json.parse().unwrap();
+
+        assert_eq!(keystore["version"].as_u64(), Some(3));
+        assert_eq!(keystore["crypto"]["kdf"].as_str(), Some("scrypt"));
+        assert_eq!(keystore["id"].as_str().unwrap().len(), 36);
+        assert_eq!(&keystore["id"].as_str().unwrap()[14..15], "4");
+        assert_eq!(decrypt(&json, "password").unwrap(), key);
+
+        // tampering with the ciphertext breaks the MAC
+        let ciphertext: &str = keystore["crypto"]["ciphertext"].as_str().unwrap();
+        let tampered: String =
+            json.replace(ciphertext, &ciphertext.chars().rev().collect::<String>());
+
+        assert!(decrypt(&tampered, "password").is_err());
+        assert!(
+            encrypt_with_kdf(&key, "password", KdfParams::Scrypt { n: 15, r: 8, p: 1 }).is_err()
+        );
+        assert!(encrypt_with_kdf(&key, "password", KdfParams::Pbkdf2 { rounds: 0 }).is_err());
+    }
+
+    #[test]
+    fn keystore_kdf_limits() {
+        let key: [u8; 32] = [0x42; 32];
+        let json: String =
+            encrypt_with_kdf(&key, "password", KdfParams::Pbkdf2 { rounds: 1 }).unwrap();
+
+        // the parameters are checked before deriving anything, so these fail right away
+        [
+            KdfParams::Scrypt {
+                n: 1 << 21,
+                r: 8,
+                p: 1,
+            },
+            KdfParams::Scrypt {
+                n: 1 << 62,
+                r: u32::MAX,
+                p: 1,
+            },
+            KdfParams::Scrypt {
+                n: 2,
+                r: 1,
+                p: MAX_SCRYPT_P + 1,
+            },
+            KdfParams::Pbkdf2 {
+                rounds: MAX_PBKDF2_ROUNDS + 1,
+            },
+        ]
+        .iter()
+        .for_each(|&kdf: &KdfParams| {
+            match encrypt_with_kdf(&key, "password", kdf).unwrap_err().kind() {
+                ErrorKind::InvalidKdfParams(_) => {}
+                kind => panic!("unexpected error: {:?}", kind),
+            }
+        });
+
+        let greedy: String = json.replace(r#""c":1,"#, r#""c":4294967295,"#);
+
+        assert_ne!(greedy, json);
+        match decrypt(&greedy, "password").unwrap_err().kind() {
+            ErrorKind::InvalidKdfParams(_) => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn keystore_invalid() {
+        assert!(decrypt("", "").is_err());
+        assert!(decrypt("[]", "").is_err());
+        assert!(decrypt(r#"{ "version": 1, "crypto": {} }"#, "").is_err());
+        assert!(decrypt(r#"{ "version": 3 }"#, "").is_err());
+        assert!(decrypt(
+            r#"{ "version": 3, "crypto": { "cipher": "aes-128-cbc" } }"#,
+            ""
+        )
+        .is_err());
+    }
+}
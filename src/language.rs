@@ -1,10 +1,12 @@
+use crate::error::Error;
 use crate::ErrorKind;
 use crate::{Bits, Bits11};
-use failure::Error;
 use hashbrown::HashMap;
+use unicode_normalization::UnicodeNormalization;
 
 pub struct WordMap {
     inner: HashMap<&'static str, Bits11>,
+    lang: Language,
 }
 
 pub struct WordList {
@@ -12,10 +14,34 @@ pub struct WordList {
 }
 
 impl WordMap {
+    /// Look up a single word, the index of an `ErrorKind::InvalidWord` error is always 0
     pub fn get_bits(&self, word: &str) -> Result<Bits11, Error> {
-        match self.inner.get(word) {
-            Some(n) => Ok(*n),
-            None => Err(ErrorKind::InvalidWord)?,
+        self.get_bits_at(word, 0)
+    }
+
+    /// Look up the word at position `index` of a phrase, for the `ErrorKind::InvalidWord` error
+    ///
+    /// A word that is only found once normalized to NFKD or NFC returns an
+    /// `ErrorKind::InvalidNormalization` error instead.
+    pub(crate) fn get_bits_at(&self, word: &str, index: usize) -> Result<Bits11, Error> {
+        if let Some(n) = self.inner.get(word) {
+            return Ok(*n);
+        }
+
+        let nfkd: String = word.nfkd().collect();
+        let nfc: String = word.nfc().collect();
+
+        if self.inner.contains_key(nfkd.as_str()) || self.inner.contains_key(nfc.as_str()) {
+            Err(ErrorKind::InvalidNormalization {
+                word: word.to_string(),
+                index,
+            })?
+        } else {
+            Err(ErrorKind::InvalidWord {
+                word: word.to_string(),
+                index,
+                lang: Some(self.lang),
+            })?
         }
     }
 }
@@ -24,10 +50,15 @@ impl WordList {
     pub fn get_word(&self, bits: Bits11) -> &'static str {
         self.inner[bits.bits() as usize]
     }
+
+    /// All the words, in wordlist order
+    pub(crate) fn words(&self) -> &[&'static str] {
+        &self.inner
+    }
 }
 
 mod lazy {
-    use super::{Bits11, WordList, WordMap};
+    use super::{Bits11, Language, WordList, WordMap};
     use once_cell::sync::Lazy;
 
     /// lazy generation of the word list
@@ -40,7 +71,7 @@ mod lazy {
     }
 
     /// lazy generation of the word map
-    fn gen_wordmap(wordlist: &WordList) -> WordMap {
+    fn gen_wordmap(wordlist: &WordList, lang: Language) -> WordMap {
         let inner = wordlist
             .inner
             .iter()
@@ -48,7 +79,7 @@ mod lazy {
             .map(|(i, item): (usize, &&'static str)| (*item, Bits11::from(i as u16)))
             .collect();
 
-        WordMap { inner }
+        WordMap { inner, lang }
     }
 
     pub static WORDLIST_ENGLISH: Lazy<WordList> =
@@ -75,23 +106,29 @@ mod lazy {
     pub static WORDLIST_SPANISH: Lazy<WordList> =
         sync_lazy! { gen_wordlist(include_str!("langs/spanish.txt")) };
 
-    pub static WORDMAP_ENGLISH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_ENGLISH) };
+    pub static WORDMAP_ENGLISH: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_ENGLISH, Language::English) };
     #[cfg(feature = "chinese-simplified")]
     pub static WORDMAP_CHINESE_SIMPLIFIED: Lazy<WordMap> =
-        sync_lazy! {  gen_wordmap(&WORDLIST_CHINESE_SIMPLIFIED) };
+        sync_lazy! { gen_wordmap(&WORDLIST_CHINESE_SIMPLIFIED, Language::ChineseSimplified) };
     #[cfg(feature = "chinese-traditional")]
     pub static WORDMAP_CHINESE_TRADITIONAL: Lazy<WordMap> =
-        sync_lazy! { gen_wordmap(&WORDLIST_CHINESE_TRADITIONAL) };
+        sync_lazy! { gen_wordmap(&WORDLIST_CHINESE_TRADITIONAL, Language::ChineseTraditional) };
     #[cfg(feature = "french")]
-    pub static WORDMAP_FRENCH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_FRENCH) };
+    pub static WORDMAP_FRENCH: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_FRENCH, Language::French) };
     #[cfg(feature = "italian")]
-    pub static WORDMAP_ITALIAN: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_ITALIAN) };
+    pub static WORDMAP_ITALIAN: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_ITALIAN, Language::Italian) };
     #[cfg(feature = "japanese")]
-    pub static WORDMAP_JAPANESE: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_JAPANESE) };
+    pub static WORDMAP_JAPANESE: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_JAPANESE, Language::Japanese) };
     #[cfg(feature = "korean")]
-    pub static WORDMAP_KOREAN: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_KOREAN) };
+    pub static WORDMAP_KOREAN: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_KOREAN, Language::Korean) };
     #[cfg(feature = "spanish")]
-    pub static WORDMAP_SPANISH: Lazy<WordMap> = sync_lazy! { gen_wordmap(&WORDLIST_SPANISH) };
+    pub static WORDMAP_SPANISH: Lazy<WordMap> =
+        sync_lazy! { gen_wordmap(&WORDLIST_SPANISH, Language::Spanish) };
 }
 
 /// The language determines which words will be used in a keyphrase, but also indirectly
@@ -101,7 +138,7 @@ mod lazy {
 ///
 /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
 /// [Seed]: ./seed/struct.Seed.html
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Language {
     English,
     #[cfg(feature = "chinese-simplified")]
@@ -142,6 +179,91 @@ impl Language {
         }
     }
 
+    /// Get every word of the wordlist starting with `prefix`, in wordlist order
+    ///
+    /// Meant for autocompletion while a phrase is typed: the English words are unique by their
+    /// first four letters, so a prefix of four letters leaves at most one of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Language;
+    ///
+    /// assert_eq!(Language::English.words_with_prefix("ank"), vec!["ankle"]);
+    /// assert_eq!(Language::English.words_with_prefix("zo"), vec!["zone", "zoo"]);
+    /// ```
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<&'static str> {
+        self.wordlist()
+            .words()
+            .iter()
+            .filter(|word: &&&'static str| word.starts_with(prefix))
+            .copied()
+            .collect()
+    }
+
+    /// Stable numeric code of the language, used by binary encodings
+    ///
+    /// The codes do not depend on which language features are enabled.
+    pub(crate) fn code(&self) -> u8 {
+        match *self {
+            Language::English => 0,
+            #[cfg(feature = "chinese-simplified")]
+            Language::ChineseSimplified => 1,
+            #[cfg(feature = "chinese-traditional")]
+            Language::ChineseTraditional => 2,
+            #[cfg(feature = "french")]
+            Language::French => 3,
+            #[cfg(feature = "italian")]
+            Language::Italian => 4,
+            #[cfg(feature = "japanese")]
+            Language::Japanese => 5,
+            #[cfg(feature = "korean")]
+            Language::Korean => 6,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => 7,
+        }
+    }
+
+    /// Get the language for a code returned by [`Language::code()`][Language::code()]
+    ///
+    /// Returns `None` for unknown codes and for languages whose feature is not enabled.
+    ///
+    /// [Language::code()]: ./enum.Language.html#method.code
+    pub(crate) fn from_code(code: u8) -> Option<Language> {
+        match code {
+            0 => Some(Language::English),
+            #[cfg(feature = "chinese-simplified")]
+            1 => Some(Language::ChineseSimplified),
+            #[cfg(feature = "chinese-traditional")]
+            2 => Some(Language::ChineseTraditional),
+            #[cfg(feature = "french")]
+            3 => Some(Language::French),
+            #[cfg(feature = "italian")]
+            4 => Some(Language::Italian),
+            #[cfg(feature = "japanese")]
+            5 => Some(Language::Japanese),
+            #[cfg(feature = "korean")]
+            6 => Some(Language::Korean),
+            #[cfg(feature = "spanish")]
+            7 => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+
+    /// Every language enabled by the features of the crate
+    pub(crate) fn all() -> impl Iterator<Item = Language> {
+        (0..8).filter_map(Language::from_code)
+    }
+
+    /// Every language enabled by the features of the crate, starting with the default one, in
+    /// the order the language of a phrase is detected
+    pub(crate) fn detection_order() -> impl Iterator<Item = Language> {
+        let default: Language = Language::default();
+
+        std::iter::once(default)
+            .chain(Language::all().filter(move |&lang: &Language| lang != default))
+    }
+
     /// Get a [`WordMap`][WordMap] that allows word -> index lookups in the word list
     ///
     /// The index of an individual word in the word list is used as the binary value of that word
@@ -15,7 +15,7 @@
 //! // grocery unknown bench gold grant slim assist monster laptop cruise hamster any
 //!
 //! /// get the HD wallet seed
-//! let seed = Seed::new(&keyphrase, "");
+//! let seed = Seed::new_bip39(&keyphrase, "");
 //!
 //! // print the HD wallet seed as a hex string
 //! println!("{:X}", seed);
@@ -23,26 +23,192 @@
 //! ```
 //!
 #[macro_use]
-extern crate failure;
-#[macro_use]
 extern crate once_cell;
 extern crate hashbrown;
 extern crate hmac;
 extern crate pbkdf2;
+extern crate rand_core;
 extern crate sha2;
+extern crate unicode_normalization;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "getrandom")]
+extern crate getrandom;
+#[cfg(feature = "test-rng")]
+extern crate rand_chacha;
+#[cfg(feature = "mlock")]
+extern crate memsec;
+#[cfg(feature = "bip39")]
+extern crate bip39;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "scrypt")]
+extern crate scrypt;
+#[cfg(feature = "argon2")]
+extern crate argon2;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "bip32")]
+extern crate secp256k1;
+#[cfg(feature = "bip32")]
+extern crate ripemd160;
+#[cfg(feature = "secrecy")]
+extern crate secrecy;
+#[cfg(feature = "encodings")]
+extern crate base64;
+#[cfg(feature = "encodings")]
+extern crate bech32;
+#[cfg(any(feature = "encodings", feature = "bip32"))]
+extern crate bs58;
+#[cfg(feature = "encrypted-export")]
+extern crate chacha20poly1305;
+#[cfg(feature = "keystore")]
+extern crate aes;
+#[cfg(feature = "keystore")]
+extern crate ctr;
+#[cfg(feature = "keystore")]
+extern crate sha3;
+#[cfg(feature = "keystore")]
+#[macro_use]
+extern crate serde_json;
+#[cfg(feature = "qr")]
+extern crate qrcode;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+#[cfg(feature = "python")]
+extern crate pyo3;
+#[cfg(feature = "mobile")]
+extern crate uniffi;
+#[cfg(feature = "node")]
+extern crate napi;
+#[cfg(feature = "node")]
+extern crate napi_derive;
 
+mod analog;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_compat;
+mod audit;
+mod backup;
+#[cfg(feature = "bip39")]
+mod bip39_compat;
+#[cfg(feature = "bytewords")]
+mod bytewords;
+mod checksum;
+#[cfg(feature = "codex32")]
+mod codex32;
+mod compact;
+mod diff;
+#[cfg(feature = "rand")]
+mod distribution;
+#[cfg(feature = "emoji")]
+mod emoji;
+#[cfg(feature = "encrypted-export")]
+mod encrypted;
+#[cfg(feature = "encodings")]
+mod encodings;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod fuzzing;
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+mod generator;
+#[cfg(any(feature = "bip32", feature = "ed25519"))]
+pub mod hd;
+pub mod compress;
 mod error;
 mod keyphrase;
+mod keyphrase_builder;
 mod keyphrase_type;
+#[cfg(feature = "keystore")]
+pub mod keystore;
 mod language;
+#[cfg(feature = "mlock")]
+mod locked;
+#[cfg(feature = "mobile")]
+pub mod mobile;
+#[cfg(feature = "node")]
+pub mod node;
+mod passphrase;
+#[cfg(feature = "protect")]
+pub mod protect;
+#[cfg(feature = "python")]
+// The `#[pymethods]` of PyO3 0.20 define impls inside functions, which newer compilers lint
+#[allow(non_local_definitions)]
+pub mod python;
+#[cfg(feature = "qr")]
+pub mod qr;
+mod radix;
+pub mod recovery;
+#[cfg(feature = "rfc1751")]
+mod rfc1751;
 mod seed;
+#[cfg(feature = "seedqr")]
+mod seedqr;
+#[cfg(feature = "async")]
+mod seed_future;
+#[cfg(feature = "serde")]
+mod serde_compat;
+#[cfg(feature = "shamir")]
+pub mod shamir;
+#[cfg(feature = "slip39")]
+pub mod slip39;
+#[cfg(feature = "test-rng")]
+mod test_rng;
+mod trace;
+#[cfg(feature = "legacy-2of3")]
+mod two_of_three;
+#[cfg(feature = "ur")]
+pub mod ur;
 mod util;
+mod validation;
+mod validator;
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+mod vanity;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 mod crypto;
 
+#[cfg(feature = "mobile")]
+uniffi::setup_scaffolding!();
+
 pub use self::keyphrase::KeyPhrase;
-pub use error::ErrorKind;
+pub use analog::Card;
+#[cfg(feature = "arbitrary")]
+pub use arbitrary_compat::{ArbitraryEntropy, ArbitraryPhrase};
+pub use audit::Warning;
+pub use backup::{BackupEntry, BackupTemplate, PlateLayout};
+#[cfg(feature = "bytewords")]
+pub use bytewords::BytewordsStyle;
+pub use checksum::{ChecksumScheme, Sha256Checksum};
+pub use compact::CompactKeyPhrase;
+pub use crypto::{DefaultRng, Kdf};
+pub use diff::WordDiff;
+#[cfg(feature = "rand")]
+pub use distribution::KeyPhraseDistribution;
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+pub use generator::KeyPhraseGenerator;
+pub use self::keyphrase_builder::KeyPhraseBuilder;
+pub use error::{Error, ErrorKind};
 pub use keyphrase_type::KeyPhraseType;
 pub use language::Language;
+pub use passphrase::Passphrase;
+pub use radix::Radix;
+#[cfg(feature = "rfc1751")]
+pub use rfc1751::{decode_rfc1751, encode_rfc1751};
 pub use seed::Seed;
+#[cfg(feature = "test-rng")]
+pub use test_rng::TestRng;
+#[cfg(feature = "async")]
+pub use seed_future::SeedFuture;
+pub use trace::{ParseTrace, TraceStep};
+#[cfg(feature = "legacy-2of3")]
+pub use two_of_three::TwoOfThreeCard;
 pub use util::*;
+pub use validation::ValidationReport;
+pub use validator::{PhraseValidator, WordStatus};
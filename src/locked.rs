@@ -0,0 +1,168 @@
+//! Heap buffers for the entropy of a [`KeyPhrase`][KeyPhrase] and the bytes of a [`Seed`][Seed],
+//! locked in RAM, enabled by the `mlock` feature.
+//!
+//! The buffers are allocated with `memsec`: their pages are locked with `mlock`
+//! (`VirtualLock` on Windows) so they are never written to swap, are left out of core dumps on
+//! Linux and FreeBSD, and sit between inaccessible guard pages, with a canary checked when they
+//! are freed. Locking fails silently once the `RLIMIT_MEMLOCK` of the process is reached, and an
+//! allocation that fails, or a platform without these calls such as WebAssembly, falls back to
+//! a regular heap buffer. Every locked buffer takes at least 4 pages of address space, so the
+//! feature suits the few long-lived secrets of a wallet daemon rather than batch generation.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//! [Seed]: ../seed/struct.Seed.html
+
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use zeroize::Zeroize;
+
+/// A box of secret bytes, `[u8]` or `[u8; N]`, locked in RAM
+pub(crate) struct Locked<T: ?Sized> {
+    ptr: NonNull<T>,
+    /// Whether `ptr` was allocated by `memsec`, rather than by a `Box`
+    #[cfg(any(unix, windows))]
+    locked: bool,
+}
+
+// `Locked` owns its buffer like a `Box`, which is `Send` and `Sync`
+unsafe impl<T: ?Sized + Send> Send for Locked<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Locked<T> {}
+
+impl<T: ?Sized> Locked<T> {
+    fn from_box(boxed: Box<T>) -> Locked<T> {
+        Locked {
+            ptr: NonNull::from(Box::leak(boxed)),
+            #[cfg(any(unix, windows))]
+            locked: false,
+        }
+    }
+}
+
+impl Locked<[u8]> {
+    /// Copy `bytes` into a new buffer
+    pub fn from_slice(bytes: &[u8]) -> Locked<[u8]> {
+        #[cfg(any(unix, windows))]
+        {
+            if !bytes.is_empty() {
+                // Safety: memsec returns a buffer of `bytes.len()` writable bytes, or nothing
+                if let Some(mut ptr) = unsafe { memsec::malloc_sized(bytes.len()) } {
+                    unsafe { ptr.as_mut() }.copy_from_slice(bytes);
+
+                    return Locked { ptr, locked: true };
+                }
+            }
+        }
+
+        Locked::from_box(bytes.into())
+    }
+}
+
+impl<const N: usize> Locked<[u8; N]> {
+    /// Create a new buffer of zeros
+    pub fn zeroed() -> Locked<[u8; N]> {
+        #[cfg(any(unix, windows))]
+        {
+            // Safety: memsec returns a buffer of `N` writable bytes, or nothing
+            if let Some(mut ptr) = unsafe { memsec::malloc::<[u8; N]>() } {
+                unsafe { ptr.as_mut() }.fill(0);
+
+                return Locked { ptr, locked: true };
+            }
+        }
+
+        Locked::from_box(Box::new([0; N]))
+    }
+}
+
+impl<T: ?Sized> Deref for Locked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: `ptr` is valid and owned by `self` until it is dropped
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for Locked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: `ptr` is valid and owned by `self` until it is dropped
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl Clone for Locked<[u8]> {
+    fn clone(&self) -> Locked<[u8]> {
+        Locked::from_slice(self)
+    }
+}
+
+impl<const N: usize> Clone for Locked<[u8; N]> {
+    fn clone(&self) -> Locked<[u8; N]> {
+        let mut clone: Locked<[u8; N]> = Locked::zeroed();
+
+        clone.copy_from_slice(&self[..]);
+        clone
+    }
+}
+
+/// Move the bytes of a `Vec` into a new buffer, wiping the `Vec`
+impl From<Vec<u8>> for Locked<[u8]> {
+    fn from(mut bytes: Vec<u8>) -> Locked<[u8]> {
+        let locked: Locked<[u8]> = Locked::from_slice(&bytes);
+
+        bytes.zeroize();
+        locked
+    }
+}
+
+/// Copy the bytes out of the buffer, which is wiped when dropped
+impl From<Locked<[u8]>> for Vec<u8> {
+    fn from(locked: Locked<[u8]>) -> Vec<u8> {
+        locked.to_vec()
+    }
+}
+
+impl Default for Locked<[u8]> {
+    fn default() -> Locked<[u8]> {
+        Locked::from_box(Box::new([]))
+    }
+}
+
+impl<T: ?Sized> Drop for Locked<T> {
+    fn drop(&mut self) {
+        #[cfg(any(unix, windows))]
+        {
+            if self.locked {
+                // Safety: `ptr` was allocated by memsec, which wipes and unlocks it when freeing
+                unsafe { memsec::free(self.ptr) };
+
+                return;
+            }
+        }
+
+        // Safety: `ptr` was leaked from a box in `from_box()`
+        drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locked_buffers() {
+        let mut array: Locked<[u8; 64]> = Locked::zeroed();
+
+        assert_eq!(&array[..], &[0; 64][..]);
+
+        array[3] = 7;
+
+        let copy: Locked<[u8; 64]> = array.clone();
+        let slice: Locked<[u8]> = Locked::from_slice(&copy[..16]);
+
+        assert_eq!(copy[3], 7);
+        assert_eq!(slice.len(), 16);
+        assert_eq!(&slice[..], &array[..16]);
+        assert!(Locked::<[u8]>::default().is_empty());
+    }
+}
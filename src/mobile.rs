@@ -0,0 +1,269 @@
+//! Kotlin and Swift bindings built with UniFFI, enabled by the `mobile` feature.
+//!
+//! The interface is declared with the UniFFI proc-macros, so the bindings are generated from the
+//! built library itself:
+//!
+//! ```sh
+//! cargo build --release --features mobile
+//! uniffi-bindgen generate --library target/release/libkeyphrase.so --language kotlin --out-dir out
+//! uniffi-bindgen generate --library target/release/libkeyphrase.so --language swift --out-dir out
+//! ```
+//!
+//! Phrases are plain strings and entropy and seeds byte arrays: the apps should keep them in
+//! memory no longer than needed. Errors are thrown as `MobileException`, after their
+//! `ErrorKind`.
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use crate::validation::ValidationReport;
+use std::fmt;
+
+/// The languages of [`Language`][Language], with their stable codes
+///
+/// Every language is listed, but those whose feature isn't enabled throw
+/// `UnsupportedLanguage` when used. [`supported_languages()`][supported_languages()] lists the
+/// others.
+///
+/// [Language]: ../language/enum.Language.html
+/// [supported_languages()]: ./fn.supported_languages.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum MobileLanguage {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+impl MobileLanguage {
+    fn to_language(self) -> Result<Language, MobileError> {
+        let code: u8 = match self {
+            MobileLanguage::English => 0,
+            MobileLanguage::ChineseSimplified => 1,
+            MobileLanguage::ChineseTraditional => 2,
+            MobileLanguage::French => 3,
+            MobileLanguage::Italian => 4,
+            MobileLanguage::Japanese => 5,
+            MobileLanguage::Korean => 6,
+            MobileLanguage::Spanish => 7,
+        };
+
+        Language::from_code(code).ok_or_else(|| ErrorKind::UnsupportedLanguage.into())
+    }
+
+    fn from_language(lang: Language) -> MobileLanguage {
+        match lang.code() {
+            1 => MobileLanguage::ChineseSimplified,
+            2 => MobileLanguage::ChineseTraditional,
+            3 => MobileLanguage::French,
+            4 => MobileLanguage::Italian,
+            5 => MobileLanguage::Japanese,
+            6 => MobileLanguage::Korean,
+            7 => MobileLanguage::Spanish,
+            _ => MobileLanguage::English,
+        }
+    }
+}
+
+/// The errors of the mobile bindings, after their `ErrorKind`, with its message
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+pub enum MobileError {
+    InvalidChecksum { message: String },
+    InvalidWord { message: String },
+    InvalidWordLength { message: String },
+    InvalidKeysize { message: String },
+    UnsupportedLanguage { message: String },
+    Other { message: String },
+}
+
+impl fmt::Display for MobileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MobileError::InvalidChecksum { message }
+            | MobileError::InvalidWord { message }
+            | MobileError::InvalidWordLength { message }
+            | MobileError::InvalidKeysize { message }
+            | MobileError::UnsupportedLanguage { message }
+            | MobileError::Other { message } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for MobileError {}
+
+impl From<Error> for MobileError {
+    fn from(error: Error) -> MobileError {
+        let message: String = error.to_string();
+
+        match error.kind() {
+            ErrorKind::InvalidChecksum => MobileError::InvalidChecksum { message },
+            ErrorKind::InvalidWord { .. } | ErrorKind::InvalidNormalization { .. } => {
+                MobileError::InvalidWord { message }
+            }
+            ErrorKind::InvalidWordLength { .. } => MobileError::InvalidWordLength { message },
+            ErrorKind::InvalidKeysize(_) | ErrorKind::InvalidEntropyLength(..) => {
+                MobileError::InvalidKeysize { message }
+            }
+            ErrorKind::UnsupportedLanguage => MobileError::UnsupportedLanguage { message },
+            _ => MobileError::Other { message },
+        }
+    }
+}
+
+impl From<ErrorKind> for MobileError {
+    fn from(kind: ErrorKind) -> MobileError {
+        Error::from(kind).into()
+    }
+}
+
+/// Every problem of a phrase, for forms, from
+/// [`KeyPhrase::validate_detailed()`][KeyPhrase::validate_detailed()]
+///
+/// [KeyPhrase::validate_detailed()]: ../keyphrase/struct.KeyPhrase.html#method.validate_detailed
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct PhraseValidation {
+    /// Whether the phrase is a valid keyphrase
+    pub is_valid: bool,
+    /// Number of words in the phrase
+    pub word_count: u32,
+    /// Positions of the words that are not in the wordlist, starting at 0
+    pub invalid_words: Vec<u32>,
+    /// Whether the checksum matched, `null` if it couldn't be checked
+    pub checksum_valid: Option<bool>,
+}
+
+/// The languages enabled in this build
+#[uniffi::export]
+pub fn supported_languages() -> Vec<MobileLanguage> {
+    Language::all().map(MobileLanguage::from_language).collect()
+}
+
+/// Generate a new phrase of 12, 15, 18, 21 or 24 words
+#[uniffi::export]
+pub fn generate(word_count: u32, language: MobileLanguage) -> Result<String, MobileError> {
+    let keyphrase_type: KeyPhraseType = KeyPhraseType::for_word_count(word_count as usize)?;
+
+    Ok(KeyPhrase::try_new(keyphrase_type, language.to_language()?)?.into_phrase())
+}
+
+/// Check that a phrase is a valid keyphrase, throwing the first problem found
+#[uniffi::export]
+pub fn validate(phrase: String, language: MobileLanguage) -> Result<(), MobileError> {
+    Ok(KeyPhrase::validate(&phrase, language.to_language()?)?)
+}
+
+/// Report every problem of a phrase at once
+#[uniffi::export]
+pub fn validate_detailed(
+    phrase: String,
+    language: MobileLanguage,
+) -> Result<PhraseValidation, MobileError> {
+    let report: ValidationReport = KeyPhrase::validate_detailed(&phrase, language.to_language()?);
+
+    Ok(PhraseValidation {
+        is_valid: report.is_valid(),
+        word_count: report.word_count as u32,
+        invalid_words: report
+            .invalid_words
+            .iter()
+            .map(|&(position, _): &(usize, String)| position as u32)
+            .collect(),
+        checksum_valid: report.checksum_valid,
+    })
+}
+
+/// Get the entropy of a phrase
+#[uniffi::export]
+pub fn phrase_to_entropy(phrase: String, language: MobileLanguage) -> Result<Vec<u8>, MobileError> {
+    Ok(KeyPhrase::from_phrase(phrase, language.to_language()?)?.into_entropy())
+}
+
+/// Get the phrase of 16 to 32 bytes of entropy
+#[uniffi::export]
+pub fn entropy_to_phrase(
+    entropy: Vec<u8>,
+    language: MobileLanguage,
+) -> Result<String, MobileError> {
+    Ok(KeyPhrase::from_entropy(&entropy, language.to_language()?)?.into_phrase())
+}
+
+/// Derive the 64 bytes BIP39 seed of a phrase, like any BIP39 wallet
+#[uniffi::export]
+pub fn seed_bip39(
+    phrase: String,
+    language: MobileLanguage,
+    password: String,
+) -> Result<Vec<u8>, MobileError> {
+    let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, language.to_language()?)?;
+
+    Ok(Seed::new_bip39(&keyphrase, password).to_array().to_vec())
+}
+
+/// Derive the 64 bytes legacy seed of a phrase, salted with `"keyphrase"`, as `Seed::new()` does
+#[cfg(not(feature = "strict-bip39-only"))]
+#[uniffi::export]
+pub fn seed_legacy(
+    phrase: String,
+    language: MobileLanguage,
+    password: String,
+) -> Result<Vec<u8>, MobileError> {
+    let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, language.to_language()?)?;
+
+    Ok(Seed::new(&keyphrase, password).to_array().to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mobile_roundtrip() {
+        let phrase: String = generate(18, MobileLanguage::English).unwrap();
+        let entropy: Vec<u8> = phrase_to_entropy(phrase.clone(), MobileLanguage::English).unwrap();
+
+        assert_eq!(entropy.len(), 24);
+        assert_eq!(
+            entropy_to_phrase(entropy, MobileLanguage::English).unwrap(),
+            phrase
+        );
+        assert!(supported_languages().contains(&MobileLanguage::English));
+
+        let seed: Vec<u8> = seed_bip39(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            MobileLanguage::English,
+            "TREZOR".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(&seed[..4], &[0xc5, 0x52, 0x57, 0xc3]);
+    }
+
+    #[test]
+    fn mobile_errors() {
+        let validation: PhraseValidation =
+            validate_detailed("abandon abandn".to_string(), MobileLanguage::English).unwrap();
+
+        assert!(!validation.is_valid);
+        assert_eq!(validation.invalid_words, vec![1]);
+
+        match validate(
+            "abandon ".repeat(12).trim().to_string(),
+            MobileLanguage::English,
+        ) {
+            Err(MobileError::InvalidChecksum { message }) => {
+                assert_eq!(message, "invalid checksum")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match generate(13, MobileLanguage::English) {
+            Err(MobileError::InvalidWordLength { .. }) => (),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
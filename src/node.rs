@@ -0,0 +1,180 @@
+//! Node.js bindings built with napi-rs, enabled by the `node` feature.
+//!
+//! `napi build --platform --release --features node` builds the native addon, for Electron
+//! wallets and Node.js services. Entropy and seeds are `Buffer`s: the copies made by the library
+//! are wiped as soon as they are dropped, as the feature enables `zeroize`, and `wipe()` wipes
+//! the buffers held by JavaScript once they are no longer needed. Phrases are JavaScript strings,
+//! which can't be wiped.
+//!
+//! ```js
+//! const { generate, seedBip39, wipe, Language } = require("keyphrase");
+//!
+//! const phrase = generate(24, Language.English);
+//! const seed = seedBip39(phrase, Language.English, "");
+//!
+//! // ... derive the keys, then
+//! wipe(seed);
+//! ```
+//!
+//! Errors are thrown as JavaScript `Error`s with the message of their `ErrorKind`.
+
+use crate::error::Error as KeyPhraseError;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use zeroize::Zeroize;
+
+/// The languages of [`Language`][Language], with their stable codes
+///
+/// Every language is listed, but those whose feature isn't enabled throw an error when used.
+///
+/// [Language]: ../language/enum.Language.html
+#[napi(js_name = "Language")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum NodeLanguage {
+    English = 0,
+    ChineseSimplified = 1,
+    ChineseTraditional = 2,
+    French = 3,
+    Italian = 4,
+    Japanese = 5,
+    Korean = 6,
+    Spanish = 7,
+}
+
+impl NodeLanguage {
+    fn to_language(self) -> Result<Language> {
+        let code: u8 = match self {
+            NodeLanguage::English => 0,
+            NodeLanguage::ChineseSimplified => 1,
+            NodeLanguage::ChineseTraditional => 2,
+            NodeLanguage::French => 3,
+            NodeLanguage::Italian => 4,
+            NodeLanguage::Japanese => 5,
+            NodeLanguage::Korean => 6,
+            NodeLanguage::Spanish => 7,
+        };
+
+        Language::from_code(code)
+            .ok_or_else(|| Error::new(Status::InvalidArg, "unsupported language"))
+    }
+}
+
+/// Generate a new phrase of 12, 15, 18, 21 or 24 words
+#[napi]
+pub fn generate(word_count: u32, language: NodeLanguage) -> Result<String> {
+    let keyphrase_type: KeyPhraseType =
+        KeyPhraseType::for_word_count(word_count as usize).map_err(node_error)?;
+
+    KeyPhrase::try_new(keyphrase_type, language.to_language()?)
+        .map(KeyPhrase::into_phrase)
+        .map_err(node_error)
+}
+
+/// Check that a phrase is a valid keyphrase, throwing the first problem found
+#[napi]
+pub fn validate(phrase: String, language: NodeLanguage) -> Result<()> {
+    KeyPhrase::validate(&phrase, language.to_language()?).map_err(node_error)
+}
+
+/// Get the entropy of a phrase
+#[napi]
+pub fn phrase_to_entropy(phrase: String, language: NodeLanguage) -> Result<Buffer> {
+    KeyPhrase::from_phrase(phrase, language.to_language()?)
+        .map(|keyphrase: KeyPhrase| keyphrase.into_entropy().into())
+        .map_err(node_error)
+}
+
+/// Get the phrase of 16 to 32 bytes of entropy
+#[napi]
+pub fn entropy_to_phrase(entropy: Buffer, language: NodeLanguage) -> Result<String> {
+    KeyPhrase::from_entropy(&entropy, language.to_language()?)
+        .map(KeyPhrase::into_phrase)
+        .map_err(node_error)
+}
+
+/// Derive the 64 bytes BIP39 seed of a phrase, like any BIP39 wallet
+#[napi]
+pub fn seed_bip39(phrase: String, language: NodeLanguage, password: String) -> Result<Buffer> {
+    derive_seed(
+        phrase,
+        language,
+        password,
+        |keyphrase: &KeyPhrase, password: &str| Seed::new_bip39(keyphrase, password),
+    )
+}
+
+/// Derive the 64 bytes legacy seed of a phrase, salted with `"keyphrase"`, as `Seed::new()` does
+#[cfg(not(feature = "strict-bip39-only"))]
+#[napi]
+pub fn seed_legacy(phrase: String, language: NodeLanguage, password: String) -> Result<Buffer> {
+    derive_seed(
+        phrase,
+        language,
+        password,
+        |keyphrase: &KeyPhrase, password: &str| Seed::new(keyphrase, password),
+    )
+}
+
+/// Wipe a `Buffer` of entropy or seed once it is no longer needed
+#[napi]
+pub fn wipe(buffer: Buffer) {
+    let mut buffer: Buffer = buffer;
+
+    (*buffer).zeroize();
+}
+
+/// Derive a seed with `derive`, wiping the password afterwards
+fn derive_seed<F>(
+    phrase: String,
+    language: NodeLanguage,
+    mut password: String,
+    derive: F,
+) -> Result<Buffer>
+where
+    F: Fn(&KeyPhrase, &str) -> Seed,
+{
+    let seed: Result<Buffer> = KeyPhrase::from_phrase(phrase, language.to_language()?)
+        .map(|keyphrase: KeyPhrase| derive(&keyphrase, &password).as_bytes().to_vec().into())
+        .map_err(node_error);
+
+    password.zeroize();
+
+    seed
+}
+
+/// Turn an error into a JavaScript `Error` with its message
+fn node_error(error: KeyPhraseError) -> Error {
+    Error::new(Status::InvalidArg, error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn node_roundtrip() {
+        let phrase: String = generate(12, NodeLanguage::English).unwrap();
+        let entropy: Buffer = phrase_to_entropy(phrase.clone(), NodeLanguage::English).unwrap();
+
+        assert_eq!(entropy.len(), 16);
+        assert_eq!(
+            entropy_to_phrase(entropy, NodeLanguage::English).unwrap(),
+            phrase
+        );
+
+        let seed: Buffer = seed_bip39(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            NodeLanguage::English,
+            "TREZOR".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(&seed[..4], &[0xc5, 0x52, 0x57, 0xc3]);
+        assert!(validate(phrase, NodeLanguage::English).is_ok());
+        assert!(generate(11, NodeLanguage::English).is_err());
+    }
+}
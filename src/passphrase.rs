@@ -0,0 +1,132 @@
+//! The password protecting a [`Seed`][Seed], kept out of logs and wiped when dropped.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+
+#[cfg(feature = "secrecy")]
+use secrecy::{ExposeSecret, SecretString};
+use std::fmt;
+#[cfg(all(feature = "zeroize", not(feature = "secrecy")))]
+use zeroize::Zeroize;
+
+/// The password used with a [`KeyPhrase`][KeyPhrase] to derive a [`Seed`][Seed]
+///
+/// Every seed derivation accepts a `Passphrase`, or anything that converts into one like `&str`
+/// and `String`. Its `Debug` output is redacted and, with the `zeroize` feature, it is wiped
+/// when dropped. With the `secrecy` feature it wraps a `secrecy::SecretString`, so a password
+/// already held in one never has to be exposed by the caller.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Passphrase, Seed};
+///
+/// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+/// let passphrase = Passphrase::new("correct horse battery staple");
+///
+/// assert_eq!(format!("{:?}", passphrase), "Passphrase(..)");
+///
+/// let seed = Seed::new_bip39(&keyphrase, &passphrase);
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [Seed]: ../seed/struct.Seed.html
+#[derive(Clone)]
+pub struct Passphrase {
+    #[cfg(not(feature = "secrecy"))]
+    inner: String,
+    #[cfg(feature = "secrecy")]
+    inner: SecretString,
+}
+
+impl Passphrase {
+    /// Create a passphrase, taking ownership of the password
+    pub fn new<S: Into<String>>(password: S) -> Passphrase {
+        Passphrase {
+            #[cfg(not(feature = "secrecy"))]
+            inner: password.into(),
+            #[cfg(feature = "secrecy")]
+            inner: SecretString::new(password.into()),
+        }
+    }
+
+    /// The empty passphrase, used when the keyphrase is not protected by a password
+    pub fn empty() -> Passphrase {
+        Passphrase::new(String::new())
+    }
+
+    /// Get the password, only meant to be passed on to the derivation itself
+    pub fn expose_secret(&self) -> &str {
+        #[cfg(not(feature = "secrecy"))]
+        let password: &str = &self.inner;
+        #[cfg(feature = "secrecy")]
+        let password: &str = self.inner.expose_secret();
+
+        password
+    }
+}
+
+impl Default for Passphrase {
+    fn default() -> Passphrase {
+        Passphrase::empty()
+    }
+}
+
+impl<'a> From<&'a str> for Passphrase {
+    fn from(password: &'a str) -> Passphrase {
+        Passphrase::new(password)
+    }
+}
+
+impl From<String> for Passphrase {
+    fn from(password: String) -> Passphrase {
+        Passphrase::new(password)
+    }
+}
+
+/// Makes a copy, which is wiped like the original
+impl<'a> From<&'a Passphrase> for Passphrase {
+    fn from(passphrase: &'a Passphrase) -> Passphrase {
+        passphrase.clone()
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl From<SecretString> for Passphrase {
+    fn from(password: SecretString) -> Passphrase {
+        Passphrase { inner: password }
+    }
+}
+
+/// `secrecy::SecretString` wipes itself when dropped
+#[cfg(all(feature = "zeroize", not(feature = "secrecy")))]
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.inner.zeroize();
+    }
+}
+
+/// The password is never printed
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn passphrase_conversions() {
+        let passphrase: Passphrase = Passphrase::new("TREZOR");
+
+        assert_eq!(passphrase.expose_secret(), "TREZOR");
+        assert_eq!(Passphrase::from(&passphrase).expose_secret(), "TREZOR");
+        assert_eq!(
+            Passphrase::from("TREZOR".to_string()).expose_secret(),
+            "TREZOR"
+        );
+        assert_eq!(Passphrase::default().expose_secret(), "");
+        assert!(!format!("{:?}", passphrase).contains("TREZOR"));
+    }
+}
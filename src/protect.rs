@@ -0,0 +1,254 @@
+//! Passphrase protected [`KeyPhrase`][KeyPhrase]s, in the spirit of BIP38, enabled by the
+//! `protect` feature.
+//!
+//! The entropy of the keyphrase is encrypted with the passphrase and written as another phrase
+//! of the same length and language, with a valid checksum, after a version word. Whoever finds
+//! the protected phrase on paper only has a phrase of an empty wallet: the keyphrase can't be
+//! recovered without the passphrase.
+//!
+//! The encryption is a 4 rounds Feistel network with PBKDF2-HMAC-SHA256 as round function, like
+//! SLIP-39, so the protected phrase is exactly as long as the keyphrase. The version word holds
+//! the version of the scheme and an 8 bits tag of the keyphrase and passphrase, which catches
+//! all but 1 in 256 wrong passphrases.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{protect, KeyPhrase, KeyPhraseType, Language};
+//!
+//! let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+//! let protected = protect::encrypt(&keyphrase, "correct horse battery staple").unwrap();
+//!
+//! assert_eq!(protected.split(' ').count(), 13);
+//!
+//! let decrypted = protect::decrypt(&protected, "correct horse battery staple", Language::English)
+//!     .unwrap();
+//!
+//! assert_eq!(decrypted.phrase(), keyphrase.phrase());
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::{hmac_sha512, pbkdf2_sha256};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::passphrase::Passphrase;
+use crate::util::{Bits, Bits11};
+use std::mem;
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Version of the scheme, in the upper 3 bits of the version word
+const VERSION: u16 = 1;
+
+/// Domain separation of the round function and of the tag
+const CUSTOMIZATION: &[u8] = b"keyphrase-protect";
+
+/// Rounds of the Feistel network
+const FEISTEL_ROUNDS: u8 = 4;
+
+/// PBKDF2 iterations of every round
+const ROUND_ITERATIONS: usize = 16_384;
+
+/// Encrypt `keyphrase` with `passphrase` into a protected phrase
+///
+/// The passphrase is NFKD normalized, like BIP39 passwords. The protected phrase has one more
+/// word than the keyphrase and is in the same language.
+pub fn encrypt<P>(keyphrase: &KeyPhrase, passphrase: P) -> Result<String, Error>
+where
+    P: Into<Passphrase>,
+{
+    #[allow(unused_mut)]
+    let mut passphrase: String = passphrase.into().expose_secret().nfkd().collect();
+    let lang: Language = keyphrase.language();
+
+    #[allow(unused_mut)]
+    let mut encrypted: Vec<u8> = feistel(keyphrase.entropy(), passphrase.as_bytes(), true);
+    let version: Bits11 =
+        Bits11::from(VERSION << 8 | u16::from(tag(keyphrase.entropy(), &passphrase)));
+    let protected: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&encrypted, lang);
+
+    #[cfg(feature = "zeroize")]
+    {
+        passphrase.zeroize();
+        encrypted.zeroize();
+    }
+
+    Ok(format!(
+        "{} {}",
+        lang.wordlist().get_word(version),
+        protected?.phrase()
+    ))
+}
+
+/// Decrypt a phrase protected with [`encrypt()`][encrypt()]
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidPassword` if the passphrase is wrong,
+/// `ErrorKind::InvalidEncoding` if the version is unknown, or the errors of
+/// [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()] if the words after the version word
+/// aren't a valid phrase.
+///
+/// [encrypt()]: ./fn.encrypt.html
+/// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+pub fn decrypt<P>(protected: &str, passphrase: P, lang: Language) -> Result<KeyPhrase, Error>
+where
+    P: Into<Passphrase>,
+{
+    let protected: String = protected.nfkd().collect();
+    let (version_word, phrase): (&str, &str) = protected
+        .trim()
+        .split_once(' ')
+        .ok_or(ErrorKind::InvalidEncoding("missing version word"))?;
+    let version: u16 = lang.wordmap().get_bits(version_word)?.bits() as u16;
+
+    if version >> 8 != VERSION {
+        Err(ErrorKind::InvalidEncoding(
+            "unsupported protected phrase version",
+        ))?;
+    }
+
+    let encrypted: KeyPhrase = KeyPhrase::from_phrase(phrase, lang)?;
+
+    #[allow(unused_mut)]
+    let mut passphrase: String = passphrase.into().expose_secret().nfkd().collect();
+    #[allow(unused_mut)]
+    let mut entropy: Vec<u8> = feistel(encrypted.entropy(), passphrase.as_bytes(), false);
+
+    let keyphrase: Result<KeyPhrase, Error> = match KeyPhrase::from_entropy(&entropy, lang) {
+        Ok(keyphrase) if u16::from(tag(&entropy, &passphrase)) == version & 0xff => Ok(keyphrase),
+        _ => Err(ErrorKind::InvalidPassword.into()),
+    };
+
+    #[cfg(feature = "zeroize")]
+    {
+        passphrase.zeroize();
+        entropy.zeroize();
+    }
+
+    keyphrase
+}
+
+/// Compute the tag of the version word, binding the entropy to the passphrase
+fn tag(entropy: &[u8], passphrase: &str) -> u8 {
+    let mut input: Vec<u8> = CUSTOMIZATION.to_vec();
+
+    input.extend_from_slice(entropy);
+
+    let tag: u8 = hmac_sha512(passphrase.as_bytes(), &input)[0];
+
+    #[cfg(feature = "zeroize")]
+    input.zeroize();
+
+    tag
+}
+
+/// Encrypt or decrypt the entropy with the Feistel network keyed by the passphrase
+fn feistel(entropy: &[u8], passphrase: &[u8], encrypt: bool) -> Vec<u8> {
+    let half: usize = entropy.len() / 2;
+    let mut left: Vec<u8> = entropy[..half].to_vec();
+    let mut right: Vec<u8> = entropy[half..].to_vec();
+
+    for round in 0..FEISTEL_ROUNDS {
+        let step: u8 = if encrypt {
+            round
+        } else {
+            FEISTEL_ROUNDS - 1 - round
+        };
+        let mut password: Vec<u8> = vec![step];
+        password.extend_from_slice(passphrase);
+        let mut salt: Vec<u8> = CUSTOMIZATION.to_vec();
+        salt.extend_from_slice(&right);
+
+        let mut output: Vec<u8> = vec![0; half];
+        pbkdf2_sha256(&password, &salt, ROUND_ITERATIONS, &mut output);
+
+        left.iter_mut()
+            .zip(&output)
+            .for_each(|(byte, output): (&mut u8, &u8)| *byte ^= output);
+        mem::swap(&mut left, &mut right);
+
+        #[cfg(feature = "zeroize")]
+        {
+            password.zeroize();
+            output.zeroize();
+        }
+    }
+
+    right.extend_from_slice(&left);
+
+    #[cfg(feature = "zeroize")]
+    left.zeroize();
+
+    right
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+
+    #[test]
+    fn protect_round_trip() {
+        [KeyPhraseType::Words12, KeyPhraseType::Words24]
+            .iter()
+            .for_each(|&keyphrase_type: &KeyPhraseType| {
+                let keyphrase: KeyPhrase = KeyPhrase::new(keyphrase_type, Language::English);
+                let protected: String = encrypt(&keyphrase, "passphrase").unwrap();
+                let words: Vec<&str> = protected.split(' ').collect();
+
+                assert_eq!(words.len(), keyphrase_type.word_count() + 1);
+
+                // the words after the version word are a valid phrase of another wallet
+                let decoy: KeyPhrase =
+                    KeyPhrase::from_phrase(words[1..].join(" "), Language::English).unwrap();
+                assert_ne!(decoy.phrase(), keyphrase.phrase());
+
+                assert_eq!(
+                    decrypt(&protected, "passphrase", Language::English)
+                        .unwrap()
+                        .phrase(),
+                    keyphrase.phrase()
+                );
+            });
+    }
+
+    #[test]
+    fn protect_deterministic() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+
+        let protected: String = encrypt(&keyphrase, "TREZOR").unwrap();
+
+        assert_eq!(encrypt(&keyphrase, "TREZOR").unwrap(), protected);
+        assert_ne!(encrypt(&keyphrase, "TREZOR!").unwrap(), protected);
+    }
+
+    #[test]
+    fn protect_wrong_passphrase() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            Language::English,
+        )
+        .unwrap();
+        let protected: String = encrypt(&keyphrase, "passphrase").unwrap();
+
+        assert!(decrypt(&protected, "Passphrase", Language::English).is_err());
+        assert!(decrypt(&protected, "", Language::English).is_err());
+
+        // unknown version, or no version word at all
+        let words: Vec<&str> = protected.split(' ').collect();
+
+        assert!(decrypt(&words[1..].join(" "), "passphrase", Language::English).is_err());
+        assert!(decrypt(
+            &(String::from("abandon ") + &words[1..].join(" ")),
+            "passphrase",
+            Language::English
+        )
+        .is_err());
+    }
+}
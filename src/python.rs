@@ -0,0 +1,320 @@
+//! Python bindings built with PyO3, enabled by the `python` feature.
+//!
+//! `maturin build --release` builds the `keyphrase` Python module, with the `KeyPhrase`, `Seed`
+//! and `Language` classes and a `validate()` function. Errors are raised as subclasses of
+//! `keyphrase.KeyPhraseError`, itself a `ValueError`, after their `ErrorKind`.
+//!
+//! ```python
+//! from keyphrase import KeyPhrase, Language, Seed
+//!
+//! keyphrase = KeyPhrase(24, Language.English)
+//! seed = Seed.bip39(keyphrase, "")
+//!
+//! print(keyphrase.phrase, seed.hex())
+//! ```
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+create_exception!(
+    keyphrase,
+    KeyPhraseError,
+    PyValueError,
+    "Base class of the errors of the keyphrase module"
+);
+create_exception!(
+    keyphrase,
+    InvalidChecksumError,
+    KeyPhraseError,
+    "The phrase doesn't match its checksum"
+);
+create_exception!(
+    keyphrase,
+    InvalidWordError,
+    KeyPhraseError,
+    "A word isn't in the wordlist"
+);
+create_exception!(
+    keyphrase,
+    InvalidWordLengthError,
+    KeyPhraseError,
+    "The number of words isn't 12, 15, 18, 21 or 24"
+);
+create_exception!(
+    keyphrase,
+    InvalidKeysizeError,
+    KeyPhraseError,
+    "The entropy isn't 16, 20, 24, 28 or 32 bytes"
+);
+create_exception!(
+    keyphrase,
+    UnsupportedLanguageError,
+    KeyPhraseError,
+    "The language isn't enabled in this build"
+);
+
+/// The languages of [`Language`][Language], with their stable codes
+///
+/// Every language is listed, but those whose feature isn't enabled raise
+/// `UnsupportedLanguageError` when used.
+///
+/// [Language]: ../language/enum.Language.html
+#[pyclass(name = "Language")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyLanguage {
+    English = 0,
+    ChineseSimplified = 1,
+    ChineseTraditional = 2,
+    French = 3,
+    Italian = 4,
+    Japanese = 5,
+    Korean = 6,
+    Spanish = 7,
+}
+
+impl PyLanguage {
+    fn to_language(self) -> PyResult<Language> {
+        Language::from_code(self as u8)
+            .ok_or_else(|| py_error(ErrorKind::UnsupportedLanguage.into()))
+    }
+
+    fn from_language(lang: Language) -> PyLanguage {
+        match lang.code() {
+            1 => PyLanguage::ChineseSimplified,
+            2 => PyLanguage::ChineseTraditional,
+            3 => PyLanguage::French,
+            4 => PyLanguage::Italian,
+            5 => PyLanguage::Japanese,
+            6 => PyLanguage::Korean,
+            7 => PyLanguage::Spanish,
+            _ => PyLanguage::English,
+        }
+    }
+}
+
+/// A [`KeyPhrase`][KeyPhrase] for Python
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+#[pyclass(name = "KeyPhrase")]
+pub struct PyKeyPhrase {
+    keyphrase: KeyPhrase,
+}
+
+#[pymethods]
+impl PyKeyPhrase {
+    /// Generate a new keyphrase of 12, 15, 18, 21 or 24 words
+    #[new]
+    #[pyo3(signature = (word_count = 12, language = PyLanguage::English))]
+    fn new(word_count: usize, language: PyLanguage) -> PyResult<PyKeyPhrase> {
+        let keyphrase_type: KeyPhraseType =
+            KeyPhraseType::for_word_count(word_count).map_err(py_error)?;
+        let keyphrase: KeyPhrase =
+            KeyPhrase::try_new(keyphrase_type, language.to_language()?).map_err(py_error)?;
+
+        Ok(PyKeyPhrase { keyphrase })
+    }
+
+    /// Create a keyphrase from an existing phrase, checking its checksum
+    #[staticmethod]
+    #[pyo3(signature = (phrase, language = PyLanguage::English))]
+    fn from_phrase(phrase: &str, language: PyLanguage) -> PyResult<PyKeyPhrase> {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_phrase(phrase, language.to_language()?).map_err(py_error)?;
+
+        Ok(PyKeyPhrase { keyphrase })
+    }
+
+    /// Create a keyphrase from its entropy, 16 to 32 bytes
+    #[staticmethod]
+    #[pyo3(signature = (entropy, language = PyLanguage::English))]
+    fn from_entropy(entropy: &[u8], language: PyLanguage) -> PyResult<PyKeyPhrase> {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy(entropy, language.to_language()?).map_err(py_error)?;
+
+        Ok(PyKeyPhrase { keyphrase })
+    }
+
+    /// The words of the keyphrase, separated by spaces
+    #[getter]
+    fn phrase(&self) -> &str {
+        self.keyphrase.phrase()
+    }
+
+    /// The entropy of the keyphrase
+    #[getter]
+    fn entropy<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, self.keyphrase.entropy())
+    }
+
+    /// The language of the keyphrase
+    #[getter]
+    fn language(&self) -> PyLanguage {
+        PyLanguage::from_language(self.keyphrase.language())
+    }
+
+    /// The redacted `Debug` output, so that the phrase doesn't end up in logs
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.keyphrase)
+    }
+}
+
+/// A [`Seed`][Seed] for Python
+///
+/// [Seed]: ../seed/struct.Seed.html
+#[pyclass(name = "Seed")]
+pub struct PySeed {
+    seed: Seed,
+}
+
+#[pymethods]
+impl PySeed {
+    /// Derive the seed of a keyphrase as defined by BIP39, like any BIP39 wallet
+    #[staticmethod]
+    #[pyo3(signature = (keyphrase, password = ""))]
+    fn bip39(keyphrase: &PyKeyPhrase, password: &str) -> PySeed {
+        PySeed {
+            seed: Seed::new_bip39(&keyphrase.keyphrase, password),
+        }
+    }
+
+    /// Derive the legacy seed of a keyphrase, salted with `"keyphrase"`, as `Seed::new()` does
+    #[cfg(not(feature = "strict-bip39-only"))]
+    #[staticmethod]
+    #[pyo3(signature = (keyphrase, password = ""))]
+    fn legacy(keyphrase: &PyKeyPhrase, password: &str) -> PySeed {
+        PySeed {
+            seed: Seed::new(&keyphrase.keyphrase, password),
+        }
+    }
+
+    /// The 64 bytes of the seed
+    #[getter]
+    fn bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, self.seed.as_bytes())
+    }
+
+    /// The seed as lowercase hex
+    fn hex(&self) -> String {
+        self.seed.to_hex()
+    }
+
+    /// The redacted `Debug` output
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.seed)
+    }
+}
+
+/// Check that a phrase is a valid keyphrase, raising the first problem found
+#[pyfunction]
+#[pyo3(signature = (phrase, language = PyLanguage::English))]
+fn validate(phrase: &str, language: PyLanguage) -> PyResult<()> {
+    KeyPhrase::validate(phrase, language.to_language()?).map_err(py_error)
+}
+
+/// The `keyphrase` Python module
+#[pymodule]
+fn keyphrase(py: Python, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyLanguage>()?;
+    module.add_class::<PyKeyPhrase>()?;
+    module.add_class::<PySeed>()?;
+    module.add_function(wrap_pyfunction!(validate, module)?)?;
+    module.add("KeyPhraseError", py.get_type::<KeyPhraseError>())?;
+    module.add(
+        "InvalidChecksumError",
+        py.get_type::<InvalidChecksumError>(),
+    )?;
+    module.add("InvalidWordError", py.get_type::<InvalidWordError>())?;
+    module.add(
+        "InvalidWordLengthError",
+        py.get_type::<InvalidWordLengthError>(),
+    )?;
+    module.add("InvalidKeysizeError", py.get_type::<InvalidKeysizeError>())?;
+    module.add(
+        "UnsupportedLanguageError",
+        py.get_type::<UnsupportedLanguageError>(),
+    )?;
+
+    Ok(())
+}
+
+/// Raise an error as the exception of its `ErrorKind`, with its message
+fn py_error(error: Error) -> PyErr {
+    let message: String = error.to_string();
+
+    match error.kind() {
+        ErrorKind::InvalidChecksum => InvalidChecksumError::new_err(message),
+        ErrorKind::InvalidWord { .. } | ErrorKind::InvalidNormalization { .. } => {
+            InvalidWordError::new_err(message)
+        }
+        ErrorKind::InvalidWordLength { .. } => InvalidWordLengthError::new_err(message),
+        ErrorKind::InvalidKeysize(_) | ErrorKind::InvalidEntropyLength(..) => {
+            InvalidKeysizeError::new_err(message)
+        }
+        ErrorKind::UnsupportedLanguage => UnsupportedLanguageError::new_err(message),
+        _ => KeyPhraseError::new_err(message),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn python_errors() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py: Python| {
+            let checksum: PyErr = py_error(ErrorKind::InvalidChecksum.into());
+            let length: PyErr = PyKeyPhrase::from_phrase("abandon about", PyLanguage::English)
+                .err()
+                .unwrap();
+            let other: PyErr = py_error(ErrorKind::InvalidPassword.into());
+
+            assert!(checksum.is_instance_of::<InvalidChecksumError>(py));
+            assert!(checksum.is_instance_of::<KeyPhraseError>(py));
+            assert!(checksum.is_instance_of::<PyValueError>(py));
+            assert!(length.is_instance_of::<InvalidWordLengthError>(py));
+            assert!(!other.is_instance_of::<InvalidChecksumError>(py));
+            assert!(other.is_instance_of::<KeyPhraseError>(py));
+            assert!(PyKeyPhrase::from_entropy(&[0; 15], PyLanguage::English)
+                .err()
+                .unwrap()
+                .is_instance_of::<InvalidKeysizeError>(py));
+            assert!(validate("abandon abandon abandon", PyLanguage::English).is_err());
+        });
+    }
+
+    #[test]
+    fn python_keyphrase_seed() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py: Python| {
+            let keyphrase: PyKeyPhrase =
+                PyKeyPhrase::from_phrase(PHRASE, PyLanguage::English).unwrap();
+            let entropy: &PyBytes = keyphrase.entropy(py);
+
+            assert_eq!(entropy.as_bytes(), &[0; 16][..]);
+            assert_eq!(keyphrase.language(), PyLanguage::English);
+            assert_eq!(
+                PyKeyPhrase::from_entropy(entropy.as_bytes(), PyLanguage::English)
+                    .unwrap()
+                    .phrase(),
+                PHRASE
+            );
+            assert_eq!(
+                PySeed::bip39(&keyphrase, "TREZOR").hex(),
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+            );
+            assert!(validate(PHRASE, PyLanguage::English).is_ok());
+        });
+    }
+}
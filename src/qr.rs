@@ -0,0 +1,359 @@
+//! QR codes of [`KeyPhrase`][KeyPhrase]s and [`Seed`][Seed]s, enabled by the `qr` feature.
+//!
+//! A [`QrMatrix`][QrMatrix] is the grid of modules of a QR code, which apps can draw however they
+//! like, or write as SVG or PNG with a quiet zone of 4 modules. Payloads are encoded in the
+//! densest QR mode that fits them: the digits of a SeedQR in numeric mode, an uppercase UR in
+//! alphanumeric mode and anything else in byte mode.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::qr::QrPayload;
+//! use keyphrase::{KeyPhrase, Language};
+//!
+//! let phrase = "forum undo fragile fade shy sign arrest garment culture tube off merit";
+//! let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+//! let qr = keyphrase.to_qr(QrPayload::SeedQr).unwrap();
+//!
+//! assert!(qr.width() >= 21);
+//! assert!(qr.to_svg(8).starts_with("<svg"));
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//! [Seed]: ../seed/struct.Seed.html
+//! [QrMatrix]: ./struct.QrMatrix.html
+
+use crate::bytewords::crc32;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::seed::Seed;
+use qrcode::{Color, EcLevel, QrCode};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Light modules around the code, as required by the QR specification
+const QUIET_ZONE: usize = 4;
+
+/// Largest block of uncompressed deflate data
+const STORED_BLOCK_BYTES: usize = 0xffff;
+
+/// The payloads a keyphrase can be written as in a QR code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QrPayload {
+    /// The phrase itself, as text
+    Phrase,
+    /// The digits of a standard SeedQR, only for English keyphrases of 12 or 24 words
+    SeedQr,
+    /// The bytes of a CompactSeedQR, only for English keyphrases of 12 or 24 words
+    CompactSeedQr,
+    /// The `ur:crypto-seed` string of the entropy, in uppercase
+    Ur,
+}
+
+/// The modules of a QR code, without quiet zone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Encode `data` in a QR code with the error correction level `ec_level`
+    fn encode(data: &[u8], ec_level: EcLevel) -> Result<QrMatrix, Error> {
+        let code: QrCode = QrCode::with_error_correction_level(data, ec_level)
+            .map_err(|_| ErrorKind::InvalidEncoding("payload too long for a QR code"))?;
+
+        Ok(QrMatrix {
+            width: code.width(),
+            modules: code
+                .to_colors()
+                .into_iter()
+                .map(|color: Color| color == Color::Dark)
+                .collect(),
+        })
+    }
+
+    /// Get the number of modules of a side of the code
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Check if the module at column `x` and row `y` is dark
+    ///
+    /// Panics if `x` or `y` is not less than [`QrMatrix::width()`][QrMatrix::width()].
+    ///
+    /// [QrMatrix::width()]: ./struct.QrMatrix.html#method.width
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.width && y < self.width, "module out of the code");
+
+        self.modules[y * self.width + x]
+    }
+
+    /// Write the code as an SVG image, `module_size` pixels per module
+    ///
+    /// Runs of dark modules are drawn as a single path on a white background.
+    pub fn to_svg(&self, module_size: u32) -> String {
+        let size: usize = self.width + 2 * QUIET_ZONE;
+        let pixels: usize = size * module_size as usize;
+        let mut path: String = String::new();
+
+        for y in 0..self.width {
+            let mut x: usize = 0;
+
+            while x < self.width {
+                let run: usize = (x..self.width)
+                    .take_while(|&x: &usize| self.is_dark(x, y))
+                    .count();
+
+                if run > 0 {
+                    path.push_str(&format!(
+                        "M{} {}h{}v1h-{}z",
+                        x + QUIET_ZONE,
+                        y + QUIET_ZONE,
+                        run,
+                        run
+                    ));
+                }
+
+                x += run.max(1);
+            }
+        }
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\"><rect width=\"{}\" height=\"{}\" fill=\"#fff\"/><path d=\"{}\" fill=\"#000\"/></svg>",
+            pixels, pixels, size, size, size, size, path
+        )
+    }
+
+    /// Write the code as a black and white PNG image, `module_size` pixels per module
+    ///
+    /// The image is 1 bit grayscale and stored without compression, which is still small at the
+    /// sizes of keyphrase payloads.
+    ///
+    /// Panics if `module_size` is 0.
+    pub fn to_png(&self, module_size: u32) -> Vec<u8> {
+        assert!(module_size > 0, "modules are at least 1 pixel");
+
+        let pixels: usize = (self.width + 2 * QUIET_ZONE) * module_size as usize;
+        let row_bytes: usize = pixels.div_ceil(8);
+        let mut raw: Vec<u8> = Vec::with_capacity((row_bytes + 1) * pixels);
+
+        for py in 0..pixels {
+            let y: usize = py / module_size as usize;
+
+            // filter type none, then the pixels, 1 for white
+            raw.push(0);
+            raw.extend((0..row_bytes).map(|byte: usize| {
+                (0..8).fold(0, |bits: u8, bit: usize| {
+                    let px: usize = byte * 8 + bit;
+                    let white: bool =
+                        px >= pixels || !self.is_dark_padded(px / module_size as usize, y);
+
+                    bits | (white as u8) << (7 - bit)
+                })
+            }));
+        }
+
+        let mut ihdr: Vec<u8> = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(pixels as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(pixels as u32).to_be_bytes());
+        // 1 bit grayscale, deflate, adaptive filtering, no interlace
+        ihdr.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+        let mut png: Vec<u8> = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        write_chunk(&mut png, b"IHDR", &ihdr);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+
+    /// Check if the module at column `x` and row `y`, counting the quiet zone, is dark
+    fn is_dark_padded(&self, x: usize, y: usize) -> bool {
+        (QUIET_ZONE..QUIET_ZONE + self.width).contains(&x)
+            && (QUIET_ZONE..QUIET_ZONE + self.width).contains(&y)
+            && self.is_dark(x - QUIET_ZONE, y - QUIET_ZONE)
+    }
+}
+
+impl KeyPhrase {
+    /// Get a QR code of the keyphrase with the given payload
+    ///
+    /// SeedQRs use the low error correction level of their specification, the other payloads
+    /// the medium level. Returns the errors of
+    /// [`KeyPhrase::to_seedqr_digits()`][KeyPhrase::to_seedqr_digits()] for SeedQR payloads of
+    /// keyphrases which don't have one.
+    ///
+    /// Whoever sees the code has the keyphrase: only show it to the device that needs it.
+    ///
+    /// [KeyPhrase::to_seedqr_digits()]: ../keyphrase/struct.KeyPhrase.html#method.to_seedqr_digits
+    pub fn to_qr(&self, payload: QrPayload) -> Result<QrMatrix, Error> {
+        #[allow(unused_mut)]
+        let mut data: Vec<u8> = match payload {
+            QrPayload::Phrase => self.phrase().as_bytes().to_vec(),
+            QrPayload::SeedQr => self.to_seedqr_digits()?.into_bytes(),
+            QrPayload::CompactSeedQr => self.to_compact_seedqr_bytes()?,
+            QrPayload::Ur => self.to_ur_crypto_seed().to_uppercase().into_bytes(),
+        };
+        let ec_level: EcLevel = match payload {
+            QrPayload::SeedQr | QrPayload::CompactSeedQr => EcLevel::L,
+            QrPayload::Phrase | QrPayload::Ur => EcLevel::M,
+        };
+        let qr: Result<QrMatrix, Error> = QrMatrix::encode(&data, ec_level);
+
+        #[cfg(feature = "zeroize")]
+        data.zeroize();
+
+        qr
+    }
+}
+
+impl Seed {
+    /// Get a QR code of the seed, as uppercase hex
+    ///
+    /// Whoever sees the code has the seed: only show it to the device that needs it.
+    pub fn to_qr(&self) -> QrMatrix {
+        #[allow(unused_mut)]
+        let mut hex: String = format!("{:X}", self);
+        let qr: QrMatrix =
+            QrMatrix::encode(hex.as_bytes(), EcLevel::M).expect("a seed fits in a QR code");
+
+        #[cfg(feature = "zeroize")]
+        hex.zeroize();
+
+        qr
+    }
+}
+
+/// Append a PNG chunk and its checksum
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut chunk: Vec<u8> = kind.to_vec();
+
+    chunk.extend_from_slice(data);
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(&chunk);
+    png.extend_from_slice(&crc32(&chunk).to_be_bytes());
+}
+
+/// Wrap data in a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks: usize = data.len().div_ceil(STORED_BLOCK_BYTES).max(1);
+    let mut zlib: Vec<u8> = Vec::with_capacity(data.len() + 5 * blocks + 6);
+
+    // deflate with a 32K window, no dictionary, fastest compression
+    zlib.extend_from_slice(&[0x78, 0x01]);
+
+    for i in 0..blocks {
+        let block: &[u8] =
+            &data[i * STORED_BLOCK_BYTES..((i + 1) * STORED_BLOCK_BYTES).min(data.len())];
+
+        zlib.push((i + 1 == blocks) as u8);
+        zlib.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+
+    let (a, b): (u32, u32) = data.iter().fold((1, 0), |(a, b): (u32, u32), &byte: &u8| {
+        let a: u32 = (a + u32::from(byte)) % 65521;
+
+        (a, (b + a) % 65521)
+    });
+
+    zlib.extend_from_slice(&(b << 16 | a).to_be_bytes());
+
+    zlib
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+    use crate::language::Language;
+    use std::convert::TryFrom;
+
+    /// Check the 3 finder patterns of a QR code, which every reader looks for
+    fn assert_finder_patterns(qr: &QrMatrix) {
+        let width: usize = qr.width();
+
+        assert!(width >= 21 && (width - 17) % 4 == 0);
+
+        [(0, 0), (width - 7, 0), (0, width - 7)]
+            .iter()
+            .for_each(|&(x, y): &(usize, usize)| {
+                assert!(qr.is_dark(x, y) && qr.is_dark(x + 6, y + 6));
+                assert!(qr.is_dark(x + 3, y + 3) && !qr.is_dark(x + 1, y + 1));
+            });
+    }
+
+    #[test]
+    fn keyphrase_to_qr() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "forum undo fragile fade shy sign arrest garment culture tube off merit",
+            Language::English,
+        )
+        .unwrap();
+
+        [
+            QrPayload::Phrase,
+            QrPayload::SeedQr,
+            QrPayload::CompactSeedQr,
+            QrPayload::Ur,
+        ]
+        .iter()
+        .for_each(|&payload: &QrPayload| {
+            assert_finder_patterns(&keyphrase.to_qr(payload).unwrap());
+        });
+
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words15, Language::English);
+
+        assert!(keyphrase.to_qr(QrPayload::Phrase).is_ok());
+        assert!(keyphrase.to_qr(QrPayload::SeedQr).is_err());
+        assert!(keyphrase.to_qr(QrPayload::CompactSeedQr).is_err());
+    }
+
+    #[test]
+    fn seed_to_qr() {
+        let seed: Seed = Seed::try_from(&[0xab; 64][..]).unwrap();
+
+        assert_finder_patterns(&seed.to_qr());
+    }
+
+    #[test]
+    fn qr_to_svg() {
+        let qr: QrMatrix = QrMatrix {
+            width: 2,
+            modules: vec![true, true, false, true],
+        };
+        let svg: String = qr.to_svg(10);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\""));
+        assert!(svg.contains("d=\"M4 4h2v1h-2zM5 5h1v1h-1z\""));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn qr_to_png() {
+        let qr: QrMatrix = QrMatrix {
+            width: 2,
+            modules: vec![true, true, false, true],
+        };
+        let png: Vec<u8> = qr.to_png(1);
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDR of a 10 by 10 pixels, 1 bit grayscale image
+        assert_eq!(
+            &png[8..33],
+            &[
+                0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0, 0, 10, 0, 0, 0, 10, 1, 0, 0, 0, 0, 0xa5,
+                0x49, 0xf2, 0x10
+            ][..]
+        );
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        assert_eq!(
+            zlib_stored(b"abc"),
+            [0x78, 0x01, 1, 3, 0, 0xfc, 0xff, b'a', b'b', b'c', 0x02, 0x4d, 0x01, 0x27]
+        );
+    }
+}
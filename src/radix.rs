@@ -0,0 +1,154 @@
+//! Writing a [`KeyPhrase`][KeyPhrase] as the raw wordlist indices of its words.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::util::IterExt;
+
+/// Number base used to write wordlist indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    /// 11 binary digits per word, for example `10011101011`
+    Binary,
+    /// 4 decimal digits per word, for example `1259`
+    Decimal,
+    /// 3 hexadecimal digits per word, for example `4eb`
+    Hex,
+}
+
+impl Radix {
+    fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Decimal => 10,
+            Radix::Hex => 16,
+        }
+    }
+
+    fn format(self, index: u16) -> String {
+        match self {
+            Radix::Binary => format!("{:011b}", index),
+            Radix::Decimal => format!("{:04}", index),
+            Radix::Hex => format!("{:03x}", index),
+        }
+    }
+}
+
+impl KeyPhrase {
+    /// Get the wordlist indices of the words, separated by spaces
+    ///
+    /// Indices start at 0 and are zero-padded to a fixed width, see [`Radix`][Radix].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Radix};
+    ///
+    /// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     keyphrase.to_index_string(Radix::Decimal),
+    ///     "1282 1452 1306 0984 1162 1673 0988 0094 1347 1415 0819 0073"
+    /// );
+    /// ```
+    ///
+    /// [Radix]: ./enum.Radix.html
+    pub fn to_index_string(&self, radix: Radix) -> String {
+        self.word_indices()
+            .into_iter()
+            .map(|index: u16| radix.format(index))
+            .join(" ")
+    }
+
+    /// Create a [`KeyPhrase`][KeyPhrase] from wordlist indices separated by spaces
+    ///
+    /// Zero-padding is optional. An index that can't be parsed in the given radix, or that is
+    /// outside of the wordlist, returns an `Error` of kind `ErrorKind::InvalidWord`. The
+    /// checksum is validated the same way as for
+    /// [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Radix};
+    ///
+    /// let indices = "1282 1452 1306 984 1162 1673 988 94 1347 1415 819 73";
+    /// let keyphrase = KeyPhrase::from_index_string(indices, Radix::Decimal, Language::English).unwrap();
+    ///
+    /// assert_eq!(keyphrase.phrase(), "park remain person kitchen mule spell knee armed position rail grid ankle");
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+    pub fn from_index_string(
+        indices: &str,
+        radix: Radix,
+        lang: Language,
+    ) -> Result<KeyPhrase, Error> {
+        let mut parsed: Vec<u16> = Vec::with_capacity(24);
+
+        for (index, digits) in indices.split(' ').enumerate() {
+            match u16::from_str_radix(digits, radix.base()) {
+                Ok(value) if value < 2048 => parsed.push(value),
+                _ => Err(ErrorKind::InvalidWord {
+                    word: digits.to_string(),
+                    index,
+                    lang: None,
+                })?,
+            }
+        }
+
+        KeyPhrase::from_word_indices(&parsed, lang)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    use crate::keyphrase_type::KeyPhraseType;
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn index_string_roundtrip() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+
+        for &radix in &[Radix::Binary, Radix::Decimal, Radix::Hex] {
+            let indices: String = keyphrase.to_index_string(radix);
+            let restored: KeyPhrase =
+                KeyPhrase::from_index_string(&indices, radix, Language::English).unwrap();
+
+            assert_eq!(keyphrase.phrase(), restored.phrase());
+        }
+    }
+
+    #[test]
+    fn index_string_formats() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        assert!(keyphrase
+            .to_index_string(Radix::Binary)
+            .starts_with("10100000010 10110101100 "));
+        assert!(keyphrase
+            .to_index_string(Radix::Hex)
+            .starts_with("502 5ac 51a 3d8 "));
+
+        assert!(KeyPhrase::from_index_string(
+            "2048 1 2 3 4 5 6 7 8 9 10 11",
+            Radix::Decimal,
+            Language::English
+        )
+        .is_err());
+        assert!(KeyPhrase::from_index_string(
+            "12g 1 2 3 4 5 6 7 8 9 10 11",
+            Radix::Hex,
+            Language::English
+        )
+        .is_err());
+    }
+}
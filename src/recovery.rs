@@ -0,0 +1,260 @@
+//! Recovering [`KeyPhrase`][KeyPhrase]s with missing or unreadable words.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{recovery, KeyPhrase, Language};
+//!
+//! let pattern = "park remain ? kitchen mule spell knee armed position rail grid ankle";
+//!
+//! let candidates: Vec<KeyPhrase> = recovery::recover(pattern, Language::English)
+//!     .unwrap()
+//!     .collect();
+//!
+//! assert!(candidates
+//!     .iter()
+//!     .any(|keyphrase| keyphrase.phrase().starts_with("park remain person")));
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::Error;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordMap};
+
+/// Placeholder for a missing word in a pattern
+pub const WILDCARD: &str = "?";
+
+/// Find every valid [`KeyPhrase`][KeyPhrase] matching a pattern with missing words
+///
+/// The pattern is a phrase where missing words are replaced with `?`. Every word of the wordlist
+/// is tried in place of each `?`, and the candidates are filtered by their checksum. On average
+/// 1 in 16 (12 words) to 1 in 256 (24 words) candidates is valid, so a single missing word
+/// leaves a handful of candidates, which can be narrowed down by checking the addresses derived
+/// from them.
+///
+/// Candidates are generated lazily, in wordlist order, but every `?` multiplies the number of
+/// candidates by 2048: more than two of them quickly becomes impractical.
+///
+/// The pattern must have a valid number of words, otherwise an `Error` of kind
+/// `ErrorKind::InvalidWordLength` is returned, and every other word must be in the wordlist,
+/// otherwise an `Error` of kind `ErrorKind::InvalidWord` is returned.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+pub fn recover(pattern: &str, lang: Language) -> Result<impl Iterator<Item = KeyPhrase>, Error> {
+    let wordmap: &WordMap = lang.wordmap();
+
+    KeyPhraseType::for_word_count(pattern.split(' ').count())?;
+
+    let mut indices: Vec<u16> = Vec::new();
+    let mut wildcards: Vec<usize> = Vec::new();
+
+    for (i, word) in pattern.split(' ').enumerate() {
+        if word == WILDCARD {
+            wildcards.push(i);
+            indices.push(0);
+        } else {
+            indices.push(wordmap.get_bits_at(word, i)?.into());
+        }
+    }
+
+    Ok(Recover {
+        indices,
+        wildcards,
+        lang,
+        done: false,
+    })
+}
+
+/// Find every valid [`KeyPhrase`][KeyPhrase] that differs from a phrase by exactly one word
+///
+/// When a phrase fails its checksum because of a single wrong word, every word of the wordlist
+/// is tried at every position, which leaves on average 1 candidate in 16 (12 words) to 1 in 256
+/// (24 words) for each position. If some words are not in the wordlist, only their position is
+/// tried, and if there are several of them no single correction is possible, so the list is
+/// empty. The phrase itself is never part of the candidates.
+///
+/// The phrase must have a valid number of words, otherwise an `Error` of kind
+/// `ErrorKind::InvalidWordLength` is returned.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{recovery, KeyPhrase, Language};
+///
+/// let phrase = "park remain person kitchen mule spell knee armed position rail grid ankel";
+///
+/// let candidates: Vec<KeyPhrase> = recovery::correct_single_word(phrase, Language::English).unwrap();
+///
+/// assert!(candidates.iter().any(|keyphrase| keyphrase.phrase().ends_with("grid ankle")));
+/// ```
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+pub fn correct_single_word(phrase: &str, lang: Language) -> Result<Vec<KeyPhrase>, Error> {
+    let wordmap: &WordMap = lang.wordmap();
+
+    KeyPhraseType::for_word_count(phrase.split(' ').count())?;
+
+    let mut indices: Vec<u16> = Vec::new();
+    let mut unknown: Vec<usize> = Vec::new();
+
+    for (i, word) in phrase.split(' ').enumerate() {
+        match wordmap.get_bits(word) {
+            Ok(bits) => indices.push(bits.into()),
+            Err(_) => {
+                unknown.push(i);
+                indices.push(0);
+            }
+        }
+    }
+
+    let positions: Vec<usize> = match unknown.len() {
+        0 => (0..indices.len()).collect(),
+        1 => unknown,
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut candidates: Vec<KeyPhrase> = Vec::new();
+
+    for position in positions {
+        let mut search: Recover = Recover {
+            indices: indices.clone(),
+            wildcards: vec![position],
+            lang,
+            done: false,
+        };
+
+        search.indices[position] = 0;
+
+        candidates.extend(search.filter(|keyphrase: &KeyPhrase| keyphrase.phrase() != phrase));
+    }
+
+    Ok(candidates)
+}
+
+/// Iterator over the candidates of a pattern, trying wildcard values like an odometer
+struct Recover {
+    indices: Vec<u16>,
+    wildcards: Vec<usize>,
+    lang: Language,
+    done: bool,
+}
+
+impl Recover {
+    /// Move to the next combination of wildcard values, returns `false` once all were tried
+    fn advance(&mut self) -> bool {
+        for &position in self.wildcards.iter().rev() {
+            if self.indices[position] < 2047 {
+                self.indices[position] += 1;
+
+                return true;
+            }
+
+            self.indices[position] = 0;
+        }
+
+        false
+    }
+}
+
+impl Iterator for Recover {
+    type Item = KeyPhrase;
+
+    fn next(&mut self) -> Option<KeyPhrase> {
+        while !self.done {
+            let candidate: Result<KeyPhrase, Error> =
+                KeyPhrase::from_word_indices(&self.indices, self.lang);
+
+            self.done = !self.advance();
+
+            if let Ok(keyphrase) = candidate {
+                return Some(keyphrase);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recover_missing_words() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+        let candidates: Vec<KeyPhrase> = recover(
+            "park remain ? kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap()
+        .collect();
+
+        assert!(!candidates.is_empty());
+        assert!(candidates
+            .iter()
+            .any(|keyphrase: &KeyPhrase| keyphrase.phrase() == phrase));
+
+        // The last word is constrained by the checksum
+        let candidates: Vec<KeyPhrase> = recover(
+            "park remain person kitchen mule spell knee armed position rail grid ?",
+            Language::English,
+        )
+        .unwrap()
+        .collect();
+
+        assert_eq!(candidates.len(), 128);
+
+        // No wildcard just validates the phrase
+        assert_eq!(recover(phrase, Language::English).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn recover_invalid_pattern() {
+        assert!(recover("park remain ? kitchen", Language::English).is_err());
+        assert!(recover(
+            "park remain ? kitchen mule spell knee armed position rail grid ankel",
+            Language::English
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn correct_single_word_candidates() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+
+        // A valid phrase only lists other phrases
+        let candidates: Vec<KeyPhrase> = correct_single_word(phrase, Language::English).unwrap();
+
+        assert!(!candidates.is_empty());
+        assert!(candidates
+            .iter()
+            .all(|keyphrase: &KeyPhrase| keyphrase.phrase() != phrase));
+
+        let typo: &str = "park remain person kitchen mule spell knee armed positon rail grid ankle";
+        let candidates: Vec<KeyPhrase> = correct_single_word(typo, Language::English).unwrap();
+
+        assert!(candidates
+            .iter()
+            .any(|keyphrase: &KeyPhrase| keyphrase.phrase() == phrase));
+
+        let wrong: &str =
+            "park remain person kitchen mule spell knee armed position rail grid park";
+        let candidates: Vec<KeyPhrase> = correct_single_word(wrong, Language::English).unwrap();
+
+        assert!(candidates
+            .iter()
+            .any(|keyphrase: &KeyPhrase| keyphrase.phrase() == phrase));
+
+        let typos: &str =
+            "park remain person kitchen mule spell knee armed positon rail grid ankel";
+
+        assert!(correct_single_word(typos, Language::English)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! RFC 1751 (S/KEY) words, enabled by the `rfc1751` feature.
+//!
+//! Every 64 bits are written as 6 words of the S/KEY dictionary, 66 bits with a 2 bits parity.
+//! The dictionary is 571 words of up to 3 letters followed by 1477 words of 4 letters, each
+//! part in alphabetical order.
+
+use crate::error::{Error, ErrorKind};
+use once_cell::sync::Lazy;
+
+/// The 2048 words of the S/KEY dictionary, in the order of the 11 bits they encode
+static WORDLIST: Lazy<Vec<&'static str>> =
+    sync_lazy! { include_str!("langs/rfc1751.txt").split_whitespace().collect() };
+
+/// Words of up to 3 letters, at the start of the dictionary
+const SHORT_WORDS: usize = 571;
+
+/// Words of every group of 64 bits
+const WORDS_PER_GROUP: usize = 6;
+
+/// Encode entropy as RFC 1751 words, in uppercase
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidKeysize` unless the entropy is a multiple of 8
+/// bytes.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{decode_rfc1751, encode_rfc1751};
+///
+/// let key = [
+///     0xcc, 0xac, 0x2a, 0xed, 0x59, 0x10, 0x56, 0xbe, 0x4f, 0x90, 0xfd, 0x44, 0x1c, 0x53, 0x47,
+///     0x66,
+/// ];
+/// let words = encode_rfc1751(&key).unwrap();
+///
+/// assert_eq!(words, "RASH BUSH MILK LOOK BAD BRIM AVID GAFF BAIT ROT POD LOVE");
+/// assert_eq!(decode_rfc1751(&words).unwrap(), key);
+/// ```
+pub fn encode_rfc1751(entropy: &[u8]) -> Result<String, Error> {
+    if entropy.is_empty() || entropy.len() % 8 != 0 {
+        Err(ErrorKind::InvalidKeysize(entropy.len() * 8))?;
+    }
+
+    let words: Vec<&str> = entropy
+        .chunks(8)
+        .flat_map(|chunk: &[u8]| {
+            let group: u64 = chunk
+                .iter()
+                .fold(0, |group: u64, &byte: &u8| group << 8 | u64::from(byte));
+            let bits: u128 = u128::from(group) << 2 | u128::from(parity(group));
+
+            (0..WORDS_PER_GROUP).map(move |i: usize| {
+                WORDLIST[(bits >> (11 * (WORDS_PER_GROUP - 1 - i))) as usize & 0x7ff]
+            })
+        })
+        .collect();
+
+    Ok(words.join(" "))
+}
+
+/// Decode RFC 1751 words, in either case, and verify their parity
+///
+/// Like the reference implementation, the digits `0`, `1` and `5` are read as the letters `O`,
+/// `L` and `S` they are often mistaken for. Returns an `Error` of kind
+/// `ErrorKind::InvalidWordLength` unless there are groups of 6 words, `ErrorKind::InvalidWord`
+/// if a word isn't in the dictionary, or `ErrorKind::InvalidChecksum` if the parity of a group
+/// doesn't match.
+pub fn decode_rfc1751(words: &str) -> Result<Vec<u8>, Error> {
+    let words: Vec<&str> = words.split_whitespace().collect();
+
+    if words.is_empty() || words.len() % WORDS_PER_GROUP != 0 {
+        Err(ErrorKind::InvalidWordLength {
+            words: words.len(),
+            expected: "a multiple of 6",
+        })?;
+    }
+
+    let indices: Vec<u16> = words
+        .iter()
+        .enumerate()
+        .map(|(index, word): (usize, &&str)| {
+            word_index(word).ok_or_else(|| ErrorKind::InvalidWord {
+                word: word.to_string(),
+                index,
+                lang: None,
+            })
+        })
+        .collect::<Result<Vec<u16>, ErrorKind>>()?;
+
+    let mut entropy: Vec<u8> = Vec::with_capacity(words.len() / WORDS_PER_GROUP * 8);
+
+    for group in indices.chunks(WORDS_PER_GROUP) {
+        let bits: u128 = group
+            .iter()
+            .fold(0, |bits: u128, &index: &u16| bits << 11 | u128::from(index));
+        let key: u64 = (bits >> 2) as u64;
+
+        if parity(key) != (bits & 3) as u8 {
+            Err(ErrorKind::InvalidChecksum)?;
+        }
+
+        entropy.extend_from_slice(&key.to_be_bytes());
+    }
+
+    Ok(entropy)
+}
+
+/// Find the index of a word, in the part of the dictionary of its length
+fn word_index(word: &str) -> Option<u16> {
+    let word: String = word
+        .to_ascii_uppercase()
+        .replace('1', "L")
+        .replace('0', "O")
+        .replace('5', "S");
+    let (offset, part): (usize, &[&str]) = match word.len() {
+        1..=3 => (0, &WORDLIST[..SHORT_WORDS]),
+        4 => (SHORT_WORDS, &WORDLIST[SHORT_WORDS..]),
+        _ => return None,
+    };
+
+    part.binary_search(&word.as_str())
+        .ok()
+        .map(|position: usize| (offset + position) as u16)
+}
+
+/// Compute the parity of 64 bits, the sum of their pairs of bits
+fn parity(key: u64) -> u8 {
+    (0..32).fold(0, |parity: u8, pair: u32| {
+        parity.wrapping_add((key >> (2 * pair)) as u8 & 3)
+    }) & 3
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc1751_test_vectors() {
+        // The examples of RFC 1751
+        let vectors: [(&str, &str); 3] = [
+            ("eb33f77ee73d4053", "TIDE ITCH SLOW REIN RULE MOT"),
+            (
+                "ccac2aed591056be4f90fd441c534766",
+                "RASH BUSH MILK LOOK BAD BRIM AVID GAFF BAIT ROT POD LOVE",
+            ),
+            (
+                "eff81f9bfbc65350920cdd7416de8009",
+                "TROD MUTE TAIL WARM CHAR KONG HAAG CITY BORE O TEAL AWL",
+            ),
+        ];
+
+        assert_eq!(WORDLIST.len(), 2048);
+
+        vectors.iter().for_each(|&(hex, words): &(&str, &str)| {
+            let entropy: Vec<u8> = (0..hex.len())
+                .step_by(2)
+                .map(|i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                .collect();
+
+            assert_eq!(encode_rfc1751(&entropy).unwrap(), words);
+            assert_eq!(decode_rfc1751(words).unwrap(), entropy);
+            assert_eq!(decode_rfc1751(&words.to_lowercase()).unwrap(), entropy);
+        });
+    }
+
+    #[test]
+    fn rfc1751_invalid() {
+        // wrong parity, not a word, 5 words, too long
+        assert!(decode_rfc1751("RASH BUSH MILK LOOK BAD BRIG").is_err());
+        assert!(decode_rfc1751("RASH BUSH MILK LOOK BAD BRIX").is_err());
+        assert!(decode_rfc1751("RASH BUSH MILK LOOK BAD").is_err());
+        assert!(decode_rfc1751("RASH BUSH MILK LOOK BAD BRIMS").is_err());
+        assert!(decode_rfc1751("").is_err());
+
+        // digits mistaken for letters
+        assert_eq!(
+            decode_rfc1751("RASH BUSH MI1K 100K BAD BRIM AVID GAFF BAIT R0T P0D 10VE").unwrap(),
+            decode_rfc1751("RASH BUSH MILK LOOK BAD BRIM AVID GAFF BAIT ROT POD LOVE").unwrap()
+        );
+
+        assert!(encode_rfc1751(&[0; 12]).is_err());
+        assert!(encode_rfc1751(&[]).is_err());
+    }
+}
@@ -1,6 +1,44 @@
-use crate::crypto::pbkdf2;
+use crate::crypto::{constant_time_eq, hkdf_sha512, pbkdf2, pbkdf2_with_progress, Kdf};
+use crate::error::{Error, ErrorKind};
+#[cfg(any(feature = "bip32", feature = "ed25519"))]
+use crate::hd::DerivationPath;
+#[cfg(feature = "ed25519")]
+use crate::hd::Ed25519Key;
+#[cfg(feature = "bip32")]
+use crate::hd::{ExtendedPrivKey, PublicKey, SecretKey};
 use crate::keyphrase::KeyPhrase;
+#[cfg(feature = "mlock")]
+use crate::locked::Locked;
+use crate::passphrase::Passphrase;
+use crate::util::decode_hex;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::convert::TryFrom;
 use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Length of a seed in bytes
+const SEED_BYTES: usize = 64;
+
+/// The bytes of a seed, locked in RAM with the `mlock` feature
+#[cfg(feature = "mlock")]
+type SeedBytes = Locked<[u8; SEED_BYTES]>;
+#[cfg(not(feature = "mlock"))]
+type SeedBytes = [u8; SEED_BYTES];
+
+/// Number of PBKDF2 rounds between two calls of the progress callback
+const ROUNDS_PER_PROGRESS: usize = 128;
+
+/// HKDF salt of [`Seed::derive_subkey()`][Seed::derive_subkey()], so that subkeys can't collide
+/// with keys derived from the seed by other schemes
+///
+/// [Seed::derive_subkey()]: ./seed/struct.Seed.html#method.derive_subkey
+const SUBKEY_SALT: &[u8] = b"keyphrase-subkey";
+
+/// Longest subkey HKDF-SHA512 can derive
+const SUBKEY_MAX_BYTES: usize = 255 * 64;
 
 /// The secret value used to derive HD wallet addresses from a [`KeyPhrase`][KeyPhrase] phrase.
 ///
@@ -18,7 +56,7 @@ use std::fmt;
 
 #[derive(Clone)]
 pub struct Seed {
-    bytes: Vec<u8>,
+    bytes: SeedBytes,
 }
 
 impl Seed {
@@ -39,11 +77,439 @@ impl Seed {
     ///
     /// let seed: Seed = Seed::new(&keyphrase, "");
     /// ```
-    pub fn new(keyphrase: &KeyPhrase, password: &str) -> Self {
-        let salt: String = format!("keyphrase{}", password);
-        let bytes: Vec<u8> = pbkdf2(keyphrase.entropy(), &salt);
+    ///
+    /// **Note:** This derivation is salted with `"keyphrase"` and is not compatible with BIP39
+    /// wallets, use [`Seed::new_bip39()`][Seed::new_bip39()] for that. It is compiled out entirely
+    /// when the `strict-bip39-only` feature is enabled.
+    ///
+    /// [Seed::new_bip39()]: ./seed/struct.Seed.html#method.new_bip39
+    #[cfg(not(feature = "strict-bip39-only"))]
+    pub fn new<P: Into<Passphrase>>(keyphrase: &KeyPhrase, password: P) -> Self {
+        let password: Passphrase = password.into();
+        #[allow(unused_mut)]
+        let mut salt: String = format!("keyphrase{}", password.expose_secret());
+        let mut seed: Seed = Seed::empty();
+
+        pbkdf2(keyphrase.entropy(), &salt, &mut seed.bytes);
+
+        #[cfg(feature = "zeroize")]
+        salt.zeroize();
+
+        seed
+    }
+
+    /// Generates the seed from the [`KeyPhrase`][KeyPhrase] and the password, as defined by BIP39
+    ///
+    /// The seed is identical to the one of any BIP39 wallet (Trezor, Ledger, …) for the same phrase
+    /// and password, so funds can be moved between them. Unlike [`Seed::new()`][Seed::new()], it is
+    /// derived from the NFKD normalized phrase, salted with `"mnemonic"` and the NFKD normalized
+    /// password.
+    ///
+    /// Like every derivation, it takes the password as a [`Passphrase`][Passphrase] or anything
+    /// that converts into one, such as `&str`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language, Seed};
+    ///
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// let seed = Seed::new_bip39(&keyphrase, "TREZOR");
+    ///
+    /// assert_eq!(&seed.as_bytes()[..4], &[0xc5, 0x52, 0x57, 0xc3]);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Passphrase]: ./passphrase/struct.Passphrase.html
+    /// [Seed::new()]: ./seed/struct.Seed.html#method.new
+    pub fn new_bip39<P: Into<Passphrase>>(keyphrase: &KeyPhrase, password: P) -> Self {
+        let (phrase, salt): (String, String) = bip39_input(keyphrase, &password.into());
+        let mut seed: Seed = Seed::empty();
+
+        pbkdf2(phrase.as_bytes(), &salt, &mut seed.bytes);
+
+        wipe(phrase, salt);
+
+        seed
+    }
+
+    /// Generates the seed from the [`KeyPhrase`][KeyPhrase] and the password with a chosen key
+    /// derivation function
+    ///
+    /// The phrase and password are normalized and salted like
+    /// [`Seed::new_bip39()`][Seed::new_bip39()], which is the same as using `Kdf::default()`.
+    /// Returns an `Error` of kind `ErrorKind::InvalidKdfParams` if the parameters of the
+    /// function are invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{Kdf, KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    ///
+    /// let seed = Seed::new_with_params(&keyphrase, "", Kdf::Pbkdf2 { rounds: 100_000 }).unwrap();
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Seed::new_bip39()]: ./seed/struct.Seed.html#method.new_bip39
+    pub fn new_with_params<P: Into<Passphrase>>(
+        keyphrase: &KeyPhrase,
+        password: P,
+        kdf: Kdf,
+    ) -> Result<Self, Error> {
+        let (phrase, salt): (String, String) = bip39_input(keyphrase, &password.into());
+        let mut seed: Seed = Seed::empty();
+
+        let result: Result<(), Error> = kdf.derive(phrase.as_bytes(), &salt, &mut seed.bytes);
+
+        wipe(phrase, salt);
+        result?;
+
+        Ok(seed)
+    }
+
+    /// Same as [`Seed::new_with_params()`][Seed::new_with_params()], calling `progress` while
+    /// the seed is derived
+    ///
+    /// `progress` is called with `0.0` when the derivation starts, with the fraction of the work
+    /// done every few PBKDF2 rounds, and with `1.0` when the seed is ready. Functions other than
+    /// PBKDF2 can't report their progress, only the first and last calls are made for them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{Kdf, KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    ///
+    /// let seed = Seed::new_with_progress(&keyphrase, "", Kdf::default(), |done: f32| {
+    ///     println!("{:.0}%", done * 100.0);
+    /// });
+    /// ```
+    ///
+    /// [Seed::new_with_params()]: ./seed/struct.Seed.html#method.new_with_params
+    pub fn new_with_progress<P, F>(
+        keyphrase: &KeyPhrase,
+        password: P,
+        kdf: Kdf,
+        mut progress: F,
+    ) -> Result<Self, Error>
+    where
+        P: Into<Passphrase>,
+        F: FnMut(f32),
+    {
+        let (phrase, salt): (String, String) = bip39_input(keyphrase, &password.into());
+        let mut seed: Seed = Seed::empty();
+
+        progress(0.0);
+
+        let result: Result<(), Error> = match kdf {
+            Kdf::Pbkdf2 { rounds } => pbkdf2_with_progress(
+                phrase.as_bytes(),
+                &salt,
+                rounds as usize,
+                ROUNDS_PER_PROGRESS,
+                &mut seed.bytes,
+                &mut progress,
+            ),
+            #[allow(unreachable_patterns)]
+            kdf => kdf
+                .derive(phrase.as_bytes(), &salt, &mut seed.bytes)
+                .map(|()| progress(1.0)),
+        };
+
+        wipe(phrase, salt);
+        result?;
+
+        Ok(seed)
+    }
+
+    /// Generate the seeds of many [`KeyPhrase`][KeyPhrase]s and passwords
+    ///
+    /// Every seed is derived like [`Seed::new_bip39()`][Seed::new_bip39()], the result has one
+    /// seed per pair, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrases: Vec<(KeyPhrase, &str)> = KeyPhrase::generator(KeyPhraseType::Words12, Language::English)
+    ///     .take(10)
+    ///     .map(|keyphrase| (keyphrase, ""))
+    ///     .collect();
+    ///
+    /// let seeds = Seed::derive_batch(&keyphrases);
+    ///
+    /// assert_eq!(seeds.len(), 10);
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Seed::new_bip39()]: ./seed/struct.Seed.html#method.new_bip39
+    pub fn derive_batch(keyphrases: &[(KeyPhrase, &str)]) -> Vec<Seed> {
+        let mut seeds: Vec<Seed> = Vec::with_capacity(keyphrases.len());
+
+        seeds.extend(
+            keyphrases
+                .iter()
+                .map(|(keyphrase, password): &(KeyPhrase, &str)| {
+                    Seed::new_bip39(keyphrase, *password)
+                }),
+        );
+
+        seeds
+    }
+
+    /// Same as [`Seed::derive_batch()`][Seed::derive_batch()], deriving the seeds on all the
+    /// cores
+    ///
+    /// [Seed::derive_batch()]: ./seed/struct.Seed.html#method.derive_batch
+    #[cfg(feature = "parallel")]
+    pub fn derive_batch_parallel(keyphrases: &[(KeyPhrase, &str)]) -> Vec<Seed> {
+        keyphrases
+            .par_iter()
+            .map(|(keyphrase, password): &(KeyPhrase, &str)| Seed::new_bip39(keyphrase, *password))
+            .collect()
+    }
+
+    /// Check that the [`KeyPhrase`][KeyPhrase] and the password give this seed
+    ///
+    /// Meant for restore flows: the seed is derived again like
+    /// [`Seed::new_bip39()`][Seed::new_bip39()] and compared in constant time. Use
+    /// [`Seed::verify_with_params()`][Seed::verify_with_params()] for seeds derived with another
+    /// key derivation function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed = Seed::new_bip39(&keyphrase, "TREZOR");
+    ///
+    /// assert!(seed.verify(&keyphrase, "TREZOR"));
+    /// assert!(!seed.verify(&keyphrase, "trezor"));
+    /// ```
+    ///
+    /// [KeyPhrase]: ./keyphrase/struct.KeyPhrase.html
+    /// [Seed::new_bip39()]: ./seed/struct.Seed.html#method.new_bip39
+    /// [Seed::verify_with_params()]: ./seed/struct.Seed.html#method.verify_with_params
+    pub fn verify<P: Into<Passphrase>>(&self, keyphrase: &KeyPhrase, password: P) -> bool {
+        *self == Seed::new_bip39(keyphrase, password)
+    }
+
+    /// Same as [`Seed::verify()`][Seed::verify()], deriving the seed again with `kdf` like
+    /// [`Seed::new_with_params()`][Seed::new_with_params()]
+    ///
+    /// Invalid `kdf` parameters never verify.
+    ///
+    /// [Seed::verify()]: ./seed/struct.Seed.html#method.verify
+    /// [Seed::new_with_params()]: ./seed/struct.Seed.html#method.new_with_params
+    pub fn verify_with_params<P: Into<Passphrase>>(
+        &self,
+        keyphrase: &KeyPhrase,
+        password: P,
+        kdf: Kdf,
+    ) -> bool {
+        match Seed::new_with_params(keyphrase, password, kdf) {
+            Ok(seed) => *self == seed,
+            Err(_) => false,
+        }
+    }
+
+    /// Get the seed value as a lowercase hex string, two digits per byte
+    ///
+    /// The result can be parsed back with [`Seed::from_hex()`][Seed::from_hex()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    /// use std::convert::TryFrom;
+    ///
+    /// let seed = Seed::try_from(&[0x05; 64][..]).unwrap();
+    ///
+    /// assert_eq!(seed.to_hex(), "05".repeat(64));
+    /// ```
+    ///
+    /// [Seed::from_hex()]: ./seed/struct.Seed.html#method.from_hex
+    pub fn to_hex(&self) -> String {
+        format!("{:x}", self)
+    }
+
+    /// Display the whole seed as hex
+    ///
+    /// The `Debug` output of a seed is redacted, this is the explicit way to print it, for
+    /// example on an export screen. Never log it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    /// use std::convert::TryFrom;
+    ///
+    /// let seed = Seed::try_from(&[0xab; 64][..]).unwrap();
+    ///
+    /// assert_eq!(seed.display_full().to_string(), "ab".repeat(64));
+    /// ```
+    pub fn display_full(&self) -> impl fmt::Display + '_ {
+        struct Full<'a>(&'a Seed);
+
+        impl<'a> fmt::Display for Full<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:x}", self.0)
+            }
+        }
+
+        Full(self)
+    }
+
+    /// Get the BIP32 master key fingerprint of the seed
+    ///
+    /// The fingerprint is the first 4 bytes of the hash160 of the master public key, it is what
+    /// wallets and coordinators show to identify a seed. Comparing it after a restore checks that
+    /// the right phrase and password were entered, without deriving any address. It doesn't
+    /// reveal anything about the seed.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidDerivedKey` for the seeds, with a
+    /// probability lower than 1 in 2^127, that have no valid master key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    ///
+    /// let seed = Seed::from_hex(&"00".repeat(64)).unwrap();
+    ///
+    /// assert_eq!(seed.fingerprint().unwrap().len(), 4);
+    /// ```
+    #[cfg(feature = "bip32")]
+    pub fn fingerprint(&self) -> Result<[u8; 4], Error> {
+        Ok(ExtendedPrivKey::from_seed(self)?.fingerprint())
+    }
+
+    /// Get the BIP32 secp256k1 master keypair of the seed
+    ///
+    /// This is the key pair at `m`, for signing code that needs the master key without the
+    /// chain code. Use [`Seed::derive_path()`][Seed::derive_path()] to get the keys of an
+    /// account.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidDerivedKey` for the seeds, with a
+    /// probability lower than 1 in 2^127, that have no valid master key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    ///
+    /// let seed = Seed::from_hex(&"00".repeat(64)).unwrap();
+    /// let (secret_key, public_key) = seed.to_secp256k1_master().unwrap();
+    ///
+    /// assert_eq!(public_key.serialize().len(), 33);
+    /// ```
+    ///
+    /// [Seed::derive_path()]: ../seed/struct.Seed.html#method.derive_path
+    #[cfg(feature = "bip32")]
+    pub fn to_secp256k1_master(&self) -> Result<(SecretKey, PublicKey), Error> {
+        let master: ExtendedPrivKey = ExtendedPrivKey::from_seed(self)?;
+
+        Ok((*master.secret_key(), master.public_key()))
+    }
+
+    /// Derive the BIP32 private key at `path` from the master key of the seed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::hd::DerivationPath;
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed = Seed::new_bip39(&keyphrase, "");
+    ///
+    /// let path: DerivationPath = "m/44'/0'/0'/0/0".parse().unwrap();
+    /// let key = seed.derive_path(&path).unwrap();
+    ///
+    /// assert_eq!(key.depth(), 5);
+    /// ```
+    #[cfg(feature = "bip32")]
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivKey, Error> {
+        ExtendedPrivKey::from_seed(self)?.derive_path(path)
+    }
 
-        Self { bytes }
+    /// Derive the SLIP-0010 ed25519 private key at `path`, which must only contain hardened
+    /// children
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::hd::DerivationPath;
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed = Seed::new_bip39(&keyphrase, "");
+    ///
+    /// let path: DerivationPath = "m/44'/501'/0'/0'".parse().unwrap();
+    /// let key = seed.derive_ed25519(&path).unwrap();
+    ///
+    /// assert_eq!(key.depth(), 4);
+    /// ```
+    #[cfg(feature = "ed25519")]
+    pub fn derive_ed25519(&self, path: &DerivationPath) -> Result<Ed25519Key, Error> {
+        Ed25519Key::from_seed(self).derive_path(path)
+    }
+
+    /// Derive a key for a given purpose from the seed, with HKDF-SHA512
+    ///
+    /// Every `info` gives an unrelated key, name it after what the key is used for, like
+    /// `b"db-encryption"` or `b"api-hmac"`, so that the seed itself never has to be used as a
+    /// key. Knowing a subkey tells nothing about the seed or the other subkeys.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidKeysize` if `len` is 0 or more than
+    /// 16320 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed = Seed::new_bip39(&keyphrase, "");
+    ///
+    /// let encryption_key = seed.derive_subkey(b"db-encryption", 32).unwrap();
+    /// let hmac_key = seed.derive_subkey(b"api-hmac", 64).unwrap();
+    ///
+    /// assert_ne!(&encryption_key[..], &hmac_key[..32]);
+    /// ```
+    pub fn derive_subkey(&self, info: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+        if len == 0 || len > SUBKEY_MAX_BYTES {
+            Err(ErrorKind::InvalidKeysize(len))?;
+        }
+
+        let mut subkey: Vec<u8> = vec![0; len];
+
+        hkdf_sha512(SUBKEY_SALT, &self.bytes[..], info, &mut subkey);
+
+        Ok(subkey)
+    }
+
+    /// All zero seed, to derive into
+    pub(crate) fn empty() -> Seed {
+        Seed {
+            #[cfg(feature = "mlock")]
+            bytes: Locked::zeroed(),
+            #[cfg(not(feature = "mlock"))]
+            bytes: [0; SEED_BYTES],
+        }
+    }
+
+    /// Mutable access to the seed value, to derive into
+    #[cfg(feature = "async")]
+    pub(crate) fn bytes_mut(&mut self) -> &mut [u8; SEED_BYTES] {
+        &mut self.bytes
     }
 
     /// Get the seed value as a byte slice
@@ -59,12 +525,103 @@ impl Seed {
     ///
     /// let keyphrase: KeyPhrase = KeyPhrase::new(word_count, lang);
     ///
-    /// let seed: Seed = Seed::new(&keyphrase, "");
+    /// let seed: Seed = Seed::new_bip39(&keyphrase, "");
     ///     
     /// let seed_bytes: &[u8] = seed.as_bytes();
     /// ```
     pub fn as_bytes(&self) -> &[u8] {
-        &self.bytes
+        &self.bytes[..]
+    }
+
+    /// Get a copy of the seed value as a fixed-size array
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+    /// let seed = Seed::new_bip39(&keyphrase, "");
+    ///
+    /// let seed_bytes: [u8; 64] = seed.to_array();
+    /// ```
+    pub fn to_array(&self) -> [u8; SEED_BYTES] {
+        let mut array: [u8; SEED_BYTES] = [0; SEED_BYTES];
+
+        array.copy_from_slice(self.as_bytes());
+        array
+    }
+
+    /// Parse a seed previously written as hex
+    ///
+    /// Both cases and an optional `0x` prefix are accepted, every byte must be written with two
+    /// digits. Returns an `Error` of kind `ErrorKind::InvalidEncoding` if the string is not hex,
+    /// or `ErrorKind::InvalidSeedLength` if it isn't 64 bytes long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::Seed;
+    ///
+    /// let hex = "0x".to_string() + &"5e".repeat(64);
+    /// let seed = Seed::from_hex(&hex).unwrap();
+    ///
+    /// assert_eq!(seed.as_bytes(), &[0x5e; 64][..]);
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Seed, Error> {
+        #[allow(unused_mut)]
+        let mut bytes: Vec<u8> = decode_hex(hex)?;
+
+        let seed: Result<Seed, Error> = Seed::try_from(bytes.as_slice());
+
+        #[cfg(feature = "zeroize")]
+        bytes.zeroize();
+
+        seed
+    }
+}
+
+/// NFKD normalize the phrase and the password into the input and salt of the BIP39 derivation
+pub(crate) fn bip39_input(keyphrase: &KeyPhrase, password: &Passphrase) -> (String, String) {
+    let phrase: String = keyphrase.phrase().nfkd().collect();
+    let salt: String = format!("mnemonic{}", password.expose_secret().nfkd());
+
+    (phrase, salt)
+}
+
+/// Wipe the input and salt of a derivation
+#[allow(unused_mut, unused_variables)]
+pub(crate) fn wipe(mut phrase: String, mut salt: String) {
+    #[cfg(feature = "zeroize")]
+    {
+        phrase.zeroize();
+        salt.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Seed {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+/// Create a [`Seed`][Seed] from its raw bytes, which must be 64 bytes long
+///
+/// [Seed]: ./seed/struct.Seed.html
+impl<'a> TryFrom<&'a [u8]> for Seed {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Seed, Error> {
+        if bytes.len() != SEED_BYTES {
+            Err(ErrorKind::InvalidSeedLength(bytes.len()))?;
+        }
+
+        let mut seed: Seed = Seed::empty();
+
+        seed.bytes.copy_from_slice(bytes);
+
+        Ok(seed)
     }
 }
 
@@ -74,9 +631,35 @@ impl AsRef<[u8]> for Seed {
     }
 }
 
+/// Seeds are compared in constant time, so that the comparison doesn't leak how many leading
+/// bytes match
+impl PartialEq for Seed {
+    fn eq(&self, other: &Seed) -> bool {
+        constant_time_eq(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl Eq for Seed {}
+
+/// The seed is redacted from the `Debug` output, so that it doesn't end up in logs or panic
+/// messages. Only the length and a short non-secret hash of the seed are printed, use
+/// [`Seed::display_full()`][Seed::display_full()] to print the seed itself.
+///
+/// [Seed::display_full()]: ./seed/struct.Seed.html#method.display_full
 impl fmt::Debug for Seed {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:#X}", self)
+        let hash: Vec<u8> = crate::crypto::sha256_tagged("keyphrase-seed-debug", self.as_bytes());
+
+        f.debug_struct("Seed")
+            .field("len", &self.bytes.len())
+            .field(
+                "hash",
+                &format_args!(
+                    "{:02x}{:02x}{:02x}{:02x}",
+                    hash[0], hash[1], hash[2], hash[3]
+                ),
+            )
+            .finish()
     }
 }
 
@@ -86,8 +669,8 @@ impl fmt::LowerHex for Seed {
             f.write_str("0x")?;
         }
 
-        for byte in &self.bytes {
-            write!(f, "{:x}", byte)?;
+        for byte in self.bytes.iter() {
+            write!(f, "{:02x}", byte)?;
         }
 
         Ok(())
@@ -100,19 +683,359 @@ impl fmt::UpperHex for Seed {
             f.write_str("0x")?;
         }
 
-        // TODO - Why are values which are less than 10 (in base 10) dropping the leading 0 when converted to hex?
-        // Ex: 03 becomes 3 which is causing the final seed string to be odd numbers. Is this an issue?
-        // for byte in &self.bytes {
-        //     if byte < &10 {
-        //         write!(f, "0{:X}", byte)?;
-        //     } else {
-        //         write!(f, "{:X}", byte)?;
-        //     }
-        // }
-
-        for byte in &self.bytes {
-            write!(f, "{:X}", byte)?;
+        for byte in self.bytes.iter() {
+            write!(f, "{:02X}", byte)?;
         }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::language::Language;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn seed_hex(phrase: &str, password: &str) -> String {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+        Seed::new_bip39(&keyphrase, password).to_hex()
+    }
+
+    #[test]
+    fn new_bip39_test_vectors() {
+        assert_eq!(
+            seed_hex(PHRASE, "TREZOR"),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        assert_eq!(
+            seed_hex("legal winner thank year wave sausage worth useful legal winner thank yellow", "TREZOR"),
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"
+        );
+    }
+
+    #[test]
+    fn new_with_params_kdf() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+
+        assert_eq!(
+            Seed::new_with_params(&keyphrase, "TREZOR", Kdf::default())
+                .unwrap()
+                .to_hex(),
+            seed_hex(PHRASE, "TREZOR")
+        );
+
+        assert!(Seed::new_with_params(&keyphrase, "", Kdf::Pbkdf2 { rounds: 0 }).is_err());
+
+        // checked before deriving anything, so this fails right away
+        match Seed::new_with_params(&keyphrase, "", Kdf::Pbkdf2 { rounds: u32::MAX }) {
+            Err(error) => match error.kind() {
+                ErrorKind::InvalidKdfParams(_) => {}
+                kind => panic!("unexpected error: {:?}", kind),
+            },
+            Ok(_) => panic!("u32::MAX rounds are accepted"),
+        }
+        assert!(Seed::new_with_progress(
+            &keyphrase,
+            "",
+            Kdf::Pbkdf2 { rounds: u32::MAX },
+            |_: f32| {}
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "scrypt")]
+    #[test]
+    fn new_with_params_scrypt() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let kdf: Kdf = Kdf::Scrypt {
+            n: 1024,
+            r: 8,
+            p: 1,
+        };
+
+        assert_eq!(
+            Seed::new_with_params(&keyphrase, "TREZOR", kdf).unwrap().to_hex(),
+            "bafa9e1857b26ca8455f685df3759f2b087fbc9c205e3b048e0d4349e3d0b31e377873ec0a11a78aa39400c7ad1e2cd2628cd17040056929c43a3e49f63c3e98"
+        );
+
+        assert!(Seed::new_with_params(
+            &keyphrase,
+            "",
+            Kdf::Scrypt {
+                n: 1000,
+                r: 8,
+                p: 1
+            }
+        )
+        .is_err());
+
+        // above 1 GiB of memory or 16 parallel runs, checked before deriving anything
+        for &kdf in &[
+            Kdf::Scrypt {
+                n: 1 << 21,
+                r: 8,
+                p: 1,
+            },
+            Kdf::Scrypt {
+                n: 1 << 62,
+                r: u32::MAX,
+                p: 1,
+            },
+            Kdf::Scrypt { n: 2, r: 1, p: 17 },
+        ] {
+            assert!(Seed::new_with_params(&keyphrase, "", kdf).is_err());
+        }
+    }
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn new_with_params_argon2id() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let kdf: Kdf = Kdf::Argon2id {
+            memory_kib: 64,
+            iterations: 2,
+            lanes: 2,
+        };
+
+        // Pinned so that the output stays the same across platforms and versions
+        assert_eq!(
+            Seed::new_with_params(&keyphrase, "TREZOR", kdf).unwrap().to_hex(),
+            "8f87e4fabdc809b0fecd1a5c22e780694d70572d075350507375fe467c86929363ca5baca8d2dcb94a60e6a1dc93cdafba1fc142c07007c515191fd9c8e603ee"
+        );
+
+        assert!(Seed::new_with_params(
+            &keyphrase,
+            "",
+            Kdf::Argon2id {
+                memory_kib: 8,
+                iterations: 1,
+                lanes: 2
+            }
+        )
+        .is_err());
+
+        // above 1 GiB of memory, 64 iterations or 16 lanes, checked before deriving anything
+        for &kdf in &[
+            Kdf::Argon2id {
+                memory_kib: 1024 * 1024 + 1,
+                iterations: 1,
+                lanes: 1,
+            },
+            Kdf::Argon2id {
+                memory_kib: 1024,
+                iterations: 65,
+                lanes: 1,
+            },
+            Kdf::Argon2id {
+                memory_kib: 1024,
+                iterations: 1,
+                lanes: 17,
+            },
+        ] {
+            assert!(Seed::new_with_params(&keyphrase, "", kdf).is_err());
+        }
+    }
+
+    #[test]
+    fn new_with_progress_reports() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let mut calls: Vec<f32> = Vec::new();
+
+        let seed: Seed =
+            Seed::new_with_progress(&keyphrase, "TREZOR", Kdf::default(), |done: f32| {
+                calls.push(done)
+            })
+            .unwrap();
+
+        assert_eq!(seed.to_hex(), seed_hex(PHRASE, "TREZOR"));
+        assert_eq!(calls.first(), Some(&0.0));
+        assert_eq!(calls.last(), Some(&1.0));
+        assert!(calls.len() > 2);
+        assert!(calls.windows(2).all(|pair: &[f32]| pair[0] < pair[1]));
+
+        assert!(
+            Seed::new_with_progress(&keyphrase, "", Kdf::Pbkdf2 { rounds: 0 }, |_: f32| {})
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn seed_derive_subkey() {
+        let seed: Seed = Seed::from_hex(&seed_hex(PHRASE, "TREZOR")).unwrap();
+        let hex = |subkey: Vec<u8>| -> String {
+            subkey
+                .iter()
+                .map(|byte: &u8| format!("{:02x}", byte))
+                .collect()
+        };
+
+        // Computed with Python's hmac module
+        assert_eq!(
+            hex(seed.derive_subkey(b"db-encryption", 32).unwrap()),
+            "673e5d6633de5df220efcd85d4364507b136d9f4cf56950b4471490fb1322083"
+        );
+        assert_eq!(
+            hex(seed.derive_subkey(b"api-hmac", 100).unwrap()),
+            "a871eaed3b472cf916fa9c3a8e876f007a707d43d2d006f257eeb0969eeac54096ca0dfd531fb40f4b0e5a5e8ad4ce15699d97a93ab3b7e9ddc571eb56812ec1870b6cc9219bf08d1deb67c7d6416a898ee4bb76586bb18fbefc7a46e09671e6e17eae93"
+        );
+
+        assert!(seed.derive_subkey(b"api-hmac", 0).is_err());
+        assert!(seed.derive_subkey(b"api-hmac", 255 * 64 + 1).is_err());
+    }
+
+    #[test]
+    fn seed_verify() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let other: KeyPhrase = KeyPhrase::from_phrase(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            Language::English,
+        )
+        .unwrap();
+        let seed: Seed = Seed::from_hex(&seed_hex(PHRASE, "TREZOR")).unwrap();
+
+        assert!(seed.verify(&keyphrase, "TREZOR"));
+        assert!(!seed.verify(&keyphrase, ""));
+        assert!(!seed.verify(&other, "TREZOR"));
+
+        let kdf: Kdf = Kdf::Pbkdf2 { rounds: 10 };
+        let stretched: Seed = Seed::new_with_params(&keyphrase, "TREZOR", kdf).unwrap();
+
+        assert!(stretched.verify_with_params(&keyphrase, "TREZOR", kdf));
+        assert!(!stretched.verify(&keyphrase, "TREZOR"));
+        assert!(!seed.verify_with_params(&keyphrase, "TREZOR", Kdf::Pbkdf2 { rounds: 0 }));
+    }
+
+    #[test]
+    fn seed_derive_batch() {
+        let keyphrases: Vec<(KeyPhrase, &str)> = vec![
+            (
+                KeyPhrase::from_phrase(PHRASE, Language::English).unwrap(),
+                "TREZOR",
+            ),
+            (
+                KeyPhrase::from_phrase(
+                    "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                    Language::English,
+                )
+                .unwrap(),
+                "TREZOR",
+            ),
+            (
+                KeyPhrase::from_phrase(PHRASE, Language::English).unwrap(),
+                "",
+            ),
+        ];
+
+        let seeds: Vec<Seed> = Seed::derive_batch(&keyphrases);
+
+        assert_eq!(seeds.len(), 3);
+        assert_eq!(seeds[0].to_hex(), seed_hex(PHRASE, "TREZOR"));
+        assert!(seeds[1].to_hex().starts_with("2e8905819b8723fe"));
+        assert!(seeds[2].verify(&keyphrases[2].0, ""));
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(Seed::derive_batch_parallel(&keyphrases), seeds);
+    }
+
+    #[test]
+    fn seed_from_hex() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let seed: Seed = Seed::new_bip39(&keyphrase, "TREZOR");
+
+        let parsed: Seed = Seed::from_hex(&seed.to_hex()).unwrap();
+        assert_eq!(parsed.to_array(), seed.to_array());
+
+        let parsed: Seed = Seed::from_hex(&format!("{:#X}", seed)).unwrap();
+        assert_eq!(parsed.as_bytes(), seed.as_bytes());
+
+        assert!(Seed::from_hex(&seed.to_hex()[1..]).is_err());
+        assert!(Seed::from_hex(&seed.to_hex()[2..]).is_err());
+        assert!(Seed::from_hex(&"zz".repeat(64)).is_err());
+        assert!(Seed::from_hex(&"+f".repeat(64)).is_err());
+        assert!(Seed::try_from(&[0u8; 32][..]).is_err());
+        assert!(Seed::try_from(&[0u8; 64][..]).is_ok());
+    }
+
+    #[test]
+    fn seed_eq() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+
+        assert_eq!(
+            Seed::new_bip39(&keyphrase, "TREZOR"),
+            Seed::new_bip39(&keyphrase, "TREZOR")
+        );
+        assert_ne!(
+            Seed::new_bip39(&keyphrase, "TREZOR"),
+            Seed::new_bip39(&keyphrase, "")
+        );
+    }
+
+    #[test]
+    fn seed_debug_redacted() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(PHRASE, Language::English).unwrap();
+        let seed: Seed = Seed::new_bip39(&keyphrase, "TREZOR");
+
+        let debug: String = format!("{:?}", seed);
+
+        assert!(debug.starts_with("Seed { len: 64, hash: "));
+        assert!(!debug.to_lowercase().contains("c55257c3"));
+        assert!(seed.display_full().to_string().starts_with("c55257c3"));
+    }
+
+    #[cfg(feature = "bip32")]
+    #[test]
+    fn seed_fingerprint() {
+        // BIP32 test vector 2
+        let seed: Seed = Seed::from_hex(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        )
+        .unwrap();
+
+        assert_eq!(seed.fingerprint().unwrap(), [0xbd, 0x16, 0xbe, 0xe5]);
+    }
+
+    #[cfg(feature = "bip32")]
+    #[test]
+    fn seed_secp256k1_master() {
+        // BIP32 test vector 2
+        let seed: Seed = Seed::from_hex(
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        )
+        .unwrap();
+        let (secret_key, public_key): (SecretKey, PublicKey) = seed.to_secp256k1_master().unwrap();
+
+        let hex = |bytes: &[u8]| -> String {
+            bytes
+                .iter()
+                .map(|byte: &u8| format!("{:02x}", byte))
+                .collect()
+        };
+
+        assert_eq!(
+            hex(&secret_key[..]),
+            "4b03d6fc340455b363f51020ad3ecca4f0850280cf436c70c727923f6db46c3e"
+        );
+        assert_eq!(
+            hex(&public_key.serialize()),
+            "03cbcaa9c98c877a26977d00825c956a238e8dddfbd322cce4f74b0b5bd6ace4a7"
+        );
+    }
+
+    #[test]
+    fn seed_hex_low_bytes() {
+        let mut bytes: [u8; 64] = [0; 64];
+        bytes[1] = 0x03;
+        bytes[63] = 0x0f;
+
+        let seed: Seed = Seed::try_from(&bytes[..]).unwrap();
+
+        assert_eq!(seed.to_hex().len(), 128);
+        assert!(seed.to_hex().starts_with("0003"));
+        assert!(format!("{:X}", seed).ends_with("000F"));
+        assert_eq!(Seed::from_hex(&format!("{:#x}", seed)).unwrap(), seed);
+    }
+}
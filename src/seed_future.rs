@@ -0,0 +1,185 @@
+//! Deriving a [`Seed`][Seed] without blocking the thread, enabled by the `async` feature.
+//!
+//! [Seed]: ../seed/struct.Seed.html
+
+use crate::crypto::{Kdf, Pbkdf2Rounds};
+use crate::error::Error;
+use crate::keyphrase::KeyPhrase;
+use crate::passphrase::Passphrase;
+use crate::seed::{bip39_input, wipe, Seed};
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Number of PBKDF2 rounds computed every time the future is polled
+const ROUNDS_PER_POLL: usize = 128;
+
+/// Future deriving a [`Seed`][Seed], returned by [`Seed::new_async()`][Seed::new_async()]
+///
+/// PBKDF2 is computed a few rounds per poll, the future wakes itself up in between so that the
+/// executor can run other tasks. It doesn't need any particular runtime.
+///
+/// [Seed]: ../seed/struct.Seed.html
+/// [Seed::new_async()]: ../seed/struct.Seed.html#method.new_async
+#[must_use = "futures do nothing unless polled"]
+pub struct SeedFuture {
+    state: State,
+}
+
+enum State {
+    Pbkdf2 {
+        rounds: Box<Pbkdf2Rounds>,
+        seed: Seed,
+    },
+    /// Functions that can't be split, derived in a single poll
+    Deferred {
+        kdf: Kdf,
+        phrase: String,
+        salt: String,
+    },
+    Failed(Error),
+    Done,
+}
+
+impl Future for SeedFuture {
+    type Output = Result<Seed, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Seed, Error>> {
+        if let State::Pbkdf2 { rounds, seed } = &mut self.state {
+            if !rounds.run(ROUNDS_PER_POLL, seed.bytes_mut()) {
+                cx.waker().wake_by_ref();
+
+                return Poll::Pending;
+            }
+        }
+
+        match mem::replace(&mut self.state, State::Done) {
+            State::Pbkdf2 { seed, .. } => Poll::Ready(Ok(seed)),
+            State::Deferred { kdf, phrase, salt } => {
+                let mut seed: Seed = Seed::empty();
+                let result: Result<(), Error> =
+                    kdf.derive(phrase.as_bytes(), &salt, seed.bytes_mut());
+
+                wipe(phrase, salt);
+
+                Poll::Ready(result.map(|()| seed))
+            }
+            State::Failed(error) => Poll::Ready(Err(error)),
+            State::Done => panic!("SeedFuture polled after completion"),
+        }
+    }
+}
+
+impl fmt::Debug for SeedFuture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SeedFuture")
+            .field("done", &matches!(self.state, State::Done))
+            .finish()
+    }
+}
+
+impl Seed {
+    /// Generates the seed from the [`KeyPhrase`][KeyPhrase] and the password without blocking
+    ///
+    /// The seed is the same as the one of
+    /// [`Seed::new_with_params()`][Seed::new_with_params()]. PBKDF2 is computed in small
+    /// steps, yielding to the executor in between, so that UI threads, wasm and async runtimes
+    /// don't freeze during a derivation with many rounds. Other functions are computed in a
+    /// single step, run them on a blocking thread pool instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use keyphrase::{Kdf, KeyPhrase, Language, Seed};
+    ///
+    /// async fn restore(phrase: &str) -> Seed {
+    ///     let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    ///     Seed::new_async(&keyphrase, "", Kdf::default()).await.unwrap()
+    /// }
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [Seed::new_with_params()]: ../seed/struct.Seed.html#method.new_with_params
+    pub fn new_async<P: Into<Passphrase>>(
+        keyphrase: &KeyPhrase,
+        password: P,
+        kdf: Kdf,
+    ) -> SeedFuture {
+        let (phrase, salt): (String, String) = bip39_input(keyphrase, &password.into());
+
+        let state: State = match kdf {
+            Kdf::Pbkdf2 { rounds } => {
+                let mut seed: Seed = Seed::empty();
+                let started: Result<Pbkdf2Rounds, Error> =
+                    Pbkdf2Rounds::new(phrase.as_bytes(), &salt, rounds as usize, seed.bytes_mut());
+
+                wipe(phrase, salt);
+
+                match started {
+                    Ok(rounds) => State::Pbkdf2 {
+                        rounds: Box::new(rounds),
+                        seed,
+                    },
+                    Err(error) => State::Failed(error),
+                }
+            }
+            #[allow(unreachable_patterns)]
+            kdf => State::Deferred { kdf, phrase, salt },
+        };
+
+        SeedFuture { state }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::language::Language;
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Poll the future until it is ready, returning its output and the number of polls
+    fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+        let waker: Waker = Arc::new(NoopWaker).into();
+        let mut cx: Context = Context::from_waker(&waker);
+        let mut future: Pin<Box<F>> = Box::pin(future);
+        let mut polls: usize = 0;
+
+        loop {
+            polls += 1;
+
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return (output, polls);
+            }
+        }
+    }
+
+    #[test]
+    fn new_async_matches_new_bip39() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+
+        let (seed, polls): (Result<Seed, Error>, usize) =
+            block_on(Seed::new_async(&keyphrase, "TREZOR", Kdf::default()));
+
+        assert_eq!(seed.unwrap(), Seed::new_bip39(&keyphrase, "TREZOR"));
+        assert_eq!(polls, 2048 / ROUNDS_PER_POLL);
+
+        let (seed, _): (Result<Seed, Error>, usize) =
+            block_on(Seed::new_async(&keyphrase, "", Kdf::Pbkdf2 { rounds: 0 }));
+
+        assert!(seed.is_err());
+    }
+}
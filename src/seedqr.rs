@@ -0,0 +1,234 @@
+//! SeedQR payloads of a [`KeyPhrase`][KeyPhrase], as scanned by SeedSigner and Specter, enabled
+//! by the `seedqr` feature.
+//!
+//! Standard SeedQRs are numeric QR codes of the word indices, while CompactSeedQRs are binary
+//! QR codes of the entropy. Both are only defined for English keyphrases of 12 or 24 words.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::util::{BitWriter, Bits11};
+
+/// Digits of every word of a standard SeedQR
+const DIGITS_PER_WORD: usize = 4;
+
+impl KeyPhrase {
+    /// Get the digits of the standard SeedQR of the keyphrase
+    ///
+    /// Every word is written as its index in the wordlist, padded to 4 digits. The digits are
+    /// meant to be encoded in a numeric mode QR code.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::UnsupportedLanguage` if the keyphrase is not
+    /// English, or `ErrorKind::InvalidWordLength` if it doesn't have 12 or 24 words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "forum undo fragile fade shy sign arrest garment culture tube off merit";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    ///
+    /// assert_eq!(
+    ///     keyphrase.to_seedqr_digits().unwrap(),
+    ///     "073318950739065415961602009907670428187212261116"
+    /// );
+    /// ```
+    pub fn to_seedqr_digits(&self) -> Result<String, Error> {
+        check_seedqr(self.language(), self.phrase().split(' ').count())?;
+
+        Ok(self
+            .word_indices()
+            .iter()
+            .map(|index: &u16| format!("{:04}", index))
+            .collect())
+    }
+
+    /// Parse the digits of a standard SeedQR, as created by
+    /// [`KeyPhrase::to_seedqr_digits()`][KeyPhrase::to_seedqr_digits()]
+    ///
+    /// The digits must be exactly 48 or 96 ASCII digits, without any separator, and make an
+    /// English keyphrase with a valid checksum. Returns an `Error` of kind
+    /// `ErrorKind::InvalidEncoding` if they don't, or `ErrorKind::InvalidChecksum` if the
+    /// checksum is wrong.
+    ///
+    /// [KeyPhrase::to_seedqr_digits()]: ../keyphrase/struct.KeyPhrase.html#method.to_seedqr_digits
+    pub fn from_seedqr_digits(digits: &str) -> Result<KeyPhrase, Error> {
+        if !digits.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+            Err(ErrorKind::InvalidEncoding(
+                "SeedQR payloads only have digits",
+            ))?;
+        }
+
+        if digits.len() % DIGITS_PER_WORD != 0 {
+            Err(ErrorKind::InvalidEncoding("invalid SeedQR length"))?;
+        }
+
+        check_seedqr(Language::English, digits.len() / DIGITS_PER_WORD)?;
+
+        let indices: Vec<u16> = digits
+            .as_bytes()
+            .chunks(DIGITS_PER_WORD)
+            .map(|chunk: &[u8]| {
+                chunk.iter().fold(0, |index: u16, &digit: &u8| {
+                    index * 10 + u16::from(digit - b'0')
+                })
+            })
+            .collect();
+
+        KeyPhrase::from_word_indices(&indices, Language::English)
+    }
+
+    /// Get the bytes of the CompactSeedQR of the keyphrase
+    ///
+    /// These are the bits of the word indices without the checksum, 16 bytes for 12 words and 32
+    /// bytes for 24 words, meant to be encoded in a byte mode QR code.
+    ///
+    /// Returns an `Error` of kind `ErrorKind::UnsupportedLanguage` if the keyphrase is not
+    /// English, or `ErrorKind::InvalidWordLength` if it doesn't have 12 or 24 words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "forum undo fragile fade shy sign arrest garment culture tube off merit";
+    /// let keyphrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+    /// let bytes = keyphrase.to_compact_seedqr_bytes().unwrap();
+    ///
+    /// assert_eq!(bytes.len(), 16);
+    /// assert_eq!(KeyPhrase::from_compact_seedqr_bytes(&bytes).unwrap().phrase(), phrase);
+    /// ```
+    pub fn to_compact_seedqr_bytes(&self) -> Result<Vec<u8>, Error> {
+        let indices: Vec<u16> = self.word_indices();
+
+        check_seedqr(self.language(), indices.len())?;
+
+        let mut bits: BitWriter = BitWriter::with_capacity(indices.len() * 11);
+
+        indices
+            .iter()
+            .for_each(|&index: &u16| bits.push(Bits11::from(index)));
+
+        let mut bytes: Vec<u8> = bits.into_bytes();
+
+        // Drop the checksum bits, 4 bits for 12 words and a whole byte for 24 words
+        bytes.truncate(indices.len() * 4 / 3);
+
+        Ok(bytes)
+    }
+
+    /// Parse the bytes of a CompactSeedQR, as created by
+    /// [`KeyPhrase::to_compact_seedqr_bytes()`][KeyPhrase::to_compact_seedqr_bytes()]
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidKeysize` unless there are exactly 16 or 32
+    /// bytes.
+    ///
+    /// [KeyPhrase::to_compact_seedqr_bytes()]: ../keyphrase/struct.KeyPhrase.html#method.to_compact_seedqr_bytes
+    pub fn from_compact_seedqr_bytes(bytes: &[u8]) -> Result<KeyPhrase, Error> {
+        if bytes.len() != 16 && bytes.len() != 32 {
+            Err(ErrorKind::InvalidKeysize(bytes.len() * 8))?;
+        }
+
+        KeyPhrase::from_entropy(bytes, Language::English)
+    }
+}
+
+/// Fail unless a keyphrase of this language and number of words has a SeedQR
+fn check_seedqr(lang: Language, words: usize) -> Result<(), Error> {
+    if lang != Language::English {
+        Err(ErrorKind::UnsupportedLanguage)?;
+    }
+
+    if words != 12 && words != 24 {
+        Err(ErrorKind::InvalidWordLength {
+            words,
+            expected: "12 or 24",
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase_type::KeyPhraseType;
+
+    #[test]
+    fn seedqr_test_vectors() {
+        // The examples of the SeedQR specification
+        let vectors: [(&str, &str); 2] = [
+            (
+                "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire",
+                "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+            ),
+            (
+                "forum undo fragile fade shy sign arrest garment culture tube off merit",
+                "073318950739065415961602009907670428187212261116",
+            ),
+        ];
+
+        vectors.iter().for_each(|&(phrase, digits): &(&str, &str)| {
+            let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+
+            assert_eq!(keyphrase.to_seedqr_digits().unwrap(), digits);
+            assert_eq!(
+                KeyPhrase::from_seedqr_digits(digits).unwrap().phrase(),
+                phrase
+            );
+        });
+    }
+
+    #[test]
+    fn compact_seedqr_test_vectors() {
+        // The examples of the CompactSeedQR specification
+        let vectors: [(&str, &str); 2] = [
+            (
+                "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire",
+                "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+            ),
+            (
+                "forum undo fragile fade shy sign arrest garment culture tube off merit",
+                "5bbd9d71a8ec7990831aff359d426545",
+            ),
+        ];
+
+        vectors.iter().for_each(|&(phrase, hex): &(&str, &str)| {
+            let keyphrase: KeyPhrase = KeyPhrase::from_phrase(phrase, Language::English).unwrap();
+            let bytes: Vec<u8> = keyphrase.to_compact_seedqr_bytes().unwrap();
+
+            assert_eq!(keyphrase.entropy_hex(), hex);
+            assert_eq!(bytes, keyphrase.entropy());
+            assert_eq!(
+                KeyPhrase::from_compact_seedqr_bytes(&bytes)
+                    .unwrap()
+                    .phrase(),
+                phrase
+            );
+        });
+
+        assert!(KeyPhrase::from_compact_seedqr_bytes(&[0; 20]).is_err());
+        assert!(KeyPhrase::from_compact_seedqr_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn seedqr_invalid() {
+        let digits: &str = "073318950739065415961602009907670428187212261116";
+
+        // wrong checksum, index out of range, separators, truncated, or 15 words
+        assert!(KeyPhrase::from_seedqr_digits(&digits.replacen("1116", "1117", 1)).is_err());
+        assert!(KeyPhrase::from_seedqr_digits(&digits.replacen("0733", "2048", 1)).is_err());
+        assert!(KeyPhrase::from_seedqr_digits(&digits.replacen("0733", "0733 ", 1)).is_err());
+        assert!(KeyPhrase::from_seedqr_digits(&digits[1..]).is_err());
+        assert!(KeyPhrase::from_seedqr_digits(&(digits.to_string() + "073318950739")).is_err());
+        assert!(KeyPhrase::from_seedqr_digits("").is_err());
+
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words15, Language::English);
+
+        assert!(keyphrase.to_seedqr_digits().is_err());
+        assert!(keyphrase.to_compact_seedqr_bytes().is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! `Serialize` and `Deserialize` implementations, enabled by the `serde` feature.
+//!
+//! A [`Seed`][Seed] is written as lowercase hex in human-readable formats such as JSON, and as
+//! raw bytes in binary formats. Temporary buffers holding seed material are wiped when the
+//! `zeroize` feature is enabled.
+//!
+//! An [`Error`][Error] is written as an object with the stable `code` of its kind, its `message`
+//! and, when the kind has them, the `index` of the faulty word, the `language` whose wordlist was
+//! searched, the number of `words` and the number `expected`, for APIs returning validation
+//! errors. The faulty word itself is left out, since it is part of a secret phrase.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{KeyPhrase, KeyPhraseType, Language, Seed};
+//!
+//! let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+//! let seed = Seed::new_bip39(&keyphrase, "");
+//!
+//! let json = serde_json::to_string(&seed).unwrap();
+//! let restored: Seed = serde_json::from_str(&json).unwrap();
+//!
+//! assert_eq!(seed.as_bytes(), restored.as_bytes());
+//! ```
+//!
+//! [Seed]: ../seed/struct.Seed.html
+//! [Error]: ../error/struct.Error.html
+
+use crate::error::{Error, ErrorKind};
+use crate::language::Language;
+use crate::seed::Seed;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+impl Serialize for Seed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(self.as_bytes());
+        }
+
+        #[allow(unused_mut)]
+        let mut hex: String = self.to_hex();
+
+        let result: Result<S::Ok, S::Error> = serializer.serialize_str(&hex);
+
+        #[cfg(feature = "zeroize")]
+        hex.zeroize();
+
+        result
+    }
+}
+
+impl<'de> Deserialize<'de> for Seed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Seed, D::Error> {
+        // Human-readable formats may write bytes as a sequence, which `deserialize_str` refuses
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(SeedVisitor)
+        } else {
+            deserializer.deserialize_bytes(SeedVisitor)
+        }
+    }
+}
+
+struct SeedVisitor;
+
+impl<'de> Visitor<'de> for SeedVisitor {
+    type Value = Seed;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a 64 byte seed, as hex or raw bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Seed, E> {
+        Seed::from_hex(hex).map_err(E::custom)
+    }
+
+    #[allow(unused_mut)]
+    fn visit_string<E: de::Error>(self, mut hex: String) -> Result<Seed, E> {
+        let seed: Result<Seed, E> = self.visit_str(&hex);
+
+        #[cfg(feature = "zeroize")]
+        hex.zeroize();
+
+        seed
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Seed, E> {
+        Seed::try_from(bytes).map_err(|_| E::invalid_length(bytes.len(), &self))
+    }
+
+    #[allow(unused_mut)]
+    fn visit_byte_buf<E: de::Error>(self, mut bytes: Vec<u8>) -> Result<Seed, E> {
+        let seed: Result<Seed, E> = self.visit_bytes(&bytes);
+
+        #[cfg(feature = "zeroize")]
+        bytes.zeroize();
+
+        seed
+    }
+
+    // Formats without a bytes type, such as JSON arrays, write bytes as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Seed, A::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(64);
+
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+
+        self.visit_byte_buf(bytes)
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.kind().serialize(serializer)
+    }
+}
+
+impl Serialize for ErrorKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, lang): (Option<usize>, Option<Language>) = match *self {
+            ErrorKind::InvalidWord { index, lang, .. } => (Some(index), lang),
+            ErrorKind::InvalidNormalization { index, .. } => (Some(index), None),
+            _ => (None, None),
+        };
+        let (words, expected): (Option<usize>, Option<&str>) = match *self {
+            ErrorKind::InvalidWordLength { words, expected } => (Some(words), Some(expected)),
+            _ => (None, None),
+        };
+        let fields: usize = 2
+            + index.is_some() as usize
+            + lang.is_some() as usize
+            + words.is_some() as usize
+            + expected.is_some() as usize;
+
+        let mut error = serializer.serialize_struct("Error", fields)?;
+
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+
+        match index {
+            Some(index) => error.serialize_field("index", &index)?,
+            None => error.skip_field("index")?,
+        }
+        match lang {
+            Some(lang) => error.serialize_field("language", language_name(lang))?,
+            None => error.skip_field("language")?,
+        }
+        match words {
+            Some(words) => error.serialize_field("words", &words)?,
+            None => error.skip_field("words")?,
+        }
+        match expected {
+            Some(expected) => error.serialize_field("expected", expected)?,
+            None => error.skip_field("expected")?,
+        }
+
+        error.end()
+    }
+}
+
+/// Stable name of a language in serialized errors
+fn language_name(lang: Language) -> &'static str {
+    match lang {
+        Language::English => "english",
+        #[cfg(feature = "chinese-simplified")]
+        Language::ChineseSimplified => "chinese-simplified",
+        #[cfg(feature = "chinese-traditional")]
+        Language::ChineseTraditional => "chinese-traditional",
+        #[cfg(feature = "french")]
+        Language::French => "french",
+        #[cfg(feature = "italian")]
+        Language::Italian => "italian",
+        #[cfg(feature = "japanese")]
+        Language::Japanese => "japanese",
+        #[cfg(feature = "korean")]
+        Language::Korean => "korean",
+        #[cfg(feature = "spanish")]
+        Language::Spanish => "spanish",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keyphrase::KeyPhrase;
+    use crate::language::Language;
+
+    fn seed() -> Seed {
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        )
+        .unwrap();
+
+        Seed::new_bip39(&keyphrase, "")
+    }
+
+    #[test]
+    fn seed_json_hex() {
+        let json: String = serde_json::to_string(&seed()).unwrap();
+
+        assert_eq!(json.len(), 2 + 128);
+
+        let restored: Seed = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.as_bytes(), seed().as_bytes());
+
+        // Arrays of bytes are accepted too
+        let array: String = serde_json::to_string(&serde_bytes(&seed())).unwrap();
+        let restored: Seed = serde_json::from_str(&array).unwrap();
+        assert_eq!(restored.as_bytes(), seed().as_bytes());
+
+        assert!(serde_json::from_str::<Seed>("\"abcd\"").is_err());
+    }
+
+    #[test]
+    fn seed_binary_bytes() {
+        let encoded: Vec<u8> = bincode::serialize(&seed()).unwrap();
+
+        // Length prefix followed by the raw bytes
+        assert_eq!(&encoded[8..], seed().as_bytes());
+
+        let restored: Seed = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(restored.as_bytes(), seed().as_bytes());
+
+        assert!(bincode::deserialize::<Seed>(&encoded[..40]).is_err());
+    }
+
+    #[test]
+    fn error_json() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed positoin rail grid ankle";
+        let error: Error = KeyPhrase::from_phrase(phrase, Language::English).unwrap_err();
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "invalid_word",
+                "message": "invalid word in phrase at position 8, not in the English wordlist",
+                "index": 8,
+                "language": "english",
+            })
+        );
+
+        let error: Error = KeyPhrase::from_phrase("park remain", Language::English).unwrap_err();
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "invalid_word_length",
+                "message": "invalid number of words in phrase: 2, expected 12, 15, 18, 21 or 24",
+                "words": 2,
+                "expected": "12, 15, 18, 21 or 24",
+            })
+        );
+    }
+
+    /// Serialize the raw bytes of a seed with a human-readable serializer
+    fn serde_bytes(seed: &Seed) -> impl Serialize + '_ {
+        struct Bytes<'a>(&'a [u8]);
+
+        impl<'a> Serialize for Bytes<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        Bytes(seed.as_bytes())
+    }
+}
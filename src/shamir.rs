@@ -0,0 +1,353 @@
+//! Shamir secret sharing of any secret over GF(256), enabled by the `shamir` feature.
+//!
+//! Every byte of the secret is the constant term of a random polynomial of degree
+//! `threshold - 1`, and share `i` holds the values of the polynomials at `i`. Any `threshold`
+//! shares give the secret back, while fewer tell nothing about it. The field is the one of
+//! SLIP-39 and AES, but the shares have no SLIP-39 framing: they are raw bytes, with no
+//! identifier or digest, so a wrong or damaged share gives a wrong secret without any error.
+//! Use the `slip39` module to back up a keyphrase as share mnemonics.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::shamir;
+//!
+//! let secret = b"any secret, such as an entropy or a seed";
+//!
+//! // any 3 of the 5 shares give the secret back
+//! let shares = shamir::split(secret, 3, 5).unwrap();
+//!
+//! assert_eq!(shamir::combine(&shares[1..4]).unwrap(), secret.to_vec());
+//! ```
+
+use crate::crypto::gen_random_bytes_from;
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+use crate::crypto::DefaultRng;
+use crate::error::{Error, ErrorKind};
+use once_cell::sync::Lazy;
+use rand_core::{CryptoRng, RngCore};
+use std::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Index of the secret itself, the constant term of the polynomials
+const SECRET_INDEX: u8 = 0;
+
+/// One share of a secret split by [`split()`][split()]
+///
+/// The `Debug` output of a share shows its threshold, index and length, never its value.
+///
+/// [split()]: ./fn.split.html
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+    threshold: u8,
+    index: u8,
+    value: Vec<u8>,
+}
+
+impl Share {
+    /// Make a share from its parts, such as the ones of a share stored elsewhere
+    ///
+    /// The parts are checked by [`combine()`][combine()].
+    ///
+    /// [combine()]: ./fn.combine.html
+    pub fn new(threshold: u8, index: u8, value: Vec<u8>) -> Share {
+        Share {
+            threshold,
+            index,
+            value,
+        }
+    }
+
+    /// Get the number of shares needed to give the secret back
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Get the index of the share, from 1 to 255
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Get the value of the share, as long as the secret
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("len", &self.value.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+/// Split a secret into `shares` shares, any `threshold` of them give it back
+///
+/// The shares have the indices 1 to `shares`. Returns an `Error` of kind
+/// `ErrorKind::InvalidSharing` if the secret is empty, or if the threshold is 0 or more than the
+/// number of shares.
+#[cfg(any(feature = "rand", feature = "getrandom"))]
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<Share>, Error> {
+    split_with_rng(secret, threshold, shares, &mut DefaultRng)
+}
+
+/// Split a secret like [`split()`][split()], with the random polynomials drawn from `rng`
+///
+/// [split()]: ./fn.split.html
+pub fn split_with_rng<R>(
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+    rng: &mut R,
+) -> Result<Vec<Share>, Error>
+where
+    R: RngCore + CryptoRng,
+{
+    if secret.is_empty() {
+        Err(ErrorKind::InvalidSharing("the secret is empty"))?;
+    }
+
+    if threshold == 0 || threshold > shares {
+        Err(ErrorKind::InvalidSharing(
+            "the threshold must be 1 to the number of shares",
+        ))?;
+    }
+
+    // The first shares are random, the polynomials going through them and the secret give the
+    // others
+    let mut base: Vec<(u8, Vec<u8>)> = (1..threshold)
+        .map(|index: u8| (index, gen_random_bytes_from(rng, secret.len())))
+        .collect();
+    base.push((SECRET_INDEX, secret.to_vec()));
+
+    let split: Vec<Share> = (1..=shares)
+        .map(|index: u8| Share {
+            threshold,
+            index,
+            value: interpolate(&base, index).expect("the base shares are distinct"),
+        })
+        .collect();
+
+    #[cfg(feature = "zeroize")]
+    base.iter_mut()
+        .for_each(|(_, value): &mut (u8, Vec<u8>)| value.zeroize());
+
+    Ok(split)
+}
+
+/// Give back a secret from at least `threshold` of its shares, in any order
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidShare` if there are too few shares, if they
+/// don't have the same threshold and length, if a share is given twice, or if an index is 0.
+/// Shares of another secret, or damaged ones, can't be detected and give a wrong secret.
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, Error> {
+    let first: &Share = shares
+        .first()
+        .ok_or(ErrorKind::InvalidShare("there are no shares"))?;
+
+    if shares
+        .iter()
+        .any(|share: &Share| share.threshold != first.threshold)
+    {
+        Err(ErrorKind::InvalidShare(
+            "the shares have different thresholds",
+        ))?;
+    }
+
+    if shares
+        .iter()
+        .any(|share: &Share| share.index == SECRET_INDEX)
+    {
+        Err(ErrorKind::InvalidShare("share indices start at 1"))?;
+    }
+
+    if shares.len() < first.threshold as usize {
+        Err(ErrorKind::InvalidShare("there are too few shares"))?;
+    }
+
+    #[allow(unused_mut)]
+    let mut points: Vec<(u8, Vec<u8>)> = shares
+        .iter()
+        .map(|share: &Share| (share.index, share.value.clone()))
+        .collect();
+    let secret: Result<Vec<u8>, Error> = interpolate(&points, SECRET_INDEX);
+
+    #[cfg(feature = "zeroize")]
+    points
+        .iter_mut()
+        .for_each(|(_, value): &mut (u8, Vec<u8>)| value.zeroize());
+
+    secret
+}
+
+/// Logarithm and exponential tables of GF(256), with the polynomial x^8 + x^4 + x^3 + x + 1
+/// and the generator 3
+struct Gf256 {
+    exp: [u8; 255],
+    log: [u8; 256],
+}
+
+static GF256: Lazy<Gf256> = sync_lazy! {
+    let mut tables: Gf256 = Gf256 { exp: [0; 255], log: [0; 256] };
+    let mut power: u8 = 1;
+
+    for i in 0..255 {
+        tables.exp[i] = power;
+        tables.log[power as usize] = i as u8;
+
+        // multiply by 3
+        power ^= power << 1 ^ if power & 0x80 != 0 { 0x1b } else { 0 };
+    }
+
+    tables
+};
+
+/// Evaluate at `x` the polynomial going through the shares, byte by byte
+pub(crate) fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Result<Vec<u8>, Error> {
+    if let Some((_, value)) = shares
+        .iter()
+        .find(|(index, _): &&(u8, Vec<u8>)| *index == x)
+    {
+        return Ok(value.clone());
+    }
+
+    let mut indexes: Vec<u8> = shares
+        .iter()
+        .map(|&(index, _): &(u8, Vec<u8>)| index)
+        .collect();
+    indexes.sort_unstable();
+    indexes.dedup();
+
+    if indexes.len() != shares.len() {
+        Err(ErrorKind::InvalidShare("the same share was given twice"))?;
+    }
+
+    let log = |value: u8| -> usize { GF256.log[value as usize] as usize };
+    let log_product: usize = shares
+        .iter()
+        .map(|&(index, _): &(u8, Vec<u8>)| log(index ^ x))
+        .sum::<usize>();
+    let mut result: Vec<u8> = vec![0; shares[0].1.len()];
+
+    for (index, value) in shares {
+        if value.len() != result.len() {
+            Err(ErrorKind::InvalidShare("the shares have different lengths"))?;
+        }
+
+        let mut log_basis: usize = log_product + 255 * shares.len() - log(index ^ x);
+
+        for &(other, _) in shares {
+            if other != *index {
+                log_basis -= log(index ^ other);
+            }
+        }
+
+        result
+            .iter_mut()
+            .zip(value)
+            .filter(|&(_, &byte): &(&mut u8, &u8)| byte != 0)
+            .for_each(|(result, &byte): (&mut u8, &u8)| {
+                *result ^= GF256.exp[(log_basis + log(byte)) % 255]
+            });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn shamir_split_combine() {
+        let secret: Vec<u8> = (0..32).collect();
+        let mut rng: StdRng = StdRng::seed_from_u64(7);
+        let shares: Vec<Share> = split_with_rng(&secret, 3, 5, &mut rng).unwrap();
+
+        assert_eq!(shares.len(), 5);
+        assert_eq!(
+            shares
+                .iter()
+                .map(|share: &Share| share.index)
+                .collect::<Vec<u8>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(shares
+            .iter()
+            .all(|share: &Share| share.value() != &secret[..]));
+
+        // any 3 shares, in any order, and more than 3
+        [[0, 1, 2], [4, 2, 0], [1, 3, 4]]
+            .iter()
+            .for_each(|picked: &[usize; 3]| {
+                let picked: Vec<Share> =
+                    picked.iter().map(|&i: &usize| shares[i].clone()).collect();
+
+                assert_eq!(combine(&picked).unwrap(), secret);
+            });
+        assert_eq!(combine(&shares).unwrap(), secret);
+
+        // a threshold of 1 copies the secret
+        let shares: Vec<Share> = split(&secret, 1, 3).unwrap();
+
+        assert!(shares
+            .iter()
+            .all(|share: &Share| share.value() == &secret[..]));
+        assert_eq!(combine(&shares[2..]).unwrap(), secret);
+    }
+
+    #[test]
+    fn shamir_known_shares() {
+        // the line 0x42 + x, so share i is 0x42 ^ i
+        let share = |index: u8| -> Share { Share::new(2, index, vec![0x42 ^ index, 0x42 ^ index]) };
+
+        assert_eq!(combine(&[share(1), share(2)]).unwrap(), vec![0x42, 0x42]);
+        assert_eq!(combine(&[share(3), share(255)]).unwrap(), vec![0x42, 0x42]);
+
+        // the value is never printed
+        assert_eq!(
+            format!("{:?}", share(1)),
+            "Share { threshold: 2, index: 1, len: 2 }"
+        );
+    }
+
+    #[test]
+    fn shamir_invalid() {
+        let shares: Vec<Share> = split(b"secret", 2, 3).unwrap();
+
+        assert!(split(b"", 2, 3).is_err());
+        assert!(split(b"secret", 0, 3).is_err());
+        assert!(split(b"secret", 4, 3).is_err());
+
+        // too few shares, the same share twice, no shares
+        assert!(combine(&shares[..1]).is_err());
+        assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+        assert!(combine(&[]).is_err());
+
+        let other: Share = Share::new(3, shares[1].index(), shares[1].value().to_vec());
+
+        assert!(combine(&[shares[0].clone(), other]).is_err());
+
+        let value: &[u8] = shares[1].value();
+        let other: Share = Share::new(2, shares[1].index(), value[..value.len() - 1].to_vec());
+
+        assert!(combine(&[shares[0].clone(), other]).is_err());
+
+        let other: Share = Share::new(2, 0, shares[1].value().to_vec());
+
+        assert!(combine(&[shares[0].clone(), other]).is_err());
+    }
+}
@@ -0,0 +1,772 @@
+//! SLIP-0039 Shamir backups of a [`KeyPhrase`][KeyPhrase], enabled by the `slip39` feature.
+//!
+//! The entropy of the keyphrase is the SLIP-39 master secret. It is encrypted with the
+//! password, then split into groups of share mnemonics: any `group_threshold` groups, each with
+//! `member_threshold` of its shares, give the keyphrase back, while fewer shares tell nothing
+//! about it.
+//!
+//! SLIP-39 wallets use the master secret itself as their BIP32 seed, not the BIP39 seed of the
+//! keyphrase, so restoring the shares in such a wallet gives other accounts than the keyphrase.
+//! Use [`combine()`][combine()] to get the keyphrase back.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{slip39, KeyPhrase, KeyPhraseType, Language};
+//!
+//! let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+//!
+//! // a single group of 3 shares, any 2 of them restore the keyphrase
+//! let groups = slip39::split(&keyphrase, "", 1, &[(2, 3)], &mut rand::thread_rng()).unwrap();
+//! let restored = slip39::combine(&groups[0][1..], "", Language::English).unwrap();
+//!
+//! assert_eq!(restored.phrase(), keyphrase.phrase());
+//! ```
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+//! [combine()]: ./fn.combine.html
+
+use crate::crypto::{constant_time_eq, gen_random_bytes_from, hmac_sha256, pbkdf2_sha256};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+use crate::passphrase::Passphrase;
+use crate::shamir::interpolate;
+use once_cell::sync::Lazy;
+use rand_core::{CryptoRng, RngCore};
+use std::collections::BTreeMap;
+use std::mem;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The 1024 words of SLIP-39 share mnemonics, in alphabetical order
+static WORDLIST: Lazy<Vec<&'static str>> =
+    sync_lazy! { include_str!("langs/slip39.txt").split_whitespace().collect() };
+
+/// Customization string of the checksum and of the encryption salt of non-extendable shares
+const CUSTOMIZATION: &[u8] = b"shamir";
+
+/// Customization string of the checksum of extendable shares
+const EXTENDABLE_CUSTOMIZATION: &[u8] = b"shamir_extendable";
+
+/// Bits encoded by each word
+const RADIX_BITS: usize = 10;
+
+/// Words of the identifier, extendable flag and iteration exponent, then of the group and
+/// member parameters, then of the checksum
+const METADATA_WORDS: usize = 7;
+
+/// Words of the checksum
+const CHECKSUM_WORDS: usize = 3;
+
+/// Shortest share value, for a 128 bits master secret
+const MIN_SECRET_BYTES: usize = 16;
+
+/// Maximum number of groups, and of shares in a group
+const MAX_SHARES: usize = 16;
+
+/// The iteration exponent of new shares, 20000 PBKDF2 rounds in total
+const ITERATION_EXPONENT: u8 = 1;
+
+/// PBKDF2 rounds of the encryption, for an iteration exponent of 0
+const BASE_ITERATIONS: usize = 10_000;
+
+/// Rounds of the Feistel network encrypting the master secret
+const FEISTEL_ROUNDS: u8 = 4;
+
+/// Index of the digest share
+const DIGEST_INDEX: u8 = 254;
+
+/// Index of the secret itself
+const SECRET_INDEX: u8 = 255;
+
+/// Length of the digest protecting the secret
+const DIGEST_BYTES: usize = 4;
+
+/// Split the entropy of a [`KeyPhrase`][KeyPhrase] into SLIP-39 share mnemonics
+///
+/// `groups` has the `(member_threshold, member_count)` of every group, and any
+/// `group_threshold` of the groups are needed to restore the keyphrase. Returns the mnemonics
+/// of every group, in order.
+///
+/// The password is needed again to restore the keyphrase, but any password gives a valid
+/// keyphrase back: a wrong one can't be detected.
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidSharing` if there are more than 16 groups or
+/// shares in a group, if a threshold is 0 or more than its count, if a group with a threshold of
+/// 1 has more than one share, or if the password isn't printable ASCII.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+pub fn split<P, R>(
+    keyphrase: &KeyPhrase,
+    password: P,
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    rng: &mut R,
+) -> Result<Vec<Vec<String>>, Error>
+where
+    P: Into<Passphrase>,
+    R: RngCore + CryptoRng,
+{
+    let password: Passphrase = password.into();
+
+    check_password(&password)?;
+
+    if groups.is_empty() || groups.len() > MAX_SHARES {
+        Err(ErrorKind::InvalidSharing("there must be 1 to 16 groups"))?;
+    }
+
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        Err(ErrorKind::InvalidSharing(
+            "the group threshold must be 1 to the number of groups",
+        ))?;
+    }
+
+    for &(member_threshold, member_count) in groups {
+        if member_count == 0 || member_count as usize > MAX_SHARES {
+            Err(ErrorKind::InvalidSharing(
+                "there must be 1 to 16 shares in a group",
+            ))?;
+        }
+
+        if member_threshold == 0 || member_threshold > member_count {
+            Err(ErrorKind::InvalidSharing(
+                "the member threshold must be 1 to the number of shares",
+            ))?;
+        }
+
+        if member_threshold == 1 && member_count > 1 {
+            Err(ErrorKind::InvalidSharing(
+                "a group with a member threshold of 1 must have a single share",
+            ))?;
+        }
+    }
+
+    let identifier: u16 = (rng.next_u32() & 0x7fff) as u16;
+    #[allow(unused_mut)]
+    let mut encrypted: Vec<u8> = feistel(
+        keyphrase.entropy(),
+        password.expose_secret().as_bytes(),
+        ITERATION_EXPONENT,
+        identifier,
+        true,
+        true,
+    );
+    #[allow(unused_mut)]
+    let mut group_secrets: Vec<(u8, Vec<u8>)> =
+        split_secret(group_threshold, groups.len() as u8, &encrypted, rng);
+
+    let mnemonics: Vec<Vec<String>> = group_secrets
+        .iter()
+        .zip(groups)
+        .map(
+            |((group_index, group_secret), &(member_threshold, member_count)): (
+                &(u8, Vec<u8>),
+                &(u8, u8),
+            )| {
+                split_secret(member_threshold, member_count, group_secret, rng)
+                    .into_iter()
+                    .map(|(member_index, value): (u8, Vec<u8>)| {
+                        Share {
+                            identifier,
+                            extendable: true,
+                            iteration_exponent: ITERATION_EXPONENT,
+                            group_index: *group_index,
+                            group_threshold,
+                            group_count: groups.len() as u8,
+                            member_index,
+                            member_threshold,
+                            value,
+                        }
+                        .to_mnemonic()
+                    })
+                    .collect()
+            },
+        )
+        .collect();
+
+    #[cfg(feature = "zeroize")]
+    {
+        encrypted.zeroize();
+        group_secrets
+            .iter_mut()
+            .for_each(|(_, group_secret): &mut (u8, Vec<u8>)| group_secret.zeroize());
+    }
+
+    Ok(mnemonics)
+}
+
+/// Restore a [`KeyPhrase`][KeyPhrase] from SLIP-39 share mnemonics
+///
+/// The shares must be those of exactly `group_threshold` groups, with exactly
+/// `member_threshold` shares of each group, and `password` must be the one given to
+/// [`split()`][split()]. A wrong password gives another keyphrase.
+///
+/// Returns an `Error` of kind `ErrorKind::InvalidShare` if a share is not a valid mnemonic,
+/// if the shares don't belong together, if there are too few or too many of them, or if they
+/// don't match their digest. Master secrets that can't be a keyphrase return
+/// `ErrorKind::InvalidKeysize`.
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+/// [split()]: ./fn.split.html
+pub fn combine<S, P>(mnemonics: &[S], password: P, lang: Language) -> Result<KeyPhrase, Error>
+where
+    S: AsRef<str>,
+    P: Into<Passphrase>,
+{
+    let password: Passphrase = password.into();
+
+    check_password(&password)?;
+
+    let shares: Vec<Share> = mnemonics
+        .iter()
+        .map(|mnemonic: &S| Share::from_mnemonic(mnemonic.as_ref()))
+        .collect::<Result<Vec<Share>, Error>>()?;
+    let first: &Share = shares
+        .first()
+        .ok_or(ErrorKind::InvalidShare("no shares given"))?;
+
+    if shares.iter().any(|share: &Share| {
+        share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+    }) {
+        Err(ErrorKind::InvalidShare(
+            "the shares belong to different sets",
+        ))?;
+    }
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+
+    for share in &shares {
+        groups.entry(share.group_index).or_default().push(share);
+    }
+
+    if groups.len() != first.group_threshold as usize {
+        Err(ErrorKind::InvalidShare(
+            "the number of groups doesn't match the group threshold",
+        ))?;
+    }
+
+    #[allow(unused_mut)]
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = groups
+        .iter()
+        .map(|(&group_index, members): (&u8, &Vec<&Share>)| {
+            let member_threshold: u8 = members[0].member_threshold;
+
+            if members
+                .iter()
+                .any(|member: &&Share| member.member_threshold != member_threshold)
+            {
+                Err(ErrorKind::InvalidShare(
+                    "the shares of a group have different thresholds",
+                ))?;
+            }
+
+            if members.len() != member_threshold as usize {
+                Err(ErrorKind::InvalidShare(
+                    "the number of shares doesn't match the member threshold",
+                ))?;
+            }
+
+            let values: Vec<(u8, Vec<u8>)> = members
+                .iter()
+                .map(|member: &&Share| (member.member_index, member.value.clone()))
+                .collect();
+
+            Ok((group_index, recover_secret(member_threshold, &values)?))
+        })
+        .collect::<Result<Vec<(u8, Vec<u8>)>, Error>>()?;
+
+    #[allow(unused_mut)]
+    let mut encrypted: Vec<u8> = recover_secret(first.group_threshold, &group_secrets)?;
+    #[allow(unused_mut)]
+    let mut entropy: Vec<u8> = feistel(
+        &encrypted,
+        password.expose_secret().as_bytes(),
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+        false,
+    );
+    let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&entropy, lang);
+
+    #[cfg(feature = "zeroize")]
+    {
+        entropy.zeroize();
+        encrypted.zeroize();
+        group_secrets
+            .iter_mut()
+            .for_each(|(_, group_secret): &mut (u8, Vec<u8>)| group_secret.zeroize());
+    }
+
+    keyphrase
+}
+
+/// A share, and the parameters of the set it belongs to
+struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl Share {
+    fn to_mnemonic(&self) -> String {
+        let id_exp: u32 = (self.identifier as u32) << 5
+            | (self.extendable as u32) << 4
+            | self.iteration_exponent as u32;
+        let parameters: u32 = (self.group_index as u32) << 16
+            | (self.group_threshold as u32 - 1) << 12
+            | (self.group_count as u32 - 1) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold as u32 - 1);
+
+        let mut words: Vec<u16> = vec![
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp & 0x3ff) as u16,
+            (parameters >> RADIX_BITS) as u16,
+            (parameters & 0x3ff) as u16,
+        ];
+        words.extend(bytes_to_words(&self.value));
+
+        let checksum: u32 =
+            rs1024_polymod(customization(self.extendable), &words, &[0; CHECKSUM_WORDS]) ^ 1;
+        words.extend(
+            (0..CHECKSUM_WORDS)
+                .rev()
+                .map(|i: usize| ((checksum >> (i * RADIX_BITS)) & 0x3ff) as u16),
+        );
+
+        words
+            .iter()
+            .map(|&word: &u16| WORDLIST[word as usize])
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    fn from_mnemonic(mnemonic: &str) -> Result<Share, Error> {
+        let words: Vec<u16> = mnemonic
+            .split_whitespace()
+            .enumerate()
+            .map(|(index, word): (usize, &str)| {
+                let word: String = word.to_lowercase();
+
+                WORDLIST
+                    .binary_search(&word.as_str())
+                    .map(|position: usize| position as u16)
+                    .map_err(|_| {
+                        ErrorKind::InvalidWord {
+                            word,
+                            index,
+                            lang: None,
+                        }
+                        .into()
+                    })
+            })
+            .collect::<Result<Vec<u16>, Error>>()?;
+
+        let min_words: usize = METADATA_WORDS + (MIN_SECRET_BYTES * 8).div_ceil(RADIX_BITS);
+
+        if words.len() < min_words {
+            Err(ErrorKind::InvalidWordLength {
+                words: words.len(),
+                expected: "at least 20",
+            })?;
+        }
+
+        let extendable: bool = (words[1] >> 4) & 1 == 1;
+
+        if rs1024_polymod(customization(extendable), &words, &[]) != 1 {
+            Err(ErrorKind::InvalidChecksum)?;
+        }
+
+        let id_exp: u32 = (words[0] as u32) << RADIX_BITS | words[1] as u32;
+        let parameters: u32 = (words[2] as u32) << RADIX_BITS | words[3] as u32;
+        let share: Share = Share {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: (parameters >> 16) as u8,
+            group_threshold: ((parameters >> 12) & 0xf) as u8 + 1,
+            group_count: ((parameters >> 8) & 0xf) as u8 + 1,
+            member_index: ((parameters >> 4) & 0xf) as u8,
+            member_threshold: (parameters & 0xf) as u8 + 1,
+            value: words_to_bytes(&words[4..words.len() - CHECKSUM_WORDS])?,
+        };
+
+        if share.group_count < share.group_threshold {
+            Err(ErrorKind::InvalidShare(
+                "the group threshold is more than the number of groups",
+            ))?;
+        }
+
+        Ok(share)
+    }
+}
+
+/// Fail unless the password only has printable ASCII characters, as SLIP-39 requires
+fn check_password(password: &Passphrase) -> Result<(), Error> {
+    if !password
+        .expose_secret()
+        .bytes()
+        .all(|byte: u8| (32..=126).contains(&byte))
+    {
+        Err(ErrorKind::InvalidSharing(
+            "the password must be printable ASCII",
+        ))?;
+    }
+
+    Ok(())
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        EXTENDABLE_CUSTOMIZATION
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+/// Compute the RS1024 checksum polynomial of the customization string and the words
+fn rs1024_polymod(customization: &[u8], words: &[u16], padding: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0x00e0_e040,
+        0x01c1_c080,
+        0x0383_8100,
+        0x0707_0200,
+        0x0e0e_0009,
+        0x1c0c_2412,
+        0x3808_6c24,
+        0x3090_fc48,
+        0x21b1_f890,
+        0x03f3_f120,
+    ];
+
+    customization
+        .iter()
+        .map(|&byte: &u8| byte as u32)
+        .chain(words.iter().chain(padding).map(|&word: &u16| word as u32))
+        .fold(1, |checksum: u32, value: u32| {
+            let top: u32 = checksum >> 20;
+
+            GENERATOR
+                .iter()
+                .enumerate()
+                .filter(|&(i, _): &(usize, &u32)| (top >> i) & 1 == 1)
+                .fold(
+                    (checksum & 0xf_ffff) << RADIX_BITS ^ value,
+                    |checksum: u32, (_, generator): (usize, &u32)| checksum ^ generator,
+                )
+        })
+}
+
+/// Split bytes into words of 10 bits, padded with leading zero bits
+fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+    let word_count: usize = (bytes.len() * 8).div_ceil(RADIX_BITS);
+    let mut words: Vec<u16> = Vec::with_capacity(word_count);
+    let mut accumulator: u32 = 0;
+    let mut bits: usize = word_count * RADIX_BITS - bytes.len() * 8;
+
+    for &byte in bytes {
+        accumulator = accumulator << 8 | byte as u32;
+        bits += 8;
+
+        while bits >= RADIX_BITS {
+            bits -= RADIX_BITS;
+            words.push((accumulator >> bits) as u16 & 0x3ff);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    words
+}
+
+/// Join words of 10 bits into bytes, checking that the leading padding bits are zero
+fn words_to_bytes(words: &[u16]) -> Result<Vec<u8>, Error> {
+    let padding: usize = words.len() * RADIX_BITS % 16;
+
+    if padding > 8 {
+        Err(ErrorKind::InvalidShare("invalid share length"))?;
+    }
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(words.len() * RADIX_BITS / 8);
+    let mut accumulator: u32 = 0;
+    let mut bits: usize = 0;
+    let mut skip: usize = padding;
+
+    for &word in words {
+        accumulator = accumulator << RADIX_BITS | word as u32;
+        bits += RADIX_BITS;
+
+        if skip > 0 {
+            if accumulator >> (bits - skip) != 0 {
+                Err(ErrorKind::InvalidShare("invalid share padding"))?;
+            }
+
+            bits -= skip;
+            skip = 0;
+        }
+
+        while bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Encrypt, or decrypt, the master secret with a 4 rounds Feistel network
+fn feistel(
+    secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    encrypt: bool,
+) -> Vec<u8> {
+    let half: usize = secret.len() / 2;
+    let mut left: Vec<u8> = secret[..half].to_vec();
+    let mut right: Vec<u8> = secret[half..].to_vec();
+    let mut salt: Vec<u8> = Vec::new();
+
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+
+    let iterations: usize = (BASE_ITERATIONS << iteration_exponent) / FEISTEL_ROUNDS as usize;
+
+    for round in 0..FEISTEL_ROUNDS {
+        let step: u8 = if encrypt {
+            round
+        } else {
+            FEISTEL_ROUNDS - 1 - round
+        };
+        let mut password: Vec<u8> = vec![step];
+        password.extend_from_slice(passphrase);
+        let mut round_salt: Vec<u8> = salt.clone();
+        round_salt.extend_from_slice(&right);
+
+        let mut output: Vec<u8> = vec![0; half];
+        pbkdf2_sha256(&password, &round_salt, iterations, &mut output);
+
+        left.iter_mut()
+            .zip(&output)
+            .for_each(|(byte, output): (&mut u8, &u8)| *byte ^= output);
+        mem::swap(&mut left, &mut right);
+
+        #[cfg(feature = "zeroize")]
+        {
+            password.zeroize();
+            output.zeroize();
+        }
+    }
+
+    right.extend_from_slice(&left);
+
+    #[cfg(feature = "zeroize")]
+    left.zeroize();
+
+    right
+}
+
+/// Split a secret into `count` shares, any `threshold` of them give it back
+fn split_secret<R>(threshold: u8, count: u8, secret: &[u8], rng: &mut R) -> Vec<(u8, Vec<u8>)>
+where
+    R: RngCore + CryptoRng,
+{
+    if threshold == 1 {
+        return (0..count)
+            .map(|index: u8| (index, secret.to_vec()))
+            .collect();
+    }
+
+    let random_count: u8 = threshold - 2;
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..random_count)
+        .map(|index: u8| (index, gen_random_bytes_from(rng, secret.len())))
+        .collect();
+
+    let random: Vec<u8> = gen_random_bytes_from(rng, secret.len() - DIGEST_BYTES);
+    let mut digest: Vec<u8> = hmac_sha256(&random, secret)[..DIGEST_BYTES].to_vec();
+    digest.extend_from_slice(&random);
+
+    let mut base: Vec<(u8, Vec<u8>)> = shares.clone();
+    base.push((DIGEST_INDEX, digest));
+    base.push((SECRET_INDEX, secret.to_vec()));
+
+    for index in random_count..count {
+        shares.push((
+            index,
+            interpolate(&base, index).expect("the base shares are distinct"),
+        ));
+    }
+
+    #[cfg(feature = "zeroize")]
+    base.iter_mut()
+        .for_each(|(_, value): &mut (u8, Vec<u8>)| value.zeroize());
+
+    shares
+}
+
+/// Recover a secret from `threshold` shares, checking its digest
+fn recover_secret(threshold: u8, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret: Vec<u8> = interpolate(shares, SECRET_INDEX)?;
+    let digest: Vec<u8> = interpolate(shares, DIGEST_INDEX)?;
+
+    if !constant_time_eq(
+        &digest[..DIGEST_BYTES],
+        &hmac_sha256(&digest[DIGEST_BYTES..], &secret)[..DIGEST_BYTES],
+    ) {
+        Err(ErrorKind::InvalidShare(
+            "the shares don't match their digest",
+        ))?;
+    }
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn slip39_test_vectors() {
+        let vectors: [(&[&str], &str); 5] = [
+            (
+                &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+                "bb54aac4b89dc868ba37d9cc21b2cece",
+            ),
+            (
+                &[
+                    "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                    "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+                ],
+                "b43ceb7e57a0ea8766221624d01b0864",
+            ),
+            (
+                &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+                "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+            ),
+            (
+                &["testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn"],
+                "1679b4516e0ee5954351d288a838f45e",
+            ),
+            (
+                &[
+                    "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+                    "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                ],
+                "b43ceb7e57a0ea8766221624d01b0864",
+            ),
+        ];
+
+        vectors
+            .iter()
+            .for_each(|&(mnemonics, entropy): &(&[&str], &str)| {
+                let keyphrase: KeyPhrase = combine(mnemonics, "TREZOR", Language::English).unwrap();
+
+                assert_eq!(keyphrase.entropy_hex(), entropy);
+            });
+    }
+
+    #[test]
+    fn slip39_share_roundtrip() {
+        let mnemonic: &str = "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed";
+        let share: Share = Share::from_mnemonic(mnemonic).unwrap();
+
+        assert!(!share.extendable);
+        assert_eq!(share.member_threshold, 2);
+        assert_eq!(share.to_mnemonic(), mnemonic);
+
+        let typo: String = mnemonic.replacen("wildlife", "window", 1);
+
+        assert!(Share::from_mnemonic(&typo).is_err());
+        assert!(Share::from_mnemonic("shadow pistol academic").is_err());
+    }
+
+    #[test]
+    fn slip39_split_combine() {
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will",
+            Language::English,
+        )
+        .unwrap();
+
+        // 2 of 3 groups: a single share, 2 of 3 shares and 3 of 5 shares
+        let groups: Vec<Vec<String>> =
+            split(&keyphrase, "TREZOR", 2, &[(1, 1), (2, 3), (3, 5)], &mut rng).unwrap();
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[2].len(), 5);
+        assert!(groups[0][0].split(' ').count() == 27);
+
+        let mnemonics: Vec<&str> = vec![&groups[0][0], &groups[2][4], &groups[2][0], &groups[2][2]];
+        let restored: KeyPhrase = combine(&mnemonics, "TREZOR", Language::English).unwrap();
+
+        assert_eq!(restored.phrase(), keyphrase.phrase());
+
+        let restored: KeyPhrase = combine(
+            &[&groups[1][2], &groups[1][0], &groups[0][0]],
+            "TREZOR",
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(restored.phrase(), keyphrase.phrase());
+
+        // a wrong password gives another keyphrase
+        let other: KeyPhrase = combine(
+            &[&groups[1][2], &groups[1][0], &groups[0][0]],
+            "",
+            Language::English,
+        )
+        .unwrap();
+
+        assert_ne!(other.phrase(), keyphrase.phrase());
+
+        // too few shares, or the same share twice
+        assert!(combine(&[&groups[0][0], &groups[1][0]], "TREZOR", Language::English).is_err());
+        assert!(combine(
+            &[&groups[0][0], &groups[2][0], &groups[2][0], &groups[2][1]],
+            "TREZOR",
+            Language::English
+        )
+        .is_err());
+        assert!(combine(&groups[2][..3], "TREZOR", Language::English).is_err());
+    }
+
+    #[test]
+    fn slip39_invalid_sharing() {
+        let mut rng: StdRng = StdRng::seed_from_u64(42);
+        let keyphrase: KeyPhrase = KeyPhrase::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )
+        .unwrap();
+
+        assert!(split(&keyphrase, "", 1, &[], &mut rng).is_err());
+        assert!(split(&keyphrase, "", 2, &[(2, 3)], &mut rng).is_err());
+        assert!(split(&keyphrase, "", 1, &[(1, 3)], &mut rng).is_err());
+        assert!(split(&keyphrase, "", 1, &[(4, 3)], &mut rng).is_err());
+        assert!(split(&keyphrase, "", 1, &[(2, 17)], &mut rng).is_err());
+        assert!(split(&keyphrase, "pässword", 1, &[(2, 3)], &mut rng).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! A seeded RNG for reproducible tests and examples, enabled by the `test-rng` feature.
+//!
+//! [`TestRng`][TestRng] gives the same [`KeyPhrase`][KeyPhrase]s for the same seed, so that
+//! integration tests and documentation can assert on the exact phrases generated. Anyone knowing
+//! the seed knows the phrases, so only enable the feature in the `dev-dependencies` of your
+//! crate, with `resolver = "2"`, as the first resolver also enables the features of
+//! `dev-dependencies` in release builds.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{KeyPhrase, KeyPhraseBuilder, KeyPhraseType, Language, TestRng};
+//!
+//! let first = KeyPhrase::new_seeded(KeyPhraseType::Words12, Language::English, 42);
+//! let second = KeyPhraseBuilder::new()
+//!     .rng(TestRng::seed_from_u64(42))
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(first.phrase(), second.phrase());
+//! ```
+//!
+//! [TestRng]: ./struct.TestRng.html
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::gen_random_bytes_from;
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, Error as RngError, RngCore, SeedableRng};
+use std::fmt;
+
+/// ChaCha20 seeded by the caller, only meant for tests
+///
+/// It is a `CryptoRng`, so that it can be given to
+/// [`KeyPhrase::new_with_rng()`][KeyPhrase::new_with_rng()] and
+/// [`KeyPhraseBuilder::rng()`][KeyPhraseBuilder::rng()], but its output is only as secret as its
+/// seed.
+///
+/// [KeyPhrase::new_with_rng()]: ../keyphrase/struct.KeyPhrase.html#method.new_with_rng
+/// [KeyPhraseBuilder::rng()]: ../keyphrase_builder/struct.KeyPhraseBuilder.html#method.rng
+#[derive(Clone)]
+pub struct TestRng {
+    inner: ChaChaRng,
+}
+
+impl TestRng {
+    /// Create a generator from a small seed, such as the number of a test
+    pub fn seed_from_u64(seed: u64) -> TestRng {
+        SeedableRng::seed_from_u64(seed)
+    }
+}
+
+impl SeedableRng for TestRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> TestRng {
+        TestRng {
+            inner: ChaChaRng::from_seed(seed),
+        }
+    }
+}
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for TestRng {}
+
+impl fmt::Debug for TestRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TestRng").finish()
+    }
+}
+
+impl KeyPhrase {
+    /// Generate the [`KeyPhrase`][KeyPhrase] of a seed, the same for every call with it
+    ///
+    /// Equivalent to [`KeyPhrase::new()`][KeyPhrase::new()] for tests, drawing the entropy from a
+    /// [`TestRng`][TestRng] seeded with `seed`. Never use it for real keyphrases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new_seeded(KeyPhraseType::Words12, Language::English, 7);
+    ///
+    /// assert_eq!(
+    ///     keyphrase.phrase(),
+    ///     KeyPhrase::new_seeded(KeyPhraseType::Words12, Language::English, 7).phrase()
+    /// );
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::new()]: ../keyphrase/struct.KeyPhrase.html#method.new
+    /// [TestRng]: ../test_rng/struct.TestRng.html
+    pub fn new_seeded(keyphrase_type: KeyPhraseType, lang: Language, seed: u64) -> KeyPhrase {
+        let entropy: Vec<u8> = gen_random_bytes_from(
+            &mut TestRng::seed_from_u64(seed),
+            keyphrase_type.entropy_bits() / 8,
+        );
+
+        KeyPhrase::from_entropy_unchecked(entropy, lang)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_keyphrases() {
+        let first: KeyPhrase = KeyPhrase::new_seeded(KeyPhraseType::Words24, Language::English, 1);
+        let again: KeyPhrase = KeyPhrase::new_seeded(KeyPhraseType::Words24, Language::English, 1);
+        let other: KeyPhrase = KeyPhrase::new_seeded(KeyPhraseType::Words24, Language::English, 2);
+
+        assert_eq!(first.phrase(), again.phrase());
+        assert_ne!(first.phrase(), other.phrase());
+        assert_eq!(
+            first.phrase(),
+            KeyPhrase::new_with_rng(
+                KeyPhraseType::Words24,
+                Language::English,
+                &mut TestRng::seed_from_u64(1)
+            )
+            .phrase()
+        );
+    }
+}
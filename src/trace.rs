@@ -0,0 +1,235 @@
+//! Sanitized record of how a phrase went through parsing, for bug reports.
+//!
+//! [`KeyPhrase::from_phrase_traced()`][KeyPhrase::from_phrase_traced()] records a
+//! [`ParseTrace`][ParseTrace] along the way and returns it with the result, so it's at hand
+//! next to the error. The trace only ever holds lengths, counts and positions, never any content of the phrase, so users can attach it to a bug report and
+//! maintainers can reproduce the issue without receiving secret material.
+//!
+//! [KeyPhrase::from_phrase_traced()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase_traced
+//! [ParseTrace]: ./struct.ParseTrace.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordMap};
+use std::fmt;
+
+/// A single step of a [`ParseTrace`][ParseTrace]
+///
+/// [ParseTrace]: ./struct.ParseTrace.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// Name of the step
+    pub name: &'static str,
+    /// Length of the input of the step, in the unit of the step (bytes or words)
+    pub len_before: usize,
+    /// Length of the output of the step, in the unit of the step (bytes or words)
+    pub len_after: usize,
+    /// Whether the step changed its input, or for checks, whether the check failed
+    pub changed: bool,
+}
+
+/// Sanitized record of the parsing of a phrase
+///
+/// The normalization steps (`trim`, `collapse_whitespace`, `lowercase`, `non_ascii`) are not
+/// applied by the parser, the trace records whether applying them *would* have changed the
+/// input, which is the most common cause of a phrase being rejected.
+///
+/// The trace implements `Display`, so it can be attached to an error as context.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseTrace {
+    steps: Vec<TraceStep>,
+    failed_at: Option<usize>,
+}
+
+impl ParseTrace {
+    /// Create an empty trace
+    pub fn new() -> Self {
+        ParseTrace::default()
+    }
+
+    /// The steps recorded so far
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    /// Position of the first word that is not in the wordlist, if any
+    pub fn failed_at(&self) -> Option<usize> {
+        self.failed_at
+    }
+
+    fn record(&mut self, name: &'static str, len_before: usize, len_after: usize, changed: bool) {
+        self.steps.push(TraceStep {
+            name,
+            len_before,
+            len_after,
+            changed,
+        });
+    }
+}
+
+impl fmt::Display for ParseTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i != 0 {
+                f.write_str("; ")?;
+            }
+
+            write!(
+                f,
+                "{}: {} -> {}{}",
+                step.name,
+                step.len_before,
+                step.len_after,
+                if step.changed { " (changed)" } else { "" }
+            )?;
+        }
+
+        if let Some(position) = self.failed_at {
+            write!(f, "; first unknown word at index {}", position)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl KeyPhrase {
+    /// Create a [`KeyPhrase`][KeyPhrase] from an existing keyphrase, recording a sanitized trace
+    ///
+    /// Behaves exactly like [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()], and returns
+    /// the trace of the steps along with the result, whether parsing succeeds or not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = " Park remain person kitchen mule spell knee armed position rail grid ankle";
+    /// let (result, trace) = KeyPhrase::from_phrase_traced(phrase, Language::English);
+    ///
+    /// if let Err(error) = result {
+    ///     // Safe to share, doesn't contain any of the words
+    ///     println!("{} ({})", error, trace);
+    /// }
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+    pub fn from_phrase_traced<S>(
+        phrase: S,
+        lang: Language,
+    ) -> (Result<KeyPhrase, Error>, ParseTrace)
+    where
+        S: Into<String>,
+    {
+        let phrase: String = phrase.into();
+        let len: usize = phrase.len();
+        let mut trace: ParseTrace = ParseTrace::new();
+
+        let trimmed: &str = phrase.trim();
+        trace.record("trim", len, trimmed.len(), trimmed.len() != len);
+
+        let collapsed: usize = phrase
+            .split_whitespace()
+            .map(|word: &str| word.len() + 1)
+            .sum();
+        let collapsed: usize = collapsed.saturating_sub(1);
+        trace.record("collapse_whitespace", len, collapsed, collapsed != len);
+
+        let uppercase: usize = phrase.chars().filter(|c: &char| c.is_uppercase()).count();
+        trace.record("lowercase", len, len, uppercase != 0);
+
+        let non_ascii: usize = phrase.chars().filter(|c: &char| !c.is_ascii()).count();
+        trace.record("non_ascii", len, non_ascii, non_ascii != 0);
+
+        let word_count: usize = phrase.split(' ').count();
+        trace.record("split_words", len, word_count, false);
+
+        let wordmap: &WordMap = lang.wordmap();
+        let unknown: Vec<usize> = phrase
+            .split(' ')
+            .enumerate()
+            .filter(|&(_, word): &(usize, &str)| wordmap.get_bits(word).is_err())
+            .map(|(i, _): (usize, &str)| i)
+            .collect();
+        trace.failed_at = unknown.first().cloned();
+        trace.record(
+            "lookup_words",
+            word_count,
+            word_count - unknown.len(),
+            !unknown.is_empty(),
+        );
+
+        let standard: bool = KeyPhraseType::for_word_count(word_count).is_ok();
+        trace.record("word_count", word_count, word_count, !standard);
+
+        let result: Result<KeyPhrase, Error> = KeyPhrase::from_phrase(phrase, lang);
+
+        if unknown.is_empty() && standard {
+            let bad_checksum: bool = match result {
+                Err(ref error) => matches!(
+                    error.kind(),
+                    ErrorKind::InvalidChecksum
+                ),
+                Ok(_) => false,
+            };
+
+            trace.record("checksum", word_count, word_count, bad_checksum);
+        }
+
+        (result, trace)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trace_valid_phrase() {
+        let phrase: &str =
+            "park remain person kitchen mule spell knee armed position rail grid ankle";
+        let (result, trace): (Result<KeyPhrase, Error>, ParseTrace) =
+            KeyPhrase::from_phrase_traced(phrase, Language::English);
+
+        assert!(result.is_ok());
+        assert!(trace.steps().iter().all(|step: &TraceStep| !step.changed));
+        assert_eq!(trace.steps().last().unwrap().name, "checksum");
+        assert_eq!(trace.failed_at(), None);
+    }
+
+    #[test]
+    fn trace_invalid_phrase() {
+        let phrase: &str =
+            " Park remain person kitchen  mule spell knee armed position rail grid ankle";
+        let (result, trace): (Result<KeyPhrase, Error>, ParseTrace) =
+            KeyPhrase::from_phrase_traced(phrase, Language::English);
+
+        assert!(result.is_err());
+
+        let changed: Vec<&str> = trace
+            .steps()
+            .iter()
+            .filter(|step: &&TraceStep| step.changed)
+            .map(|step: &TraceStep| step.name)
+            .collect();
+
+        assert_eq!(
+            changed,
+            vec![
+                "trim",
+                "collapse_whitespace",
+                "lowercase",
+                "lookup_words",
+                "word_count"
+            ]
+        );
+        assert_eq!(trace.failed_at(), Some(0));
+
+        let report: String = trace.to_string();
+
+        assert!(phrase
+            .split_whitespace()
+            .all(|word: &str| !report.contains(word)));
+    }
+}
@@ -0,0 +1,282 @@
+//! The legacy 2-of-3 card split of a 24 words [`KeyPhrase`][KeyPhrase], enabled by the
+//! `legacy-2of3` feature.
+//!
+//! The phrase is cut in three thirds A (words 1 to 8), B (9 to 16) and C (17 to 24), and every
+//! card holds two of them: card 1 has A and B, card 2 has A and C, card 3 has B and C. Any two
+//! cards hold every word.
+//!
+//! # Security
+//!
+//! This scheme predates SLIP-39 and is only here to restore existing cards. Its trade-offs are
+//! poor:
+//!
+//! - a single card gives away 16 of the 24 words, leaving 88 bits to guess, or 80 bits when the
+//!   missing third holds the checksum word. That is far below the 256 bits of the phrase and
+//!   within reach of well funded attackers
+//! - two cards give away the whole phrase, so they must be kept as safe as the phrase itself
+//! - nothing tells a genuine card from a forged one
+//!
+//! Phrases of less than 24 words are refused, as a card would leave 44 bits of a 12 words phrase,
+//! which can be guessed on a single computer. New backups should use the `slip39` module instead.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordList, WordMap};
+use crate::util::Bits11;
+
+/// Words of every third of the phrase
+const THIRD_WORDS: usize = 8;
+
+/// The thirds of the phrase on every card, by card number
+const CARD_THIRDS: [[usize; 2]; 3] = [[0, 1], [0, 2], [1, 2]];
+
+/// One of the three cards of [`KeyPhrase::split_2of3()`][KeyPhrase::split_2of3()]
+///
+/// See the `legacy-2of3` feature for the security trade-offs of this scheme.
+///
+/// [KeyPhrase::split_2of3()]: ../keyphrase/struct.KeyPhrase.html#method.split_2of3
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwoOfThreeCard {
+    /// Number of the card, from 1 to 3
+    pub number: u8,
+    /// Language of the words
+    pub language: Language,
+    /// The 16 words of the card, in the order of the phrase
+    pub words: Vec<&'static str>,
+}
+
+impl TwoOfThreeCard {
+    /// Create a card from its number and the 16 words written on it
+    ///
+    /// Returns an `Error` of kind `ErrorKind::InvalidShare` if the number isn't 1, 2 or 3,
+    /// `ErrorKind::InvalidWordLength` unless there are 16 words, or `ErrorKind::InvalidWord` if
+    /// a word isn't in the wordlist.
+    pub fn from_words(number: u8, words: &str, lang: Language) -> Result<TwoOfThreeCard, Error> {
+        if !(1..=3).contains(&number) {
+            Err(ErrorKind::InvalidShare("card numbers are 1 to 3"))?;
+        }
+
+        let wordmap: &WordMap = lang.wordmap();
+        let wordlist: &WordList = lang.wordlist();
+        let words: Vec<&'static str> = words
+            .split_whitespace()
+            .enumerate()
+            .map(|(index, word): (usize, &str)| {
+                wordmap
+                    .get_bits(word)
+                    .map(|bits: Bits11| wordlist.get_word(bits))
+                    .map_err(|_| {
+                        ErrorKind::InvalidWord {
+                            word: word.to_string(),
+                            index,
+                            lang: Some(lang),
+                        }
+                        .into()
+                    })
+            })
+            .collect::<Result<Vec<&'static str>, Error>>()?;
+
+        if words.len() != 2 * THIRD_WORDS {
+            Err(ErrorKind::InvalidWordLength {
+                words: words.len(),
+                expected: "16",
+            })?;
+        }
+
+        Ok(TwoOfThreeCard {
+            number,
+            language: lang,
+            words,
+        })
+    }
+
+    /// Get the positions in the phrase of the words of the card, starting at 1
+    ///
+    /// Panics if the number of the card isn't 1, 2 or 3.
+    pub fn positions(&self) -> Vec<usize> {
+        CARD_THIRDS[self.number as usize - 1]
+            .iter()
+            .flat_map(|&third: &usize| third * THIRD_WORDS + 1..=(third + 1) * THIRD_WORDS)
+            .collect()
+    }
+}
+
+impl KeyPhrase {
+    /// Split a 24 words keyphrase into the three cards of the legacy 2-of-3 scheme
+    ///
+    /// Any two of the cards restore the keyphrase with
+    /// [`KeyPhrase::combine_2of3()`][KeyPhrase::combine_2of3()], but a single card gives away
+    /// two thirds of the words: read the security section of the `legacy-2of3` feature before
+    /// using it, and prefer SLIP-39 for new backups. Returns an `Error` of kind
+    /// `ErrorKind::InvalidWordLength` unless the keyphrase has 24 words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::new(KeyPhraseType::Words24, Language::English);
+    /// let cards = keyphrase.split_2of3().unwrap();
+    ///
+    /// assert_eq!(cards[0].positions(), (1..=16).collect::<Vec<usize>>());
+    ///
+    /// let restored = KeyPhrase::combine_2of3(&[cards[2].clone(), cards[1].clone()]).unwrap();
+    ///
+    /// assert_eq!(restored.phrase(), keyphrase.phrase());
+    /// ```
+    ///
+    /// [KeyPhrase::combine_2of3()]: ../keyphrase/struct.KeyPhrase.html#method.combine_2of3
+    pub fn split_2of3(&self) -> Result<[TwoOfThreeCard; 3], Error> {
+        let wordlist: &WordList = self.language().wordlist();
+        let words: Vec<&'static str> = self
+            .word_indices()
+            .into_iter()
+            .map(|index: u16| wordlist.get_word(Bits11::from(index)))
+            .collect();
+
+        if words.len() != KeyPhraseType::Words24.word_count() {
+            Err(ErrorKind::InvalidWordLength {
+                words: words.len(),
+                expected: "24",
+            })?;
+        }
+
+        let card = |number: u8| -> TwoOfThreeCard {
+            TwoOfThreeCard {
+                number,
+                language: self.language(),
+                words: CARD_THIRDS[number as usize - 1]
+                    .iter()
+                    .flat_map(|&third: &usize| {
+                        words[third * THIRD_WORDS..(third + 1) * THIRD_WORDS].to_vec()
+                    })
+                    .collect(),
+            }
+        };
+
+        Ok([card(1), card(2), card(3)])
+    }
+
+    /// Restore a keyphrase from at least two different cards of
+    /// [`KeyPhrase::split_2of3()`][KeyPhrase::split_2of3()]
+    ///
+    /// Words found on several cards must match. Returns an `Error` of kind
+    /// `ErrorKind::InvalidShare` if they don't or if there aren't two different cards,
+    /// `ErrorKind::LanguageMismatch` if the cards are in different languages, or
+    /// `ErrorKind::InvalidChecksum` if the restored phrase is invalid.
+    ///
+    /// [KeyPhrase::split_2of3()]: ../keyphrase/struct.KeyPhrase.html#method.split_2of3
+    pub fn combine_2of3(cards: &[TwoOfThreeCard]) -> Result<KeyPhrase, Error> {
+        let first: &TwoOfThreeCard = cards
+            .first()
+            .ok_or(ErrorKind::InvalidShare("two different cards are needed"))?;
+        let wordmap: &WordMap = first.language.wordmap();
+        let mut indices: [Option<u16>; 3 * THIRD_WORDS] = [None; 3 * THIRD_WORDS];
+
+        for card in cards {
+            if card.language != first.language {
+                Err(ErrorKind::LanguageMismatch(first.language, card.language))?;
+            }
+
+            if !(1..=3).contains(&card.number) || card.words.len() != 2 * THIRD_WORDS {
+                Err(ErrorKind::InvalidShare("not a 2-of-3 card"))?;
+            }
+
+            for (position, word) in card.positions().into_iter().zip(&card.words) {
+                let index: u16 = wordmap.get_bits(word)?.into();
+
+                match indices[position - 1] {
+                    Some(known) if known != index => {
+                        Err(ErrorKind::InvalidShare("the cards don't match"))?
+                    }
+                    _ => indices[position - 1] = Some(index),
+                }
+            }
+        }
+
+        let indices: Vec<u16> = indices
+            .iter()
+            .map(|index: &Option<u16>| {
+                index.ok_or(ErrorKind::InvalidShare("two different cards are needed"))
+            })
+            .collect::<Result<Vec<u16>, ErrorKind>>()?;
+
+        KeyPhrase::from_word_indices(&indices, first.language)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn keyphrase() -> KeyPhrase {
+        KeyPhrase::from_phrase(
+            "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+            Language::English,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn split_2of3_cards() {
+        let cards: [TwoOfThreeCard; 3] = keyphrase().split_2of3().unwrap();
+
+        assert_eq!(cards[0].words[..2], ["hamster", "diagram"]);
+        assert_eq!(cards[1].words[8..10], ["maple", "dilemma"]);
+        assert_eq!(cards[2].words[..2], ["slide", "toddler"]);
+        assert_eq!(
+            cards[1].positions(),
+            (1..=8).chain(17..=24).collect::<Vec<usize>>()
+        );
+        assert_eq!(cards[2].positions(), (9..=24).collect::<Vec<usize>>());
+
+        // every pair of cards, in any order, restores the phrase
+        [(0, 1), (0, 2), (1, 2), (2, 0)]
+            .iter()
+            .for_each(|&(a, b): &(usize, usize)| {
+                assert_eq!(
+                    KeyPhrase::combine_2of3(&[cards[a].clone(), cards[b].clone()])
+                        .unwrap()
+                        .phrase(),
+                    keyphrase().phrase()
+                );
+            });
+        assert!(KeyPhrase::combine_2of3(&cards).is_ok());
+
+        let card: TwoOfThreeCard =
+            TwoOfThreeCard::from_words(3, &cards[2].words.join(" "), Language::English).unwrap();
+
+        assert_eq!(card, cards[2]);
+    }
+
+    #[test]
+    fn combine_2of3_invalid() {
+        let cards: [TwoOfThreeCard; 3] = keyphrase().split_2of3().unwrap();
+
+        // a single card, twice the same card, or no card at all
+        assert!(KeyPhrase::combine_2of3(&cards[..1]).is_err());
+        assert!(KeyPhrase::combine_2of3(&[cards[1].clone(), cards[1].clone()]).is_err());
+        assert!(KeyPhrase::combine_2of3(&[]).is_err());
+
+        // cards that don't agree on the words they share
+        let mut forged: TwoOfThreeCard = cards[1].clone();
+        forged.words[0] = "abandon";
+
+        assert!(KeyPhrase::combine_2of3(&[cards[0].clone(), forged]).is_err());
+
+        // cards of a 12 words phrase can't be made, nor cards with another number or length
+        assert!(KeyPhrase::new(KeyPhraseType::Words12, Language::English)
+            .split_2of3()
+            .is_err());
+        assert!(
+            TwoOfThreeCard::from_words(4, &cards[0].words.join(" "), Language::English).is_err()
+        );
+        assert!(
+            TwoOfThreeCard::from_words(1, &cards[0].words[1..].join(" "), Language::English)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Blockchain Commons UR encoding of keyphrase entropy, enabled by the `ur` feature.
+//!
+//! A `ur:crypto-seed/…` string carries the entropy of a keyphrase as a CBOR map, written in
+//! minimal bytewords, which is what airgapped signers scan from animated or static QR codes.
+//! Only single part URs are supported, the payload of a seed always fits in one QR code.
+//!
+//! # Example
+//!
+//! ```
+//! use keyphrase::{ur, KeyPhrase, KeyPhraseType, Language};
+//!
+//! let keyphrase = KeyPhrase::new(KeyPhraseType::Words12, Language::English);
+//! let encoded = keyphrase.to_ur_crypto_seed();
+//!
+//! assert!(encoded.starts_with("ur:crypto-seed/"));
+//!
+//! let entropy = ur::decode_crypto_seed(&encoded).unwrap();
+//!
+//! assert_eq!(entropy, keyphrase.entropy());
+//! ```
+
+use crate::bytewords::{self, BytewordsStyle};
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::language::Language;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Scheme and type of the URs of seeds
+const PREFIX: &str = "ur:crypto-seed/";
+
+/// Key of the seed payload in the crypto-seed CBOR map
+const PAYLOAD_KEY: u64 = 1;
+
+/// CBOR major types of the crypto-seed map
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+/// Most tags read in front of a skipped item
+const MAX_TAGS: usize = 32;
+
+/// Encode entropy as a `ur:crypto-seed` string
+///
+/// The result is lowercase, write it in uppercase to use the alphanumeric mode of QR codes.
+pub fn encode_crypto_seed(entropy: &[u8]) -> String {
+    let mut cbor: Vec<u8> = Vec::with_capacity(entropy.len() + 4);
+
+    write_head(&mut cbor, MAJOR_MAP, 1);
+    write_head(&mut cbor, MAJOR_UNSIGNED, PAYLOAD_KEY);
+    write_head(&mut cbor, MAJOR_BYTES, entropy.len() as u64);
+    cbor.extend_from_slice(entropy);
+
+    let encoded: String = String::from(PREFIX) + &bytewords::encode(&cbor, BytewordsStyle::Minimal);
+
+    #[cfg(feature = "zeroize")]
+    cbor.zeroize();
+
+    encoded
+}
+
+/// Decode the entropy of a `ur:crypto-seed` string, in either case
+///
+/// The optional creation date, name and note of the seed are ignored. Returns an `Error` of kind
+/// `ErrorKind::InvalidEncoding` if the string is not a single part crypto-seed UR, or
+/// `ErrorKind::InvalidChecksum` if its checksum doesn't match.
+pub fn decode_crypto_seed(encoded: &str) -> Result<Vec<u8>, Error> {
+    let encoded: String = encoded.trim().to_ascii_lowercase();
+
+    if !encoded.starts_with(PREFIX) {
+        Err(ErrorKind::InvalidEncoding("not a crypto-seed UR"))?;
+    }
+
+    if encoded[PREFIX.len()..].contains('/') {
+        Err(ErrorKind::InvalidEncoding(
+            "multi part URs are not supported",
+        ))?;
+    }
+
+    #[allow(unused_mut)]
+    let mut cbor: Vec<u8> = bytewords::decode(&encoded[PREFIX.len()..], BytewordsStyle::Minimal)?;
+    let entropy: Result<Vec<u8>, Error> = read_crypto_seed(&cbor);
+
+    #[cfg(feature = "zeroize")]
+    cbor.zeroize();
+
+    entropy
+}
+
+impl KeyPhrase {
+    /// Get the entropy of the keyphrase as a `ur:crypto-seed` string
+    ///
+    /// See [`ur::encode_crypto_seed()`][ur::encode_crypto_seed()].
+    ///
+    /// [ur::encode_crypto_seed()]: ../ur/fn.encode_crypto_seed.html
+    pub fn to_ur_crypto_seed(&self) -> String {
+        encode_crypto_seed(self.entropy())
+    }
+
+    /// Parse a keyphrase from a `ur:crypto-seed` string
+    ///
+    /// See [`ur::decode_crypto_seed()`][ur::decode_crypto_seed()]. Returns an `Error` of kind
+    /// `ErrorKind::InvalidKeysize` if the seed can't be the entropy of a keyphrase.
+    ///
+    /// [ur::decode_crypto_seed()]: ../ur/fn.decode_crypto_seed.html
+    pub fn from_ur_crypto_seed(encoded: &str, lang: Language) -> Result<KeyPhrase, Error> {
+        #[allow(unused_mut)]
+        let mut entropy: Vec<u8> = decode_crypto_seed(encoded)?;
+        let keyphrase: Result<KeyPhrase, Error> = KeyPhrase::from_entropy(&entropy, lang);
+
+        #[cfg(feature = "zeroize")]
+        entropy.zeroize();
+
+        keyphrase
+    }
+}
+
+/// Write the head of a CBOR item, its major type and argument
+fn write_head(cbor: &mut Vec<u8>, major: u8, argument: u64) {
+    match argument {
+        0..=23 => cbor.push(major << 5 | argument as u8),
+        24..=0xff => cbor.extend_from_slice(&[major << 5 | 24, argument as u8]),
+        0x100..=0xffff => {
+            cbor.push(major << 5 | 25);
+            cbor.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            cbor.push(major << 5 | 26);
+            cbor.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            cbor.push(major << 5 | 27);
+            cbor.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Reader of the subset of CBOR used by crypto-seed
+struct Reader<'a> {
+    cbor: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.cbor.len() - self.offset < len {
+            Err(ErrorKind::InvalidEncoding("truncated CBOR"))?;
+        }
+
+        self.offset += len;
+
+        Ok(&self.cbor[self.offset - len..self.offset])
+    }
+
+    /// Read the head of an item, its major type and argument
+    fn head(&mut self) -> Result<(u8, u64), Error> {
+        let initial: u8 = self.take(1)?[0];
+        let argument: u64 = match initial & 0x1f {
+            short @ 0..=23 => u64::from(short),
+            size @ 24..=27 => self
+                .take(1 << (size - 24))?
+                .iter()
+                .fold(0, |argument: u64, &byte: &u8| {
+                    argument << 8 | u64::from(byte)
+                }),
+            _ => Err(ErrorKind::InvalidEncoding("unsupported CBOR item"))?,
+        };
+
+        Ok((initial >> 5, argument))
+    }
+
+    /// Skip a whole item, which may be tagged up to `MAX_TAGS` times
+    fn skip(&mut self) -> Result<(), Error> {
+        let mut tags: usize = 0;
+
+        loop {
+            match self.head()? {
+                (MAJOR_UNSIGNED, _) => {}
+                (MAJOR_BYTES, len) | (MAJOR_TEXT, len) => {
+                    self.take(len as usize)?;
+                }
+                (MAJOR_TAG, _) if tags < MAX_TAGS => {
+                    tags += 1;
+                    continue;
+                }
+                (MAJOR_TAG, _) => Err(ErrorKind::InvalidEncoding("too many CBOR tags"))?,
+                _ => Err(ErrorKind::InvalidEncoding("unsupported CBOR item"))?,
+            }
+
+            return Ok(());
+        }
+    }
+}
+
+/// Read the payload of a crypto-seed CBOR map
+fn read_crypto_seed(cbor: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut reader: Reader = Reader { cbor, offset: 0 };
+    let mut payload: Option<Vec<u8>> = None;
+
+    let entries: u64 = match reader.head()? {
+        (MAJOR_MAP, entries) => entries,
+        _ => Err(ErrorKind::InvalidEncoding("crypto-seed is a CBOR map"))?,
+    };
+
+    for _ in 0..entries {
+        match reader.head()? {
+            (MAJOR_UNSIGNED, PAYLOAD_KEY) => match reader.head()? {
+                (MAJOR_BYTES, len) => payload = Some(reader.take(len as usize)?.to_vec()),
+                _ => Err(ErrorKind::InvalidEncoding(
+                    "the seed payload is a byte string",
+                ))?,
+            },
+            (MAJOR_UNSIGNED, _) => reader.skip()?,
+            _ => Err(ErrorKind::InvalidEncoding("crypto-seed keys are integers"))?,
+        }
+    }
+
+    if reader.offset != cbor.len() {
+        Err(ErrorKind::InvalidEncoding("trailing CBOR"))?;
+    }
+
+    Ok(payload.ok_or(ErrorKind::InvalidEncoding("missing seed payload"))?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ur_crypto_seed() {
+        let keyphrase: KeyPhrase = KeyPhrase::from_entropy(
+            &[
+                0x59, 0xf2, 0x29, 0x3a, 0x5b, 0xce, 0x7d, 0x4d, 0xe5, 0x9e, 0x71, 0xb4, 0x20, 0x7a,
+                0xc5, 0xd2,
+            ],
+            Language::English,
+        )
+        .unwrap();
+        let encoded: String = keyphrase.to_ur_crypto_seed();
+
+        assert_eq!(
+            encoded,
+            "ur:crypto-seed/oyadgdhkwzdtfthptokigtvwnnjsqzcxknsktdhpyljeda"
+        );
+        assert_eq!(
+            KeyPhrase::from_ur_crypto_seed(&encoded.to_uppercase(), Language::English)
+                .unwrap()
+                .phrase(),
+            keyphrase.phrase()
+        );
+
+        // with a creation date, which is skipped
+        assert_eq!(
+            decode_crypto_seed(
+                "ur:crypto-seed/oeadgdhkwzdtfthptokigtvwnnjsqzcxknsktdaotpiecffltnsfeeonhf"
+            )
+            .unwrap(),
+            keyphrase.entropy()
+        );
+
+        let encoded: String = encode_crypto_seed(&[0xff; 32]);
+
+        assert_eq!(
+            encoded,
+            "ur:crypto-seed/oyadhdcxzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmzmlkvsrlsp"
+        );
+        assert_eq!(decode_crypto_seed(&encoded).unwrap(), vec![0xff; 32]);
+    }
+
+    #[test]
+    fn ur_crypto_seed_invalid() {
+        let encoded: &str = "ur:crypto-seed/oyadgdhkwzdtfthptokigtvwnnjsqzcxknsktdhpyljeda";
+
+        assert!(decode_crypto_seed(&encoded.replace("crypto-seed", "bytes")).is_err());
+        assert!(decode_crypto_seed(&encoded.replace("/oyad", "/1-2/oyad")).is_err());
+        assert!(decode_crypto_seed(&encoded.replace("ljeda", "ljedy")).is_err());
+        assert!(decode_crypto_seed("ur:crypto-seed/").is_err());
+        // a 15 bytes seed is a valid UR, but not the entropy of a keyphrase
+        assert!(
+            KeyPhrase::from_ur_crypto_seed(&encode_crypto_seed(&[0; 15]), Language::English)
+                .is_err()
+        );
+
+        // an unknown entry may be tagged, but only so many times
+        let mut cbor: Vec<u8> = vec![0xa2, 0x01, 0x50];
+        cbor.extend_from_slice(&[0; 16]);
+        cbor.push(0x02);
+        cbor.extend(vec![0xc1; MAX_TAGS]);
+        cbor.push(0x00);
+
+        assert_eq!(read_crypto_seed(&cbor).unwrap(), vec![0; 16]);
+
+        cbor.insert(cbor.len() - 1, 0xc1);
+
+        assert!(read_crypto_seed(&cbor).is_err());
+    }
+}
@@ -1,17 +1,25 @@
+use crate::error::ErrorKind;
+
 pub(crate) trait IterExt: Iterator {
     fn join<R>(&mut self, glue: &str) -> R
     where
         R: From<String>,
         Self::Item: AsRef<str>,
+        Self: Clone,
     {
+        // Size the buffer exactly up front, a reallocation would leave a partial copy of the
+        // (secret) output behind in freed memory
+        let capacity: usize = self
+            .clone()
+            .map(|item: Self::Item| item.as_ref().len() + glue.len())
+            .sum();
+
         let first: <Self as std::iter::Iterator>::Item = match self.next() {
             Some(first) => first,
             None => return String::new().into(),
         };
 
-        let (lower, _): (usize, _) = self.size_hint();
-
-        let mut buffer = String::with_capacity(lower * (10 + glue.len()));
+        let mut buffer = String::with_capacity(capacity);
 
         buffer.push_str(first.as_ref());
 
@@ -57,6 +65,18 @@ impl<'a> Bits for &'a u8 {
     }
 }
 
+/// A single bit
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Bit(pub bool);
+
+impl Bits for Bit {
+    const SIZE: usize = 1;
+
+    fn bits(self) -> u32 {
+        self.0 as u32
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Bits11(u16);
 
@@ -80,6 +100,7 @@ impl From<Bits11> for u16 {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct BitWriter {
     offset: usize,
     remainder: u32,
@@ -127,6 +148,7 @@ impl BitWriter {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct BitIter<In: Bits, Out: Bits, I: Iterator<Item = In> + Sized> {
     _phantom: ::std::marker::PhantomData<Out>,
     source: I,
@@ -192,3 +214,29 @@ pub(crate) fn checksum(source: u8, bits: u8) -> u8 {
 
     source >> (8 - bits)
 }
+
+/// Decode hex of both cases with an optional `0x` prefix, every byte written with two digits
+pub(crate) fn decode_hex(hex: &str) -> Result<Vec<u8>, ErrorKind> {
+    let digits: &str = hex
+        .strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .unwrap_or(hex);
+
+    if digits.len() % 2 != 0 {
+        Err(ErrorKind::InvalidEncoding("odd number of hex digits"))?;
+    }
+
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair: &[u8]| {
+            let high: Option<u32> = char::from(pair[0]).to_digit(16);
+            let low: Option<u32> = char::from(pair[1]).to_digit(16);
+
+            match (high, low) {
+                (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                _ => Err(ErrorKind::InvalidEncoding("invalid hex digit")),
+            }
+        })
+        .collect()
+}
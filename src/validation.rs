@@ -0,0 +1,282 @@
+//! Validating a phrase and reporting every problem at once, for user interfaces.
+
+use crate::checksum::Sha256Checksum;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordMap};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Every problem found in a phrase by
+/// [`KeyPhrase::validate_detailed()`][KeyPhrase::validate_detailed()]
+///
+/// [KeyPhrase::validate_detailed()]: ../keyphrase/struct.KeyPhrase.html#method.validate_detailed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of words in the phrase
+    pub word_count: usize,
+    /// Whether the number of words is one defined by BIP39
+    pub word_count_valid: bool,
+    /// Position and content of every word that is not in the wordlist
+    pub invalid_words: Vec<(usize, String)>,
+    /// Other languages whose wordlist contains every word of the phrase, only filled in when some
+    /// words are not in the wordlist of the requested language
+    pub language_hints: Vec<Language>,
+    /// Whether the checksum matched, `None` if it couldn't be checked because of the problems
+    /// above
+    pub checksum_valid: Option<bool>,
+}
+
+impl ValidationReport {
+    /// Whether the phrase is a valid keyphrase
+    pub fn is_valid(&self) -> bool {
+        self.checksum_valid == Some(true)
+    }
+}
+
+impl KeyPhrase {
+    /// Check that every word of a phrase is in the wordlist, without validating the checksum
+    ///
+    /// Meant for phrases that are still being typed: any number of words up to the longest
+    /// valid length (24) is accepted, otherwise an `Error` of kind
+    /// `ErrorKind::InvalidWordLength` is returned. For every word, the result tells whether it
+    /// is in the wordlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let words = KeyPhrase::validate_words("park remain persn", Language::English).unwrap();
+    ///
+    /// assert_eq!(words, vec![true, true, false]);
+    /// ```
+    pub fn validate_words(phrase: &str, lang: Language) -> Result<Vec<bool>, Error> {
+        let wordmap: &WordMap = lang.wordmap();
+        let word_count: usize = phrase.split_whitespace().count();
+
+        if word_count == 0 || word_count > KeyPhraseType::Words24.word_count() {
+            Err(ErrorKind::InvalidWordLength {
+                words: word_count,
+                expected: "1 to 24",
+            })?;
+        }
+
+        let words: Vec<bool> = phrase
+            .split_whitespace()
+            .map(|word: &str| wordmap.get_bits(word).is_ok())
+            .collect();
+
+        Ok(words)
+    }
+
+    /// Validate a keyphrase, listing every problem instead of stopping at the first one
+    ///
+    /// Unlike [`KeyPhrase::validate()`][KeyPhrase::validate()], which returns the first error,
+    /// this checks every word so that a UI can highlight all of them at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrase = "park remain persn kitchen mule spell knee armed position rail grid ankel";
+    ///
+    /// let report = KeyPhrase::validate_detailed(phrase, Language::English);
+    ///
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.invalid_words, vec![(2, "persn".to_string()), (11, "ankel".to_string())]);
+    /// assert_eq!(report.checksum_valid, None);
+    /// ```
+    ///
+    /// [KeyPhrase::validate()]: ../keyphrase/struct.KeyPhrase.html#method.validate
+    pub fn validate_detailed(phrase: &str, lang: Language) -> ValidationReport {
+        let wordmap: &WordMap = lang.wordmap();
+
+        let word_count: usize = phrase.split(' ').count();
+        let word_count_valid: bool = KeyPhraseType::for_word_count(word_count).is_ok();
+
+        let invalid_words: Vec<(usize, String)> = phrase
+            .split(' ')
+            .enumerate()
+            .filter(|&(_, word): &(usize, &str)| wordmap.get_bits(word).is_err())
+            .map(|(i, word): (usize, &str)| (i, word.to_string()))
+            .collect();
+
+        let language_hints: Vec<Language> = if invalid_words.is_empty() {
+            Vec::new()
+        } else {
+            Language::all()
+                .filter(|&other: &Language| other != lang)
+                .filter(|other: &Language| {
+                    let wordmap: &WordMap = other.wordmap();
+
+                    phrase
+                        .split(' ')
+                        .all(|word: &str| wordmap.get_bits(word).is_ok())
+                })
+                .collect()
+        };
+
+        let checksum_valid: Option<bool> = if word_count_valid && invalid_words.is_empty() {
+            Some(KeyPhrase::phrase_to_entropy(phrase, lang, &Sha256Checksum).is_ok())
+        } else {
+            None
+        };
+
+        ValidationReport {
+            word_count,
+            word_count_valid,
+            invalid_words,
+            language_hints,
+            checksum_valid,
+        }
+    }
+
+    /// Validate many keyphrases of the same language
+    ///
+    /// Every phrase is validated like [`KeyPhrase::validate()`][KeyPhrase::validate()], the
+    /// wordlist is only looked up once. An invalid phrase doesn't stop the validation of the
+    /// others, the result has one entry per phrase, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, Language};
+    ///
+    /// let phrases = [
+    ///     "park remain person kitchen mule spell knee armed position rail grid ankle",
+    ///     "park remain person kitchen mule spell knee armed position rail grid park",
+    /// ];
+    ///
+    /// let results = KeyPhrase::validate_batch(&phrases, Language::English);
+    ///
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].is_err());
+    /// ```
+    ///
+    /// [KeyPhrase::validate()]: ../keyphrase/struct.KeyPhrase.html#method.validate
+    pub fn validate_batch(phrases: &[&str], lang: Language) -> Vec<Result<(), Error>> {
+        let wordmap: &WordMap = lang.wordmap();
+
+        phrases
+            .iter()
+            .map(|phrase: &&str| validate_with(phrase, wordmap))
+            .collect()
+    }
+
+    /// Same as [`KeyPhrase::validate_batch()`][KeyPhrase::validate_batch()], validating the
+    /// phrases on all the cores
+    ///
+    /// [KeyPhrase::validate_batch()]: ../keyphrase/struct.KeyPhrase.html#method.validate_batch
+    #[cfg(feature = "parallel")]
+    pub fn validate_batch_parallel(phrases: &[&str], lang: Language) -> Vec<Result<(), Error>> {
+        let wordmap: &WordMap = lang.wordmap();
+
+        phrases
+            .par_iter()
+            .map(|phrase: &&str| validate_with(phrase, wordmap))
+            .collect()
+    }
+}
+
+/// Validate a phrase with an already looked up wordmap
+fn validate_with(phrase: &str, wordmap: &WordMap) -> Result<(), Error> {
+    KeyPhrase::words_to_entropy(phrase, wordmap, &Sha256Checksum)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_detailed_valid() {
+        let report: ValidationReport = KeyPhrase::validate_detailed(
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            Language::English,
+        );
+
+        assert!(report.is_valid());
+        assert_eq!(report.word_count, 12);
+        assert!(report.invalid_words.is_empty());
+    }
+
+    #[test]
+    fn validate_detailed_invalid() {
+        let report: ValidationReport = KeyPhrase::validate_detailed(
+            "park remain person kitchen mule spell knee armed position rail grid park",
+            Language::English,
+        );
+
+        assert_eq!(report.checksum_valid, Some(false));
+
+        let report: ValidationReport =
+            KeyPhrase::validate_detailed("park remain persn kitchen", Language::English);
+
+        assert!(!report.word_count_valid);
+        assert_eq!(report.invalid_words, vec![(2, "persn".to_string())]);
+        assert_eq!(report.checksum_valid, None);
+    }
+
+    #[cfg(feature = "french")]
+    #[test]
+    fn validate_detailed_language_hints() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words12, Language::French);
+
+        let report: ValidationReport =
+            KeyPhrase::validate_detailed(keyphrase.phrase(), Language::English);
+
+        assert!(report.language_hints.contains(&Language::French));
+    }
+
+    #[test]
+    fn validate_words_only() {
+        // The checksum is not checked
+        let words: Vec<bool> = KeyPhrase::validate_words(
+            "park remain person kitchen mule spell knee armed position rail grid park",
+            Language::English,
+        )
+        .unwrap();
+
+        assert_eq!(words, vec![true; 12]);
+
+        assert!(KeyPhrase::validate_words("", Language::English).is_err());
+        assert!(KeyPhrase::validate_words(&["park"; 25].join(" "), Language::English).is_err());
+    }
+    #[test]
+    fn validate_batch_per_item() {
+        let phrases: [&str; 3] = [
+            "park remain person kitchen mule spell knee armed position rail grid ankle",
+            "park remain person kitchen mule spell knee armed position rail grid",
+            "park remain persn kitchen mule spell knee armed position rail grid ankle",
+        ];
+
+        let results: Vec<Result<(), Error>> =
+            KeyPhrase::validate_batch(&phrases, Language::English);
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+
+        match results[2] {
+            Err(ref error) => match error.kind() {
+                ErrorKind::InvalidWord { index, .. } => assert_eq!(*index, 2),
+                _ => panic!("unexpected error: {}", error),
+            },
+            Ok(()) => panic!("phrase should be invalid"),
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            let parallel: Vec<bool> =
+                KeyPhrase::validate_batch_parallel(&phrases, Language::English)
+                    .iter()
+                    .map(|result: &Result<(), Error>| result.is_ok())
+                    .collect();
+
+            assert_eq!(parallel, vec![true, false, false]);
+        }
+    }
+}
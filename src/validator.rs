@@ -0,0 +1,250 @@
+//! Validating a phrase word by word, as the user types it.
+
+use crate::checksum::Sha256Checksum;
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordMap};
+use crate::util::{BitWriter, Bits11};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Longest phrase defined by BIP39
+const MAX_WORDS: usize = 24;
+
+/// Result of pushing a word into a [`PhraseValidator`][PhraseValidator]
+///
+/// [PhraseValidator]: ./struct.PhraseValidator.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordStatus {
+    /// The word is not in the wordlist, it was not added
+    Unknown,
+    /// The phrase is already as long as it can be, the word was not added
+    TooManyWords,
+    /// The word was added, `remaining` more words are needed to reach the next valid length
+    Accepted { remaining: usize },
+    /// The word was added and the phrase has a valid length, but the checksum doesn't match
+    ChecksumMismatch,
+    /// The word was added and the phrase is a valid keyphrase
+    Complete,
+}
+
+/// Incremental validator for phrases typed one word at a time
+///
+/// Live recovery UIs can check each word as soon as it is typed, instead of parsing the whole
+/// phrase on every keystroke. The validator only keeps the wordlist indices of the words, and
+/// checks the checksum every time the phrase reaches a valid length.
+///
+/// Without a [`KeyPhraseType`][KeyPhraseType], every valid length is accepted, so a phrase can
+/// be `WordStatus::Complete` at 12 words and again at 15 words.
+///
+/// # Example
+///
+/// ```
+/// use keyphrase::{KeyPhraseType, Language, PhraseValidator, WordStatus};
+///
+/// let mut validator = PhraseValidator::with_type(KeyPhraseType::Words12, Language::English);
+///
+/// assert_eq!(validator.push_word("park"), WordStatus::Accepted { remaining: 11 });
+/// assert_eq!(validator.push_word("remian"), WordStatus::Unknown);
+///
+/// for word in "remain person kitchen mule spell knee armed position rail grid".split(' ') {
+///     validator.push_word(word);
+/// }
+///
+/// assert_eq!(validator.push_word("ankle"), WordStatus::Complete);
+///
+/// let keyphrase = validator.finish().unwrap();
+/// ```
+///
+/// [KeyPhraseType]: ../keyphrase_type/enum.KeyPhraseType.html
+pub struct PhraseValidator {
+    lang: Language,
+    keyphrase_type: Option<KeyPhraseType>,
+    indices: Vec<u16>,
+}
+
+impl PhraseValidator {
+    /// Create a validator accepting every valid length
+    pub fn new(lang: Language) -> PhraseValidator {
+        PhraseValidator {
+            lang,
+            keyphrase_type: None,
+            indices: Vec::with_capacity(MAX_WORDS),
+        }
+    }
+
+    /// Create a validator expecting a specific length
+    pub fn with_type(keyphrase_type: KeyPhraseType, lang: Language) -> PhraseValidator {
+        PhraseValidator {
+            lang,
+            keyphrase_type: Some(keyphrase_type),
+            indices: Vec::with_capacity(keyphrase_type.word_count()),
+        }
+    }
+
+    /// Add the next word of the phrase
+    pub fn push_word(&mut self, word: &str) -> WordStatus {
+        let wordmap: &WordMap = self.lang.wordmap();
+
+        let bits: Bits11 = match wordmap.get_bits(word) {
+            Ok(bits) => bits,
+            Err(_) => return WordStatus::Unknown,
+        };
+
+        let max_words: usize = self
+            .keyphrase_type
+            .map_or(MAX_WORDS, |ty: KeyPhraseType| ty.word_count());
+
+        if self.indices.len() >= max_words {
+            return WordStatus::TooManyWords;
+        }
+
+        self.indices.push(bits.into());
+
+        self.status()
+    }
+
+    /// Remove the last word of the phrase, for example when the user deletes it
+    pub fn pop_word(&mut self) {
+        #[cfg(feature = "zeroize")]
+        {
+            if let Some(last) = self.indices.last_mut() {
+                last.zeroize();
+            }
+        }
+
+        self.indices.pop();
+    }
+
+    /// Number of words pushed so far
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether no word was pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Create the [`KeyPhrase`][KeyPhrase] from the words pushed so far
+    ///
+    /// Returns the same errors as [`KeyPhrase::from_phrase()`][KeyPhrase::from_phrase()] for an
+    /// invalid length or checksum.
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    /// [KeyPhrase::from_phrase()]: ../keyphrase/struct.KeyPhrase.html#method.from_phrase
+    pub fn finish(self) -> Result<KeyPhrase, Error> {
+        if let Some(ty) = self.keyphrase_type {
+            if self.indices.len() != ty.word_count() {
+                Err(ErrorKind::InvalidWordLength {
+                    words: self.indices.len(),
+                    expected: match ty {
+                        KeyPhraseType::Words12 => "12",
+                        KeyPhraseType::Words15 => "15",
+                        KeyPhraseType::Words18 => "18",
+                        KeyPhraseType::Words21 => "21",
+                        KeyPhraseType::Words24 => "24",
+                    },
+                })?;
+            }
+        }
+
+        KeyPhrase::from_word_indices(&self.indices, self.lang)
+    }
+
+    fn status(&self) -> WordStatus {
+        let count: usize = self.indices.len();
+
+        let target: usize = match self.keyphrase_type {
+            Some(ty) => ty.word_count(),
+            // Valid lengths are multiples of 3, from 12 to 24
+            None => (count.div_ceil(3) * 3).max(12),
+        };
+
+        if count < target {
+            return WordStatus::Accepted {
+                remaining: target - count,
+            };
+        }
+
+        let mut bits = BitWriter::with_capacity(count * 11);
+
+        for &index in &self.indices {
+            bits.push(Bits11::from(index));
+        }
+
+        match KeyPhrase::bits_to_entropy(bits, &Sha256Checksum) {
+            Ok(_) => WordStatus::Complete,
+            Err(_) => WordStatus::ChecksumMismatch,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PhraseValidator {
+    fn drop(&mut self) {
+        self.indices.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "rand", feature = "getrandom"))]
+    fn validator_any_length() {
+        let keyphrase: KeyPhrase = KeyPhrase::new(KeyPhraseType::Words15, Language::English);
+        let mut validator: PhraseValidator = PhraseValidator::new(Language::English);
+        let words: Vec<&str> = keyphrase.phrase().split(' ').collect();
+
+        for (i, word) in words[..11].iter().enumerate() {
+            assert_eq!(
+                validator.push_word(word),
+                WordStatus::Accepted { remaining: 11 - i }
+            );
+        }
+
+        // Unlikely to be a valid 12 word phrase, but possible
+        assert_ne!(validator.push_word(words[11]), WordStatus::Unknown);
+        assert_eq!(
+            validator.push_word(words[12]),
+            WordStatus::Accepted { remaining: 2 }
+        );
+        assert_eq!(
+            validator.push_word(words[13]),
+            WordStatus::Accepted { remaining: 1 }
+        );
+        assert_eq!(validator.push_word(words[14]), WordStatus::Complete);
+
+        assert_eq!(validator.finish().unwrap().phrase(), keyphrase.phrase());
+    }
+
+    #[test]
+    fn validator_fixed_length() {
+        let mut validator: PhraseValidator =
+            PhraseValidator::with_type(KeyPhraseType::Words12, Language::English);
+
+        for word in "park remain person kitchen mule spell knee armed position rail grid".split(' ')
+        {
+            validator.push_word(word);
+        }
+
+        assert_eq!(validator.push_word("park"), WordStatus::ChecksumMismatch);
+        assert_eq!(validator.push_word("ankle"), WordStatus::TooManyWords);
+
+        validator.pop_word();
+
+        assert_eq!(validator.len(), 11);
+        assert_eq!(validator.push_word("ankle"), WordStatus::Complete);
+        assert!(validator.finish().is_ok());
+
+        let mut validator: PhraseValidator =
+            PhraseValidator::with_type(KeyPhraseType::Words24, Language::English);
+
+        validator.push_word("park");
+
+        assert!(validator.finish().is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Generating [`KeyPhrase`][KeyPhrase]s until one matches a predicate.
+//!
+//! [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+
+use crate::crypto::{sha256_first_byte, DefaultRng};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::{Language, WordList};
+use crate::util::{Bits11, IterExt};
+use rand_core::RngCore;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Generate phrases on the current thread until one matches, or `attempts` (shared between
+/// threads) reaches `max_attempts`
+fn search<P>(
+    keyphrase_type: KeyPhraseType,
+    lang: Language,
+    predicate: &P,
+    max_attempts: usize,
+    attempts: &AtomicUsize,
+) -> Option<KeyPhrase>
+where
+    P: Fn(&str) -> bool,
+{
+    let wordlist: &WordList = lang.wordlist();
+    let mut rng: DefaultRng = DefaultRng;
+
+    // Reused for every attempt, only a match gets turned into a `KeyPhrase`
+    let mut entropy: Vec<u8> = vec![0; keyphrase_type.entropy_bits() / 8 + 1];
+    let mut phrase: String = String::with_capacity(keyphrase_type.word_count() * 9);
+    let mut found: Option<KeyPhrase> = None;
+
+    while attempts.fetch_add(1, Ordering::Relaxed) < max_attempts {
+        let entropy_bytes: usize = entropy.len() - 1;
+
+        rng.fill_bytes(&mut entropy[..entropy_bytes]);
+        entropy[entropy_bytes] = sha256_first_byte(&entropy[..entropy_bytes]);
+
+        phrase.clear();
+
+        let words = entropy
+            .iter()
+            .bits()
+            .take(keyphrase_type.word_count())
+            .map(|bits: Bits11| wordlist.get_word(bits));
+
+        for (i, word) in words.enumerate() {
+            if i != 0 {
+                phrase.push(' ');
+            }
+
+            phrase.push_str(word);
+        }
+
+        if predicate(&phrase) {
+            found = Some(KeyPhrase::from_entropy_unchecked(
+                &entropy[..entropy_bytes],
+                lang,
+            ));
+
+            // Stop the other threads
+            attempts.fetch_max(max_attempts, Ordering::Relaxed);
+
+            break;
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    {
+        entropy.zeroize();
+        phrase.zeroize();
+    }
+
+    found
+}
+
+impl KeyPhrase {
+    /// Generate [`KeyPhrase`][KeyPhrase]s until one matches `predicate`
+    ///
+    /// The predicate is called with the phrase of every candidate, for example to look for
+    /// phrases starting with a given word for a demo. The RNG and buffers are reused between
+    /// attempts. Returns `None` if no match was found in `max_attempts` attempts.
+    ///
+    /// Every constraint on the phrase makes it easier to guess: a phrase whose first word is
+    /// known has 11 bits less of entropy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use keyphrase::{KeyPhrase, KeyPhraseType, Language};
+    ///
+    /// let keyphrase = KeyPhrase::generate_matching(
+    ///     KeyPhraseType::Words12,
+    ///     Language::English,
+    ///     |phrase| phrase.starts_with("earth "),
+    ///     100_000,
+    /// );
+    ///
+    /// assert!(keyphrase.unwrap().phrase().starts_with("earth "));
+    /// ```
+    ///
+    /// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+    pub fn generate_matching<P>(
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+        predicate: P,
+        max_attempts: usize,
+    ) -> Option<KeyPhrase>
+    where
+        P: Fn(&str) -> bool,
+    {
+        let attempts: AtomicUsize = AtomicUsize::new(0);
+
+        search(keyphrase_type, lang, &predicate, max_attempts, &attempts)
+    }
+
+    /// Same as [`KeyPhrase::generate_matching()`][KeyPhrase::generate_matching()], spreading
+    /// the attempts across `threads` threads
+    ///
+    /// [KeyPhrase::generate_matching()]: ../keyphrase/struct.KeyPhrase.html#method.generate_matching
+    pub fn generate_matching_parallel<P>(
+        keyphrase_type: KeyPhraseType,
+        lang: Language,
+        predicate: P,
+        max_attempts: usize,
+        threads: usize,
+    ) -> Option<KeyPhrase>
+    where
+        P: Fn(&str) -> bool + Sync,
+    {
+        let attempts: AtomicUsize = AtomicUsize::new(0);
+        let found: Mutex<Option<KeyPhrase>> = Mutex::new(None);
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| {
+                    if let Some(keyphrase) =
+                        search(keyphrase_type, lang, &predicate, max_attempts, &attempts)
+                    {
+                        let mut found = found.lock().unwrap_or_else(|error| error.into_inner());
+
+                        found.get_or_insert(keyphrase);
+                    }
+                });
+            }
+        });
+
+        found
+            .into_inner()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_matching_first_word() {
+        let keyphrase: KeyPhrase = KeyPhrase::generate_matching(
+            KeyPhraseType::Words24,
+            Language::English,
+            |phrase: &str| phrase.starts_with("earth "),
+            1_000_000,
+        )
+        .unwrap();
+
+        assert!(keyphrase.phrase().starts_with("earth "));
+        assert!(KeyPhrase::validate(keyphrase.phrase(), Language::English).is_ok());
+
+        let keyphrase: KeyPhrase = KeyPhrase::generate_matching_parallel(
+            KeyPhraseType::Words12,
+            Language::English,
+            |phrase: &str| phrase.ends_with(" zoo"),
+            1_000_000,
+            4,
+        )
+        .unwrap();
+
+        assert!(keyphrase.phrase().ends_with(" zoo"));
+    }
+
+    #[test]
+    fn generate_matching_gives_up() {
+        assert!(KeyPhrase::generate_matching(
+            KeyPhraseType::Words12,
+            Language::English,
+            |_: &str| false,
+            100,
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,267 @@
+//! JavaScript bindings built with `wasm-bindgen`, enabled by the `wasm` feature.
+//!
+//! The wrappers keep the names of the Rust API in camelCase: `KeyPhrase`, `Seed`, `Language`,
+//! `validatePhrase()` and `validateDetailed()`. Every error is thrown as a JavaScript `Error`
+//! with the message of its `ErrorKind`.
+//!
+//! The crate doesn't build a `cdylib` by default, so that its dependents don't build one too.
+//! Build the WebAssembly module with
+//! `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib`,
+//! then generate the JavaScript glue with `wasm-bindgen`. The feature makes the default RNG read
+//! from `crypto.getRandomValues()`.
+//!
+//! ```js
+//! import { KeyPhrase, Language, Seed } from "keyphrase";
+//!
+//! const keyphrase = new KeyPhrase(12, Language.English);
+//! const seed = Seed.bip39(keyphrase, "");
+//!
+//! console.log(keyphrase.phrase, seed.toHex());
+//! ```
+
+use crate::error::{Error, ErrorKind};
+use crate::keyphrase::KeyPhrase;
+use crate::keyphrase_type::KeyPhraseType;
+use crate::language::Language;
+use crate::seed::Seed;
+use crate::validation::ValidationReport;
+use wasm_bindgen::prelude::*;
+
+/// The languages of [`Language`][Language], with their stable codes
+///
+/// Every language is listed, but those whose feature isn't enabled throw an error when used.
+///
+/// [Language]: ../language/enum.Language.html
+#[wasm_bindgen(js_name = Language)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmLanguage {
+    English = 0,
+    ChineseSimplified = 1,
+    ChineseTraditional = 2,
+    French = 3,
+    Italian = 4,
+    Japanese = 5,
+    Korean = 6,
+    Spanish = 7,
+}
+
+impl WasmLanguage {
+    fn to_language(self) -> Result<Language, JsError> {
+        Language::from_code(self as u8)
+            .ok_or_else(|| js_error(ErrorKind::UnsupportedLanguage.into()))
+    }
+
+    fn from_language(lang: Language) -> WasmLanguage {
+        match lang.code() {
+            1 => WasmLanguage::ChineseSimplified,
+            2 => WasmLanguage::ChineseTraditional,
+            3 => WasmLanguage::French,
+            4 => WasmLanguage::Italian,
+            5 => WasmLanguage::Japanese,
+            6 => WasmLanguage::Korean,
+            7 => WasmLanguage::Spanish,
+            _ => WasmLanguage::English,
+        }
+    }
+}
+
+/// A [`KeyPhrase`][KeyPhrase] for JavaScript
+///
+/// [KeyPhrase]: ../keyphrase/struct.KeyPhrase.html
+#[wasm_bindgen(js_name = KeyPhrase)]
+pub struct WasmKeyPhrase {
+    keyphrase: KeyPhrase,
+}
+
+#[wasm_bindgen(js_class = KeyPhrase)]
+impl WasmKeyPhrase {
+    /// Generate a new keyphrase of 12, 15, 18, 21 or 24 words
+    #[wasm_bindgen(constructor)]
+    pub fn new(word_count: usize, language: WasmLanguage) -> Result<WasmKeyPhrase, JsError> {
+        let keyphrase_type: KeyPhraseType =
+            KeyPhraseType::for_word_count(word_count).map_err(js_error)?;
+        let keyphrase: KeyPhrase =
+            KeyPhrase::try_new(keyphrase_type, language.to_language()?).map_err(js_error)?;
+
+        Ok(WasmKeyPhrase { keyphrase })
+    }
+
+    /// Create a keyphrase from an existing phrase, checking its checksum
+    #[wasm_bindgen(js_name = fromPhrase)]
+    pub fn from_phrase(phrase: &str, language: WasmLanguage) -> Result<WasmKeyPhrase, JsError> {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_phrase(phrase, language.to_language()?).map_err(js_error)?;
+
+        Ok(WasmKeyPhrase { keyphrase })
+    }
+
+    /// Create a keyphrase from its entropy, 16 to 32 bytes
+    #[wasm_bindgen(js_name = fromEntropy)]
+    pub fn from_entropy(entropy: &[u8], language: WasmLanguage) -> Result<WasmKeyPhrase, JsError> {
+        let keyphrase: KeyPhrase =
+            KeyPhrase::from_entropy(entropy, language.to_language()?).map_err(js_error)?;
+
+        Ok(WasmKeyPhrase { keyphrase })
+    }
+
+    /// The words of the keyphrase, separated by spaces
+    #[wasm_bindgen(getter)]
+    pub fn phrase(&self) -> String {
+        self.keyphrase.phrase().to_string()
+    }
+
+    /// The entropy of the keyphrase, as a `Uint8Array`
+    #[wasm_bindgen(getter)]
+    pub fn entropy(&self) -> Vec<u8> {
+        self.keyphrase.entropy().to_vec()
+    }
+
+    /// The language of the keyphrase
+    #[wasm_bindgen(getter)]
+    pub fn language(&self) -> WasmLanguage {
+        WasmLanguage::from_language(self.keyphrase.language())
+    }
+}
+
+/// A [`Seed`][Seed] for JavaScript
+///
+/// [Seed]: ../seed/struct.Seed.html
+#[wasm_bindgen(js_name = Seed)]
+pub struct WasmSeed {
+    seed: Seed,
+}
+
+#[wasm_bindgen(js_class = Seed)]
+impl WasmSeed {
+    /// Derive the seed of a keyphrase as defined by BIP39, like any BIP39 wallet
+    pub fn bip39(keyphrase: &WasmKeyPhrase, password: &str) -> WasmSeed {
+        WasmSeed {
+            seed: Seed::new_bip39(&keyphrase.keyphrase, password),
+        }
+    }
+
+    /// Derive the legacy seed of a keyphrase, salted with `"keyphrase"`, as `Seed::new()` does
+    #[cfg(not(feature = "strict-bip39-only"))]
+    pub fn legacy(keyphrase: &WasmKeyPhrase, password: &str) -> WasmSeed {
+        WasmSeed {
+            seed: Seed::new(&keyphrase.keyphrase, password),
+        }
+    }
+
+    /// The 64 bytes of the seed, as a `Uint8Array`
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.seed.as_bytes().to_vec()
+    }
+
+    /// The seed as lowercase hex
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        self.seed.to_hex()
+    }
+}
+
+/// A [`ValidationReport`][ValidationReport] for JavaScript
+///
+/// [ValidationReport]: ../validation/struct.ValidationReport.html
+#[wasm_bindgen(js_name = ValidationReport)]
+pub struct WasmValidationReport {
+    report: ValidationReport,
+}
+
+#[wasm_bindgen(js_class = ValidationReport)]
+impl WasmValidationReport {
+    /// Whether the phrase is a valid keyphrase
+    #[wasm_bindgen(getter, js_name = isValid)]
+    pub fn is_valid(&self) -> bool {
+        self.report.is_valid()
+    }
+
+    /// Number of words in the phrase
+    #[wasm_bindgen(getter, js_name = wordCount)]
+    pub fn word_count(&self) -> usize {
+        self.report.word_count
+    }
+
+    /// Positions of the words that are not in the wordlist, starting at 0
+    #[wasm_bindgen(getter, js_name = invalidWords)]
+    pub fn invalid_words(&self) -> Vec<u32> {
+        self.report
+            .invalid_words
+            .iter()
+            .map(|&(position, _): &(usize, String)| position as u32)
+            .collect()
+    }
+
+    /// Whether the checksum matched, `undefined` if it couldn't be checked
+    #[wasm_bindgen(getter, js_name = checksumValid)]
+    pub fn checksum_valid(&self) -> Option<bool> {
+        self.report.checksum_valid
+    }
+}
+
+/// Check that a phrase is a valid keyphrase, throwing the first problem found
+#[wasm_bindgen(js_name = validatePhrase)]
+pub fn validate_phrase(phrase: &str, language: WasmLanguage) -> Result<(), JsError> {
+    KeyPhrase::validate(phrase, language.to_language()?).map_err(js_error)
+}
+
+/// Report every problem of a phrase at once, for forms
+#[wasm_bindgen(js_name = validateDetailed)]
+pub fn validate_detailed(
+    phrase: &str,
+    language: WasmLanguage,
+) -> Result<WasmValidationReport, JsError> {
+    Ok(WasmValidationReport {
+        report: KeyPhrase::validate_detailed(phrase, language.to_language()?),
+    })
+}
+
+/// Turn an error into a JavaScript `Error` with its message
+fn js_error(error: Error) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wasm_keyphrase_seed() {
+        let phrase: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let keyphrase: WasmKeyPhrase =
+            WasmKeyPhrase::from_phrase(phrase, WasmLanguage::English).unwrap();
+
+        assert_eq!(keyphrase.phrase(), phrase);
+        assert_eq!(keyphrase.language(), WasmLanguage::English);
+        assert_eq!(
+            WasmKeyPhrase::from_entropy(&keyphrase.entropy(), WasmLanguage::English)
+                .unwrap()
+                .phrase(),
+            phrase
+        );
+        assert_eq!(
+            &WasmSeed::bip39(&keyphrase, "TREZOR").to_hex()[..8],
+            "c55257c3"
+        );
+        assert_eq!(
+            WasmKeyPhrase::new(24, WasmLanguage::English)
+                .unwrap()
+                .phrase()
+                .split(' ')
+                .count(),
+            24
+        );
+    }
+
+    #[test]
+    fn wasm_validation() {
+        let report: WasmValidationReport =
+            validate_detailed("abandon abandon abandn", WasmLanguage::English).unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(report.word_count(), 3);
+        assert_eq!(report.invalid_words(), vec![2]);
+        assert_eq!(report.checksum_valid(), None);
+    }
+}
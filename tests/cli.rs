@@ -0,0 +1,162 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+fn keyphrase(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_keyphrase"))
+        .args(args)
+        .output()
+        .expect("Can run the keyphrase binary")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn cli_generate() {
+    let output: Output = keyphrase(&["generate", "--words", "12"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).split_whitespace().count(), 12);
+
+    let output: Output = keyphrase(&["generate", "--lang=english", "--format", "numbered"]);
+
+    assert_eq!(stdout(&output).lines().count(), 24);
+    assert!(stdout(&output).starts_with(" 1. "));
+}
+
+#[test]
+fn cli_generate_from_entropy() {
+    let output: Output = keyphrase(&[
+        "generate",
+        "--entropy-hex",
+        "00000000000000000000000000000000",
+        "--json",
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output).trim(),
+        "{\"phrase\":\"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about\",\"words\":12,\"language\":\"english\",\"entropy\":\"00000000000000000000000000000000\"}"
+    );
+}
+
+#[test]
+fn cli_generate_errors() {
+    // usage errors, then rejected entropy
+    assert_eq!(
+        keyphrase(&["generate", "--words", "13"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        keyphrase(&["generate", "--lang", "klingon"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(keyphrase(&["generate", "--words"]).status.code(), Some(2));
+    assert_eq!(keyphrase(&["frobnicate"]).status.code(), Some(2));
+    assert_eq!(
+        keyphrase(&[
+            "generate",
+            "--words",
+            "24",
+            "--entropy-hex",
+            "00000000000000000000000000000000"
+        ])
+        .status
+        .code(),
+        Some(2)
+    );
+    assert_eq!(
+        keyphrase(&["generate", "--entropy-hex", "0000"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn cli_validate() {
+    let output: Output = keyphrase(&[
+        "validate",
+        "park remain person kitchen mule spell knee armed position rail grid ankle",
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).trim(), "valid");
+
+    let output: Output = keyphrase(&[
+        "validate",
+        "--json",
+        "park remain person kitchen mule spell knee armed position rail grid ankel",
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output)
+        .contains("\"invalid_words\":[{\"position\":12,\"word\":\"ankel\"}]"));
+    assert!(stdout(&output).contains("\"checksum_valid\":null"));
+}
+
+#[test]
+fn cli_recover() {
+    let output: Output = keyphrase(&[
+        "recover",
+        "park remain person kitchen mule spell knee armed position rail grid ?",
+    ]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output)
+        .lines()
+        .any(|line: &str| line.ends_with("grid ankle")));
+
+    let output: Output = keyphrase(&[
+        "recover",
+        "--json",
+        "--limit",
+        "1",
+        "park remain person kitchen mule spell knee armed position rail grid ankel",
+    ]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("{\"count\":1,"));
+    assert_eq!(
+        keyphrase(&["recover", "--limit", "many", "?"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn cli_seed() {
+    let output: Output = keyphrase(&[
+        "seed",
+        "--passphrase",
+        "TREZOR",
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output).trim(),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+    assert_eq!(
+        keyphrase(&["seed", "--scheme", "electrum", "abandon"])
+            .status
+            .code(),
+        Some(2)
+    );
+    assert_eq!(
+        keyphrase(&["seed", "abandon abandon abandon"]).status.code(),
+        Some(1)
+    );
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn cli_wizard_needs_a_terminal() {
+    assert_eq!(keyphrase(&["wizard"]).status.code(), Some(1));
+    assert_eq!(
+        keyphrase(&["wizard", "--words", "13"]).status.code(),
+        Some(2)
+    );
+}